@@ -6,7 +6,7 @@ use medic_src::AppResult;
 use medic_src::cli::Flags;
 use medic_src::config::Manifest;
 use medic_src::context::Context;
-use medic_src::runnable::run;
+use medic_src::state;
 
 pub fn run_steps(
   manifest: Manifest,
@@ -15,12 +15,7 @@ pub fn run_steps(
   context: &Context,
 ) -> AppResult<()> {
   match manifest.audit {
-    Some(audit) => {
-      for step in audit.checks {
-        run(step, progress, &mut flags, context)?;
-      }
-      AppResult::Ok(())
-    }
+    Some(audit) => state::run_steps("audit", audit.checks, progress, &mut flags, context),
     None => AppResult::Err(Some("No audit checks found in medic config.".into())),
   }
 }
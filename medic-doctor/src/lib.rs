@@ -6,7 +6,7 @@ use medic_src::AppResult;
 use medic_src::cli::Flags;
 use medic_src::config::Manifest;
 use medic_src::context::Context;
-use medic_src::runnable::run;
+use medic_src::state;
 
 pub fn run_checks(
   manifest: Manifest,
@@ -15,12 +15,7 @@ pub fn run_checks(
   context: &Context,
 ) -> AppResult<()> {
   match manifest.doctor {
-    Some(doctor) => {
-      for check in doctor.checks {
-        run(check, progress, &mut flags, context)?;
-      }
-      AppResult::Ok(())
-    }
+    Some(doctor) => state::run_steps("doctor", doctor.checks, progress, &mut flags, context),
     None => AppResult::Err(Some("No doctor checks found in medic config.".into())),
   }
 }
@@ -0,0 +1,186 @@
+// @related [tests](medic-init/src/add_test.rs)
+
+use medic_src::config::discovery;
+use medic_src::error::MedicError;
+use medic_src::theme::current_theme;
+use medic_src::{AppResult, AuditStep, DoctorStep, OutdatedCheck, ShipitStep, Step};
+use std::path::PathBuf;
+use toml_edit::{Array, DocumentMut, Item, Table, Value};
+
+/// Adds `step` to the steps of `section` in the manifest at `path`, keeping
+/// the comments and formatting of the rest of the file. Adding a step that
+/// is already present leaves the file untouched.
+pub fn add_step(path: PathBuf, section: String, step: String) -> AppResult<()> {
+  let path = discovery::locate(&path)?;
+  if !path.is_file() {
+    return AppResult::Err(Some(
+      format!("File {path:?} does not exist.\r\n       Use `medic init` to create one.").into(),
+    ));
+  }
+
+  let manifest = std::fs::read_to_string(&path)?;
+  match insert(&manifest, &section, &step)? {
+    Some(updated) => {
+      std::fs::write(&path, updated)?;
+      println!(
+        "{} {path:?}",
+        current_theme()
+          .success_style
+          .apply_to(format!("Added step to [{section}] in"))
+      );
+    }
+    None => println!(
+      "{} {path:?}",
+      current_theme()
+        .warning_style
+        .apply_to(format!("Step already present in [{section}] of"))
+    ),
+  }
+
+  AppResult::Ok(())
+}
+
+/// Returns `manifest` with `step` appended to the list of `section`, or
+/// `None` when an equal step is already in the list.
+pub fn insert(manifest: &str, section: &str, step: &str) -> Result<Option<String>, MedicError> {
+  let key = list_key(section)?;
+  let step = parse_step(section, step)?;
+
+  let mut document: DocumentMut = manifest
+    .parse()
+    .map_err(|err| MedicError::from(format!("Unable to parse manifest: {err}")))?;
+
+  let mut table = document.as_table_mut();
+  for name in section.split('.') {
+    let mut implicit = Table::new();
+    implicit.set_implicit(true);
+    table = table
+      .entry(name)
+      .or_insert(Item::Table(implicit))
+      .as_table_mut()
+      .ok_or_else(|| MedicError::from(format!("`{section}` in manifest must be a table")))?;
+  }
+
+  let list = table
+    .entry(key)
+    .or_insert(Item::Value(Value::Array(Array::new())))
+    .as_array_mut()
+    .ok_or_else(|| MedicError::from(format!("`{key}` in [{section}] must be a list of steps")))?;
+
+  if list
+    .iter()
+    .any(|existing| to_toml(existing) == to_toml(&step))
+  {
+    return Ok(None);
+  }
+  push(list, step);
+
+  Ok(Some(document.to_string()))
+}
+
+fn list_key(section: &str) -> Result<&'static str, MedicError> {
+  match section {
+    "audit" | "doctor" | "outdated" | "test" => Ok("checks"),
+    "shipit" | "update" => Ok("steps"),
+    workflow if workflow.strip_prefix("workflows.").is_some_and(|name| !name.is_empty()) => Ok("steps"),
+    _ => Err(
+      format!(
+        "Unknown section `{section}`. Steps may be added to audit, doctor, outdated, shipit, test, update or workflows.<name>"
+      )
+      .into(),
+    ),
+  }
+}
+
+// Steps are checked against the section they are added to, so that a typo
+// is reported now rather than the next time the manifest is loaded. Steps
+// that `use` a definition are only known once definitions are applied.
+fn parse_step(section: &str, step: &str) -> Result<Value, MedicError> {
+  let value: Value = step
+    .parse()
+    .ok()
+    .filter(Value::is_inline_table)
+    .ok_or_else(|| {
+      MedicError::from(format!(
+        "Step must be an inline table, e.g. '{{ check = \"homebrew\" }}', got: {step}"
+      ))
+    })?;
+
+  let parsed = to_toml(&value).ok_or_else(|| MedicError::from(format!("Unable to parse step: {step}")))?;
+  if parsed.get("use").is_none() {
+    let result = match section {
+      "audit" => parsed.try_into::<AuditStep>().map(drop),
+      "doctor" => parsed.try_into::<DoctorStep>().map(drop),
+      "outdated" => parsed.try_into::<OutdatedCheck>().map(drop),
+      "shipit" => parsed.try_into::<ShipitStep>().map(drop),
+      _ => parsed.try_into::<Step>().map(drop),
+    };
+    result.map_err(|err| MedicError::from(format!("Invalid step for [{section}]: {}", err.message())))?;
+  }
+
+  Ok(value)
+}
+
+fn to_toml(value: &Value) -> Option<toml::Value> {
+  let mut value = value.clone();
+  value.decor_mut().clear();
+  toml::from_str::<toml::Table>(&format!("step = {value}"))
+    .ok()?
+    .remove("step")
+}
+
+// Lists written one step per line stay that way, with the new step indented
+// like the last one and placed after any commented-out steps at the end.
+fn push(list: &mut Array, mut step: Value) {
+  step.decor_mut().clear();
+
+  // Without a trailing comma, the newline before `]` belongs to the last step.
+  if let Some(last) = list.iter_mut().last() {
+    let suffix = last
+      .decor()
+      .suffix()
+      .and_then(|suffix| suffix.as_str())
+      .unwrap_or_default();
+    if suffix.contains('\n') && suffix.trim().is_empty() {
+      last.decor_mut().set_suffix("");
+      list.set_trailing("\n");
+    }
+  }
+
+  let multiline = list.is_empty()
+    || list.trailing().as_str().unwrap_or_default().contains('\n')
+    || list.iter().any(|value| prefix(value).contains('\n'));
+  if !multiline {
+    list.push_formatted(step);
+    list.fmt();
+    return;
+  }
+
+  let indent = list
+    .iter()
+    .last()
+    .map(prefix)
+    .and_then(|prefix| {
+      prefix
+        .rfind('\n')
+        .map(|newline| prefix[newline + 1..].to_string())
+    })
+    .unwrap_or_else(|| "  ".to_string());
+  let trailing = list.trailing().as_str().unwrap_or_default();
+  let comments = trailing
+    .rfind('\n')
+    .map_or("", |newline| &trailing[..newline]);
+
+  step.decor_mut().set_prefix(format!("{comments}\n{indent}"));
+  list.push_formatted(step);
+  list.set_trailing("\n");
+  list.set_trailing_comma(true);
+}
+
+fn prefix(value: &Value) -> &str {
+  value
+    .decor()
+    .prefix()
+    .and_then(|prefix| prefix.as_str())
+    .unwrap_or_default()
+}
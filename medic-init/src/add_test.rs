@@ -0,0 +1,146 @@
+// @related [subject](medic-init/src/add.rs)
+
+use super::add::*;
+use indoc::indoc;
+
+#[test]
+fn insert_appends_to_multiline_list() {
+  let manifest = indoc! {r#"
+    # Project checks
+    [doctor]
+    checks = [
+      { check = "homebrew" }, # keep me
+      { name = "Setup", shell = "bin/setup" },
+    ]
+
+    [test]
+    checks = []
+  "#};
+
+  let updated = insert(
+    manifest,
+    "doctor",
+    r#"{ check = "tool-versions", command = "plugin-installed" }"#,
+  )
+  .unwrap();
+  assert_eq!(
+    updated.unwrap(),
+    indoc! {r#"
+      # Project checks
+      [doctor]
+      checks = [
+        { check = "homebrew" }, # keep me
+        { name = "Setup", shell = "bin/setup" },
+        { check = "tool-versions", command = "plugin-installed" },
+      ]
+
+      [test]
+      checks = []
+    "#}
+  );
+}
+
+#[test]
+fn insert_places_step_after_commented_steps() {
+  let manifest = indoc! {r#"
+    [test]
+    checks = [
+      # {name = "Run tests", shell = "run my test suite"},
+    ]
+  "#};
+
+  let updated = insert(manifest, "test", r#"{ name = "Run tests", shell = "cargo test" }"#).unwrap();
+  assert_eq!(
+    updated.unwrap(),
+    indoc! {r#"
+      [test]
+      checks = [
+        # {name = "Run tests", shell = "run my test suite"},
+        { name = "Run tests", shell = "cargo test" },
+      ]
+    "#}
+  );
+}
+
+#[test]
+fn insert_creates_missing_sections() {
+  let manifest = "[doctor]\nchecks = []\n";
+
+  let updated = insert(manifest, "workflows.seed", r#"{ name = "Seed", shell = "bin/seed" }"#).unwrap();
+  assert_eq!(
+    updated.unwrap(),
+    indoc! {r#"
+      [doctor]
+      checks = []
+
+      [workflows.seed]
+      steps = [
+        { name = "Seed", shell = "bin/seed" },
+      ]
+    "#}
+  );
+}
+
+#[test]
+fn insert_skips_steps_already_present() {
+  let manifest = "[update]\nsteps = [\n  { step = \"git\", command = \"pull\" },\n]\n";
+
+  assert_eq!(
+    insert(manifest, "update", r#"{command="pull",step="git"}"#).unwrap(),
+    None
+  );
+}
+
+#[test]
+fn insert_rejects_invalid_steps() {
+  let manifest = "[doctor]\nchecks = []\n";
+
+  assert_eq!(
+    insert(manifest, "doctor", "check = 1")
+      .unwrap_err()
+      .to_string(),
+    "Step must be an inline table, e.g. '{ check = \"homebrew\" }', got: check = 1"
+  );
+  assert!(
+    insert(manifest, "outdated", r#"{ name = "Setup", shell = "bin/setup" }"#)
+      .unwrap_err()
+      .to_string()
+      .starts_with("Invalid step for [outdated]:")
+  );
+  assert_eq!(
+    insert(manifest, "deploy", r#"{ check = "homebrew" }"#)
+      .unwrap_err()
+      .to_string(),
+    "Unknown section `deploy`. Steps may be added to audit, doctor, outdated, shipit, test, update or workflows.<name>"
+  );
+}
+
+#[test]
+fn insert_allows_steps_using_definitions() {
+  let manifest = "[audit]\nchecks = []\n";
+
+  let updated = insert(manifest, "audit", r#"{ use = "format" }"#).unwrap();
+  assert_eq!(updated.unwrap(), "[audit]\nchecks = [\n  { use = \"format\" },\n]\n");
+}
+
+#[test]
+fn insert_adds_trailing_comma_to_multiline_list() {
+  let manifest = "[shipit]\nsteps = [\n  { audit = {} },\n  { test = {} }\n]\n";
+
+  let updated = insert(manifest, "shipit", r#"{ step = "git", command = "push" }"#).unwrap();
+  assert_eq!(
+    updated.unwrap(),
+    "[shipit]\nsteps = [\n  { audit = {} },\n  { test = {} },\n  { step = \"git\", command = \"push\" },\n]\n"
+  );
+}
+
+#[test]
+fn insert_keeps_single_line_list_on_one_line() {
+  let manifest = "[test]\nchecks = [{ name = \"Lint\", shell = \"bin/lint\" }]\n";
+
+  let updated = insert(manifest, "test", r#"{ name = "Test", shell = "bin/test" }"#).unwrap();
+  assert_eq!(
+    updated.unwrap(),
+    "[test]\nchecks = [{ name = \"Lint\", shell = \"bin/lint\" }, { name = \"Test\", shell = \"bin/test\" }]\n"
+  );
+}
@@ -1,5 +1,6 @@
 use clap::Parser;
 use clap_complete::Shell;
+use medic_src::theme::Theme;
 
 #[derive(Debug, Parser)]
 #[clap(author, version, about)]
@@ -12,6 +13,22 @@ pub struct CliArgs {
   #[clap(long, short, action)]
   pub force: bool,
 
+  /// Template to start from: a built-in template (generic, homebrew,
+  /// tool-versions, rust, elixir, node, go), the name of a file in
+  /// .config/medic/templates, or a path to a TOML file. Defaults to templates
+  /// for the files found in the project.
+  #[clap(long, short)]
+  pub template: Option<String>,
+
+  /// Adds a step to a section of the existing manifest instead of creating
+  /// one, keeping its comments and formatting
+  #[clap(long, num_args = 2, value_names = ["SECTION", "STEP"], conflicts_with_all = ["force", "template"])]
+  pub add: Option<Vec<String>>,
+
+  /// Color theme
+  #[arg(long, env = "MEDIC_THEME", default_value = "auto")]
+  pub theme: Theme,
+
   /// Shell to generate completions for
   #[clap(long, value_enum, value_parser)]
   pub completion: Option<Shell>,
@@ -1,14 +1,25 @@
 #![cfg_attr(feature = "strict", deny(warnings))]
 
+#[cfg(test)]
+mod add_test;
+#[cfg(test)]
+mod template_test;
+
+pub mod add;
 pub mod cli;
+pub mod template;
 
+use medic_src::config::discovery;
 use medic_src::{AppResult, extra};
 use std::io::Write;
 
-pub fn create_config_file(path: std::path::PathBuf, force: bool) -> AppResult<()> {
+/// Writes a new manifest to `path`, built from `template` or from the
+/// templates matching files found in the project.
+pub fn create_config_file(path: std::path::PathBuf, force: bool, template: Option<String>) -> AppResult<()> {
   let path_expansion = extra::env::subst(path.to_str().unwrap()).unwrap();
   let expanded_path = std::path::Path::new(&path_expansion);
   let config_dir = expanded_path.parent().unwrap();
+  let (contents, templates) = template::resolve(template.as_deref(), &discovery::project_dir(expanded_path))?;
 
   std::fs::create_dir_all(config_dir)?;
 
@@ -27,56 +38,14 @@ pub fn create_config_file(path: std::path::PathBuf, force: bool) -> AppResult<()
     }
   }
 
+  println!("Using templates: {}", templates.join(", "));
+
   let mut file = std::fs::OpenOptions::new()
     .create_new(true)
     .append(true)
     .open(expanded_path)?;
 
-  writeln!(file, "[doctor]")?;
-  writeln!(file, "checks = [")?;
-  writeln!(file, "  # {{check = \"homebrew\"}},")?;
-  writeln!(
-    file,
-    "  # {{check = \"tool-versions\", command = \"plugin-installed\", args = {{plugin = \"my language\"}}}},"
-  )?;
-  writeln!(
-    file,
-    "  # {{check = \"tool-versions\", command = \"package-installed\", args = {{plugin = \"my language\"}}}},"
-  )?;
-  writeln!(file, "]")?;
-  writeln!(file)?;
-  writeln!(file, "[test]")?;
-  writeln!(file, "checks = [")?;
-  writeln!(file, "  # {{name = \"Run tests\", shell = \"run my test suite\"}},")?;
-  writeln!(file, "]")?;
-  writeln!(file)?;
-  writeln!(file, "[audit]")?;
-  writeln!(file, "checks = [")?;
-  writeln!(
-    file,
-    "  # {{name = \"Format check\", shell = \"check that code is properly formatted\"}},"
-  )?;
-  writeln!(file, "  # {{name = \"Lint code\", shell = \"run my code linter\"}},")?;
-  writeln!(file, "]")?;
-  writeln!(file)?;
-  writeln!(file, "[outdated]")?;
-  writeln!(file, "checks = [")?;
-  writeln!(file, "]")?;
-  writeln!(file)?;
-  writeln!(file, "[update]")?;
-  writeln!(file, "steps = [")?;
-  writeln!(file, "  {{ step = \"git\", command = \"pull\" }},")?;
-  writeln!(file, "  {{ doctor = {{}} }},")?;
-  writeln!(file, "]")?;
-  writeln!(file)?;
-  writeln!(file, "[shipit]")?;
-  writeln!(file, "steps = [")?;
-  writeln!(file, "  {{ audit = {{}} }},")?;
-  writeln!(file, "  {{ update = {{}} }},")?;
-  writeln!(file, "  {{ test = {{}} }},")?;
-  writeln!(file, "  {{ step = \"git\", command = \"push\" }},")?;
-  writeln!(file, "]")?;
-  writeln!(file)?;
+  write!(file, "{contents}")?;
 
   AppResult::Ok(())
 }
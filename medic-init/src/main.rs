@@ -1,11 +1,16 @@
+use medic_init::add::add_step;
 use medic_init::cli::CliArgs;
 use medic_init::create_config_file;
-use medic_src::AppResult;
+use medic_src::{AppResult, theme};
 
 use clap::Parser;
 
 fn main() -> AppResult<()> {
   let cli_args = CliArgs::parse();
+  theme::set_theme((&cli_args.theme).into());
 
-  create_config_file(cli_args.config, cli_args.force)
+  match cli_args.add.as_deref() {
+    Some([section, step]) => add_step(cli_args.config, section.clone(), step.clone()),
+    _ => create_config_file(cli_args.config, cli_args.force, cli_args.template),
+  }
 }
@@ -0,0 +1,326 @@
+// @related [tests](medic-init/src/template_test.rs)
+
+use medic_src::error::MedicError;
+use std::path::{Path, PathBuf};
+
+/// Directory, relative to the project, searched for templates before the
+/// built-in ones.
+pub const LOCAL_DIR: &str = ".config/medic/templates";
+
+/// Entries for each section of a generated manifest, one step per line.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Sections {
+  pub doctor: Vec<String>,
+  pub test: Vec<String>,
+  pub audit: Vec<String>,
+  pub outdated: Vec<String>,
+  pub update: Vec<String>,
+  pub shipit: Vec<String>,
+}
+
+impl Sections {
+  fn extend(&mut self, other: Sections) {
+    self.doctor.extend(other.doctor);
+    self.test.extend(other.test);
+    self.audit.extend(other.audit);
+    self.outdated.extend(other.outdated);
+    self.update.extend(other.update);
+    self.shipit.extend(other.shipit);
+  }
+
+  /// Renders the sections as a medic manifest.
+  pub fn render(&self) -> String {
+    let mut manifest = String::new();
+    for (name, list, entries) in [
+      ("doctor", "checks", &self.doctor),
+      ("test", "checks", &self.test),
+      ("audit", "checks", &self.audit),
+      ("outdated", "checks", &self.outdated),
+      ("update", "steps", &self.update),
+      ("shipit", "steps", &self.shipit),
+    ] {
+      manifest.push_str(&format!("[{name}]\n{list} = [\n"));
+      for entry in entries {
+        manifest.push_str(&format!("  {entry}\n"));
+      }
+      manifest.push_str("]\n\n");
+    }
+    manifest
+  }
+}
+
+/// A built-in template, used when one of its marker files is found in the
+/// project or when it is chosen with `--template <name>`.
+pub struct Template {
+  pub name: &'static str,
+  pub markers: &'static [&'static str],
+  sections: fn(&Path) -> Sections,
+}
+
+impl Template {
+  pub fn sections(&self, project_dir: &Path) -> Sections {
+    (self.sections)(project_dir)
+  }
+}
+
+/// Built-in templates, in the order their steps appear in a manifest.
+pub const TEMPLATES: [Template; 7] = [
+  Template {
+    name: "generic",
+    markers: &[],
+    sections: generic,
+  },
+  Template {
+    name: "homebrew",
+    markers: &["Brewfile"],
+    sections: homebrew,
+  },
+  Template {
+    name: "tool-versions",
+    markers: &[".tool-versions"],
+    sections: tool_versions,
+  },
+  Template {
+    name: "rust",
+    markers: &["Cargo.toml"],
+    sections: rust,
+  },
+  Template {
+    name: "elixir",
+    markers: &["mix.exs"],
+    sections: elixir,
+  },
+  Template {
+    name: "node",
+    markers: &["package.json"],
+    sections: node,
+  },
+  Template {
+    name: "go",
+    markers: &["go.mod"],
+    sections: go,
+  },
+];
+
+/// Returns the contents of a new manifest for the project in `project_dir`
+/// along with the names of the templates it was built from.
+///
+/// Without a `name`, built-in templates are chosen by the files found in the
+/// project. A `name` may be a path to a TOML file, the name of a file in
+/// `.config/medic/templates`, or the name of a built-in template.
+pub fn resolve(name: Option<&str>, project_dir: &Path) -> Result<(String, Vec<String>), MedicError> {
+  let Some(name) = name else {
+    let mut templates = detect(project_dir);
+    if templates.is_empty() {
+      templates.extend(find("generic"));
+    }
+    let names = templates
+      .iter()
+      .map(|template| template.name.to_string())
+      .collect();
+    return Ok((render(&templates, project_dir), names));
+  };
+
+  let path = if name.contains('/') || name.ends_with(".toml") {
+    Some(PathBuf::from(name))
+  } else {
+    Some(project_dir.join(LOCAL_DIR).join(format!("{name}.toml"))).filter(|path| path.is_file())
+  };
+  if let Some(path) = path {
+    let contents = std::fs::read_to_string(&path)
+      .map_err(|err| MedicError::from(format!("Unable to read template {path:?}: {err}")))?;
+    return Ok((contents, vec![name.to_string()]));
+  }
+
+  match find(name) {
+    Some(template) => Ok((render(&[template], project_dir), vec![name.to_string()])),
+    None => Err(
+      format!(
+        "Unknown template `{name}`. Available templates: {}",
+        available(project_dir).join(", ")
+      )
+      .into(),
+    ),
+  }
+}
+
+/// Names of the project-local and built-in templates.
+pub fn available(project_dir: &Path) -> Vec<String> {
+  let mut names: Vec<String> = std::fs::read_dir(project_dir.join(LOCAL_DIR))
+    .into_iter()
+    .flatten()
+    .flatten()
+    .map(|entry| entry.path())
+    .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+    .filter_map(|path| {
+      path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+    })
+    .collect();
+  names.sort();
+  for template in &TEMPLATES {
+    if !names.iter().any(|name| name == template.name) {
+      names.push(template.name.to_string());
+    }
+  }
+  names
+}
+
+/// Returns the built-in template named `name`.
+pub fn find(name: &str) -> Option<&'static Template> {
+  TEMPLATES.iter().find(|template| template.name == name)
+}
+
+/// Returns the templates whose marker files exist in `project_dir`.
+pub fn detect(project_dir: &Path) -> Vec<&'static Template> {
+  TEMPLATES
+    .iter()
+    .filter(|template| {
+      template
+        .markers
+        .iter()
+        .any(|marker| project_dir.join(marker).exists())
+    })
+    .collect()
+}
+
+/// Combines `templates` into a manifest. Every manifest updates from and
+/// ships to git.
+pub fn render(templates: &[&Template], project_dir: &Path) -> String {
+  let mut sections = Sections::default();
+  for template in templates {
+    sections.extend(template.sections(project_dir));
+  }
+  if templates.iter().all(|template| template.name != "generic") {
+    sections.extend(workflow());
+  }
+  sections.render()
+}
+
+fn lines(entries: &[&str]) -> Vec<String> {
+  entries.iter().map(|entry| entry.to_string()).collect()
+}
+
+fn workflow() -> Sections {
+  Sections {
+    update: lines(&[r#"{ step = "git", command = "pull" },"#, r#"{ doctor = {} },"#]),
+    shipit: lines(&[
+      r#"{ audit = {} },"#,
+      r#"{ update = {} },"#,
+      r#"{ test = {} },"#,
+      r#"{ step = "git", command = "push" },"#,
+    ]),
+    ..Sections::default()
+  }
+}
+
+fn generic(_project_dir: &Path) -> Sections {
+  let mut sections = Sections {
+    doctor: lines(&[
+      r#"# {check = "homebrew"},"#,
+      r#"# {check = "tool-versions", command = "plugin-installed", args = {plugin = "my language"}},"#,
+      r#"# {check = "tool-versions", command = "package-installed", args = {plugin = "my language"}},"#,
+    ]),
+    test: lines(&[r#"# {name = "Run tests", shell = "run my test suite"},"#]),
+    audit: lines(&[
+      r#"# {name = "Format check", shell = "check that code is properly formatted"},"#,
+      r#"# {name = "Lint code", shell = "run my code linter"},"#,
+    ]),
+    ..Sections::default()
+  };
+  sections.extend(workflow());
+  sections
+}
+
+fn homebrew(_project_dir: &Path) -> Sections {
+  Sections {
+    doctor: lines(&[r#"{ check = "homebrew" },"#]),
+    ..Sections::default()
+  }
+}
+
+// Adds checks for each plugin listed in `.tool-versions`, or for a
+// placeholder plugin when chosen for a project without one.
+fn tool_versions(project_dir: &Path) -> Sections {
+  let contents = std::fs::read_to_string(project_dir.join(".tool-versions")).unwrap_or_default();
+  let mut plugins: Vec<&str> = contents
+    .lines()
+    .filter_map(|line| line.split_whitespace().next())
+    .filter(|plugin| !plugin.starts_with('#'))
+    .collect();
+  if plugins.is_empty() {
+    plugins.push("my language");
+  }
+
+  let mut doctor = vec![];
+  for plugin in plugins {
+    for command in ["plugin-installed", "package-installed"] {
+      doctor.push(format!(
+        r#"{{ check = "tool-versions", command = "{command}", args = {{ plugin = "{plugin}" }} }},"#
+      ));
+    }
+  }
+
+  Sections {
+    doctor,
+    ..Sections::default()
+  }
+}
+
+fn rust(_project_dir: &Path) -> Sections {
+  Sections {
+    doctor: lines(&[
+      r#"{ check = "rust", command = "crate-installed", args = { name = ["cargo-audit", "cargo-outdated"] } },"#,
+    ]),
+    test: lines(&[r#"{ name = "Run Rust tests", shell = "cargo test --workspace --color always", verbose = true },"#]),
+    audit: lines(&[
+      r#"{ name = "Audit crates", shell = "cargo audit --color always", verbose = true },"#,
+      r#"{ check = "rust", command = "format-check" },"#,
+      r#"{ step = "rust", command = "clippy" },"#,
+    ]),
+    outdated: lines(&[r#"{ check = "rust" },"#]),
+    ..Sections::default()
+  }
+}
+
+fn elixir(_project_dir: &Path) -> Sections {
+  Sections {
+    doctor: lines(&[
+      r#"{ name = "Dependencies fetched", shell = "mix deps.get --check-locked", remedy = "mix deps.get" },"#,
+    ]),
+    test: lines(&[r#"{ name = "Run Elixir tests", shell = "mix test --color", verbose = true },"#]),
+    audit: lines(&[
+      r#"{ name = "Elixir format check", shell = "mix format --check-formatted", remedy = "mix format" },"#,
+      r#"{ name = "Compile without warnings", shell = "mix compile --force --warnings-as-errors", verbose = true },"#,
+      r#"{ name = "Unused dependencies", shell = "mix deps.unlock --check-unused", remedy = "mix deps.clean --unlock --unused" },"#,
+    ]),
+    outdated: lines(&[r#"{ check = "elixir" },"#]),
+    ..Sections::default()
+  }
+}
+
+fn node(_project_dir: &Path) -> Sections {
+  Sections {
+    doctor: lines(&[r#"{ name = "Packages installed", shell = "npm ls --depth=0", remedy = "npm install" },"#]),
+    test: lines(&[r#"{ name = "Run Node tests", shell = "npm test", verbose = true },"#]),
+    audit: lines(&[
+      r#"{ name = "Lint JavaScript", shell = "npm run lint --if-present", verbose = true },"#,
+      r#"{ name = "Audit packages", shell = "npm audit", verbose = true },"#,
+    ]),
+    ..Sections::default()
+  }
+}
+
+fn go(_project_dir: &Path) -> Sections {
+  Sections {
+    doctor: lines(&[r#"{ name = "Modules downloaded", shell = "go mod download" },"#]),
+    test: lines(&[r#"{ name = "Run Go tests", shell = "go test ./...", verbose = true },"#]),
+    audit: lines(&[
+      r#"{ name = "Go format check", shell = "test -z \"$(gofmt -l .)\"", remedy = "gofmt -w ." },"#,
+      r#"{ name = "Go vet", shell = "go vet ./...", verbose = true },"#,
+      r#"{ name = "Modules tidy", shell = "go mod tidy -diff", remedy = "go mod tidy" },"#,
+    ]),
+    ..Sections::default()
+  }
+}
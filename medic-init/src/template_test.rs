@@ -0,0 +1,93 @@
+// @related [subject](medic-init/src/template.rs)
+
+use super::template::*;
+use medic_src::config::Manifest;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn project(name: &str, files: &[&str]) -> PathBuf {
+  let root = std::env::temp_dir().join(format!("medic-template-{name}-{}", std::process::id()));
+  let _ = fs::remove_dir_all(&root);
+  fs::create_dir_all(&root).unwrap();
+  for file in files {
+    fs::write(root.join(file), "").unwrap();
+  }
+  root
+}
+
+fn parse(contents: &str) -> Manifest {
+  toml::from_str(contents).unwrap_or_else(|err| panic!("{err}\n{contents}"))
+}
+
+#[test]
+fn every_template_renders_a_manifest() {
+  for template in &TEMPLATES {
+    let manifest = parse(&render(&[template], Path::new("/nonexistent")));
+    assert!(manifest.update.is_some(), "{} has no update steps", template.name);
+    assert!(manifest.shipit.is_some(), "{} has no shipit steps", template.name);
+  }
+}
+
+#[test]
+fn resolve_without_markers_uses_generic_template() {
+  let root = project("generic", &[]);
+
+  let (contents, names) = resolve(None, &root).unwrap();
+  assert_eq!(names, vec!["generic"]);
+  assert!(contents.starts_with("[doctor]\nchecks = [\n  # {check = \"homebrew\"},\n"));
+  parse(&contents);
+
+  fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn resolve_combines_detected_templates() {
+  let root = project("detected", &["Brewfile", "Cargo.toml", "package.json"]);
+  fs::write(root.join(".tool-versions"), "rust 1.90.0\n# comment\nnodejs 22.0.0\n").unwrap();
+
+  let (contents, names) = resolve(None, &root).unwrap();
+  assert_eq!(names, vec!["homebrew", "tool-versions", "rust", "node"]);
+  assert!(contents.contains(r#"command = "plugin-installed", args = { plugin = "nodejs" }"#));
+  assert!(contents.contains("cargo test --workspace"));
+  assert!(contents.contains("npm test"));
+  assert_eq!(
+    contents
+      .matches(r#"{ step = "git", command = "push" }"#)
+      .count(),
+    1
+  );
+  parse(&contents);
+
+  fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn resolve_prefers_project_local_template() {
+  let root = project("local", &["Cargo.toml"]);
+  fs::create_dir_all(root.join(LOCAL_DIR)).unwrap();
+  fs::write(root.join(LOCAL_DIR).join("rust.toml"), "[doctor]\nchecks = []\n").unwrap();
+
+  let (contents, names) = resolve(Some("rust"), &root).unwrap();
+  assert_eq!(names, vec!["rust"]);
+  assert_eq!(contents, "[doctor]\nchecks = []\n");
+
+  let (contents, _) = resolve(Some("go"), &root).unwrap();
+  assert!(contents.contains("go test ./..."));
+
+  fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn resolve_reports_unknown_template() {
+  let root = project("unknown", &[]);
+  fs::create_dir_all(root.join(LOCAL_DIR)).unwrap();
+  fs::write(root.join(LOCAL_DIR).join("company.toml"), "").unwrap();
+
+  let err = resolve(Some("python"), &root).unwrap_err();
+  assert_eq!(
+    err.to_string(),
+    "Unknown template `python`. Available templates: company, generic, homebrew, tool-versions, rust, elixir, node, go"
+  );
+
+  fs::remove_dir_all(root).unwrap();
+}
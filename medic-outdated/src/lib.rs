@@ -6,7 +6,7 @@ use medic_src::AppResult;
 use medic_src::cli::Flags;
 use medic_src::config::Manifest;
 use medic_src::context::Context;
-use medic_src::runnable::run;
+use medic_src::state;
 
 pub fn run_checks(
   manifest: Manifest,
@@ -15,12 +15,7 @@ pub fn run_checks(
   context: &Context,
 ) -> AppResult<()> {
   match manifest.outdated {
-    Some(outdated) => {
-      for check in outdated.checks {
-        run(check, progress, &mut flags, context)?;
-      }
-      AppResult::Ok(())
-    }
+    Some(outdated) => state::run_steps("outdated", outdated.checks, progress, &mut flags, context),
     None => AppResult::Err(Some("No outdated checks found in medic config.".into())),
   }
 }
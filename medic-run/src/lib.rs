@@ -5,6 +5,7 @@ pub mod cli;
 use medic_src::AppResult;
 use medic_src::cli::Flags;
 use medic_src::context::Context;
+use medic_src::interrupt;
 use medic_src::runnable::Runnable;
 use medic_src::shell::ShellConfig;
 
@@ -19,5 +20,9 @@ pub fn run_shell(
   let context = Context::new();
   let shell = ShellConfig::new(name, cmd, cd, remedy, verbose);
   let mut flags = Flags::default();
-  shell.run(progress, &mut flags, &context).into()
+  let _step = interrupt::running(&shell.to_string());
+  match shell.run(progress, &mut flags, &context) {
+    _ if interrupt::interrupted() => AppResult::Quit,
+    result => result.into(),
+  }
 }
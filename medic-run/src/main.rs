@@ -17,7 +17,7 @@ fn main() -> AppResult<()> {
     std::process::exit(0);
   }
 
-  ctrlc::set_handler(interrupt).expect("Unable to set Ctrl-C handler");
+  medic_src::interrupt::install();
 
   console::set_colors_enabled(true);
   console::set_colors_enabled_stderr(true);
@@ -39,7 +39,3 @@ fn main() -> AppResult<()> {
     Err(_) => std::process::exit(1),
   }
 }
-
-fn interrupt() {
-  std::process::exit(1);
-}
@@ -6,7 +6,7 @@ use medic_src::AppResult;
 use medic_src::cli::Flags;
 use medic_src::config::Manifest;
 use medic_src::context::Context;
-use medic_src::runnable::run;
+use medic_src::state;
 
 pub fn run_steps(
   manifest: Manifest,
@@ -15,12 +15,7 @@ pub fn run_steps(
   context: &Context,
 ) -> AppResult<()> {
   match manifest.shipit {
-    Some(shipit) => {
-      for step in shipit.steps {
-        run(step, progress, &mut flags, context)?;
-      }
-      AppResult::Ok(())
-    }
+    Some(shipit) => state::run_steps("shipit", shipit.steps, progress, &mut flags, context),
     None => AppResult::Err(Some("No shipit checks found in medic config.".into())),
   }
 }
@@ -1,17 +1,19 @@
 use crate::Check;
 use crate::cli::Flags;
+use crate::condition::Condition;
 use crate::context::Context;
 use crate::error::MedicError;
 use crate::recoverable::Recoverable;
 use crate::runnable::Runnable;
 use crate::shell::ShellConfig;
 use crate::step::StepConfig;
+use schemars::JsonSchema;
 use serde::Deserialize;
 
 use std::fmt;
 use std::process::Command;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq)]
 #[serde(untagged)]
 pub enum AuditStep {
   Check(Check),
@@ -28,6 +30,46 @@ impl Runnable for AuditStep {
     }
   }
 
+  fn condition(&self) -> Condition<'_> {
+    match self {
+      AuditStep::Check(config) => config.condition(),
+      AuditStep::Shell(config) => config.condition(),
+      AuditStep::Step(config) => config.condition(),
+    }
+  }
+
+  fn depends_on(&self) -> &[String] {
+    match self {
+      AuditStep::Check(config) => config.depends_on(),
+      AuditStep::Shell(config) => config.depends_on(),
+      AuditStep::Step(config) => config.depends_on(),
+    }
+  }
+
+  fn id(&self) -> Option<&str> {
+    match self {
+      AuditStep::Check(config) => config.id(),
+      AuditStep::Shell(config) => config.id(),
+      AuditStep::Step(config) => config.id(),
+    }
+  }
+
+  fn inputs(&self) -> &[String] {
+    match self {
+      AuditStep::Check(config) => config.inputs(),
+      AuditStep::Shell(config) => config.inputs(),
+      AuditStep::Step(config) => config.inputs(),
+    }
+  }
+
+  fn name(&self) -> Option<&str> {
+    match self {
+      AuditStep::Check(config) => config.name(),
+      AuditStep::Shell(config) => config.name(),
+      AuditStep::Step(config) => config.name(),
+    }
+  }
+
   fn platform(&self) -> &Option<Vec<String>> {
     match self {
       AuditStep::Check(config) => config.platform(),
@@ -44,6 +86,14 @@ impl Runnable for AuditStep {
     }
   }
 
+  fn tags(&self) -> &[String] {
+    match self {
+      AuditStep::Check(config) => config.tags(),
+      AuditStep::Shell(config) => config.tags(),
+      AuditStep::Step(config) => config.tags(),
+    }
+  }
+
   fn to_command(&self) -> Result<Command, MedicError> {
     match self {
       AuditStep::Check(config) => config.to_command(),
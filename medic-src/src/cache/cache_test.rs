@@ -0,0 +1,127 @@
+// @related [subject](medic-src/src/cache/mod.rs)
+
+use super::Fingerprint;
+use crate::cli::Flags;
+use crate::context::Context;
+use crate::fixture::{self, Project};
+use crate::progress;
+use crate::recoverable::Recoverable;
+use crate::runnable;
+use crate::shell::ShellConfig;
+use std::path::Path;
+use std::sync::Once;
+
+static INIT: Once = Once::new();
+pub fn initialize() {
+  INIT.call_once(|| {
+    if crate::theme::THEME.get().is_none() {
+      let theme = crate::theme::dark_theme();
+      crate::theme::set_theme(theme);
+    }
+  });
+}
+
+fn project() -> Project {
+  let dir = Project::new("");
+  std::fs::write(dir.join("mix.lock"), "one").unwrap();
+  dir
+}
+
+fn shell(dir: &Path, shell: &str) -> ShellConfig {
+  let mut config = fixture::shell("Deps", shell);
+  config.inputs = vec![format!("{}/*.lock", dir.display())];
+  config
+}
+
+#[test]
+fn test_of_without_inputs() {
+  let dir = project();
+  let config = fixture::shell("Deps", "true");
+
+  assert_eq!(Fingerprint::of(&config, &dir.flags()), None);
+}
+
+#[test]
+fn test_of_with_no_cache() {
+  let dir = project();
+  let flags = Flags {
+    no_cache: true,
+    ..dir.flags()
+  };
+
+  assert_eq!(Fingerprint::of(&shell(&dir, "true"), &flags), None);
+}
+
+#[test]
+fn test_is_cached_after_save() {
+  let dir = project();
+  let config = shell(&dir, "true");
+
+  let fingerprint = Fingerprint::of(&config, &dir.flags()).unwrap();
+  assert!(!fingerprint.is_cached());
+  fingerprint.save();
+  assert!(fingerprint.is_cached());
+  assert!(dir.join(".medic/cache.json").exists());
+}
+
+#[test]
+fn test_is_not_cached_when_inputs_change() {
+  let dir = project();
+  let config = shell(&dir, "true");
+  Fingerprint::of(&config, &dir.flags()).unwrap().save();
+
+  std::fs::write(dir.join("mix.lock"), "two").unwrap();
+  assert!(!Fingerprint::of(&config, &dir.flags()).unwrap().is_cached());
+
+  Fingerprint::of(&config, &dir.flags()).unwrap().save();
+  std::fs::write(dir.join("other.lock"), "").unwrap();
+  assert!(!Fingerprint::of(&config, &dir.flags()).unwrap().is_cached());
+}
+
+#[test]
+fn test_relative_inputs_are_matched_from_the_project_directory() {
+  let dir = project();
+  let mut config = fixture::shell("Deps", "true");
+  config.inputs = vec!["*.lock".into()];
+  Fingerprint::of(&config, &dir.flags()).unwrap().save();
+  assert!(Fingerprint::of(&config, &dir.flags()).unwrap().is_cached());
+
+  std::fs::write(dir.join("mix.lock"), "two").unwrap();
+  assert!(!Fingerprint::of(&config, &dir.flags()).unwrap().is_cached());
+}
+
+#[test]
+fn test_is_not_cached_when_definition_changes() {
+  let dir = project();
+  Fingerprint::of(&shell(&dir, "true"), &dir.flags())
+    .unwrap()
+    .save();
+
+  assert!(
+    !Fingerprint::of(&shell(&dir, "mix deps.get"), &dir.flags())
+      .unwrap()
+      .is_cached()
+  );
+}
+
+#[test]
+fn test_run_unless_skipped_caches_successful_steps() {
+  initialize();
+  let dir = project();
+  let log = dir.join("log");
+  let config = shell(&dir, &format!("echo ran >> {}", log.display()));
+  let context = Context::new();
+  let mut flags = dir.flags();
+  let mut progress = progress::new(&flags);
+
+  for _ in 0..2 {
+    let result = runnable::run_unless_skipped(&config, &mut progress, &mut flags, &context);
+    assert!(matches!(result, Recoverable::Ok(())));
+  }
+  assert_eq!(std::fs::read_to_string(&log).unwrap(), "ran\n");
+
+  flags.no_cache = true;
+  let result = runnable::run_unless_skipped(&config, &mut progress, &mut flags, &context);
+  assert!(matches!(result, Recoverable::Ok(())));
+  assert_eq!(std::fs::read_to_string(&log).unwrap(), "ran\nran\n");
+}
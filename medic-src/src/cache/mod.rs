@@ -0,0 +1,115 @@
+// @related [tests](medic-src/src/cache/cache_test.rs)
+
+#[cfg(test)]
+mod cache_test;
+
+use crate::cli::Flags;
+use crate::config::discovery;
+use crate::runnable::Runnable;
+
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const CACHE_FILE: &str = ".medic/cache.json";
+
+// Steps running in parallel may save their fingerprints at the same time.
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// A hash of a step's definition and the contents of its `inputs`, saved
+/// when the step succeeds. While the hash matches the one saved, the step
+/// is cached and need not run again.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Fingerprint {
+  hash: String,
+  key: String,
+  path: PathBuf,
+}
+
+impl Fingerprint {
+  /// The fingerprint of `runnable`, or `None` when it has no `inputs` or
+  /// medic is run with `--no-cache`.
+  pub fn of(runnable: &impl Runnable, flags: &Flags) -> Option<Self> {
+    if flags.no_cache || runnable.inputs().is_empty() {
+      return None;
+    }
+    let manifest = discovery::locate(&flags.config_path).ok()?;
+    let project_dir = discovery::project_dir(&manifest);
+    let definition = format!("{runnable:?}");
+
+    Some(Self {
+      hash: hash_inputs(&project_dir, runnable.inputs()),
+      key: format!("{:x}", Sha256::digest(&definition)),
+      path: project_dir.join(CACHE_FILE),
+    })
+  }
+
+  /// Whether the step succeeded before with the same definition and inputs.
+  pub fn is_cached(&self) -> bool {
+    let _lock = LOCK.lock().unwrap();
+    read(&self.path).get(&self.key) == Some(&self.hash)
+  }
+
+  /// Saves the fingerprint of a step which succeeded. Failing to save only
+  /// means that the step runs again next time.
+  pub fn save(&self) {
+    let _lock = LOCK.lock().unwrap();
+    let mut cache = read(&self.path);
+    cache.insert(self.key.clone(), self.hash.clone());
+
+    if let Some(dir) = self.path.parent() {
+      let _ = std::fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(&cache) {
+      let _ = std::fs::write(&self.path, json);
+    }
+  }
+}
+
+fn read(path: &Path) -> BTreeMap<String, String> {
+  std::fs::read_to_string(path)
+    .ok()
+    .and_then(|json| serde_json::from_str(&json).ok())
+    .unwrap_or_default()
+}
+
+// Hashes the path and contents of every file matched by `inputs`, in sorted
+// order, so that adding, removing or changing a file changes the hash.
+// Relative patterns are matched from the project directory, wherever medic
+// is run from.
+fn hash_inputs(project_dir: &Path, inputs: &[String]) -> String {
+  let mut hasher = Sha256::new();
+
+  for pattern in inputs {
+    hasher.update(pattern.as_bytes());
+    hasher.update([0]);
+    let pattern = if Path::new(pattern).is_absolute() {
+      pattern.clone()
+    } else {
+      let dir = glob::Pattern::escape(&project_dir.to_string_lossy());
+      format!("{dir}/{pattern}")
+    };
+    let mut paths: Vec<PathBuf> = glob::glob(&pattern)
+      .map(|paths| {
+        paths
+          .filter_map(Result::ok)
+          .filter(|path| path.is_file())
+          .collect()
+      })
+      .unwrap_or_default();
+    paths.sort();
+
+    for path in paths {
+      let relative = path.strip_prefix(project_dir).unwrap_or(&path);
+      hasher.update(relative.to_string_lossy().as_bytes());
+      hasher.update([0]);
+      match std::fs::read(&path) {
+        Ok(contents) => hasher.update(Sha256::digest(contents)),
+        Err(err) => hasher.update(err.to_string().as_bytes()),
+      }
+    }
+  }
+
+  format!("{:x}", hasher.finalize())
+}
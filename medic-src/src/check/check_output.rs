@@ -15,6 +15,16 @@ pub struct CheckOutput {
 }
 
 impl CheckOutput {
+  /// The output of a check that was killed before it finished, as written.
+  pub(crate) fn partial(result: std::process::Output) -> Self {
+    let text = |bytes: Vec<u8>| Some(crate::std_to_string(bytes).trim().to_owned()).filter(|text| !text.is_empty());
+    Self {
+      stdout: text(result.stdout),
+      stderr: text(result.stderr),
+      ..Default::default()
+    }
+  }
+
   pub(crate) fn verbose(&mut self, verbose: bool) {
     self.verbose = verbose;
   }
@@ -34,10 +34,22 @@ fn deserialize_arg_string() {
       cd: None,
       check: "check-name".to_string(),
       command: Some("subcommand".to_string()),
+      depends_on: vec![],
       env: BTreeMap::default(),
+      id: None,
+      r#if: None,
+      if_env: None,
+      inputs: vec![],
       manual: false,
       output: OutputFormat::Json,
       platform: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
       verbose: false
     }
   )
@@ -61,10 +73,22 @@ fn deserialize_arg_list() {
       cd: None,
       check: "check-name".to_string(),
       command: Some("subcommand".to_string()),
+      depends_on: vec![],
       env: BTreeMap::default(),
+      id: None,
+      r#if: None,
+      if_env: None,
+      inputs: vec![],
       manual: false,
       output: OutputFormat::Json,
       platform: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
       verbose: false
     }
   )
@@ -85,10 +109,22 @@ fn deserialize_cd_string() {
       cd: Some("./subdirectory".to_string()),
       check: "check-name".to_string(),
       command: None,
+      depends_on: vec![],
       env: BTreeMap::default(),
+      id: None,
+      r#if: None,
+      if_env: None,
+      inputs: vec![],
       manual: false,
       output: OutputFormat::Json,
       platform: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
       verbose: false
     }
   )
@@ -109,13 +145,25 @@ fn deserialize_env() {
       cd: None,
       check: "check-name".to_string(),
       command: None,
+      depends_on: vec![],
       env: BTreeMap::from([
         ("MY_VAR".to_string(), "first".to_string()),
         ("SECOND_VAR".to_string(), "second".to_string())
       ]),
+      id: None,
+      r#if: None,
+      if_env: None,
+      inputs: vec![],
       manual: false,
       output: OutputFormat::Json,
       platform: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
       verbose: false
     }
   )
@@ -136,10 +184,22 @@ fn deserialize_manual_string() {
       cd: None,
       check: "check-name".to_string(),
       command: None,
+      depends_on: vec![],
       env: BTreeMap::default(),
+      id: None,
+      r#if: None,
+      if_env: None,
+      inputs: vec![],
       manual: true,
       output: OutputFormat::Json,
       platform: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
       verbose: false
     }
   )
@@ -152,10 +212,22 @@ fn to_command() {
     cd: None,
     check: "json".to_string(),
     command: None,
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     manual: false,
     output: OutputFormat::Json,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -181,10 +253,22 @@ fn to_command_cd_relative() {
     cd: Some("../fixtures/bin".to_string()),
     check: "json".to_string(),
     command: None,
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     manual: false,
     output: OutputFormat::Json,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
   let path_expansion = extra::env::subst("${PWD}/fixtures/bin").unwrap();
@@ -202,10 +286,22 @@ fn to_command_subcommand() {
     cd: None,
     check: "json".to_string(),
     command: Some("sub-command".to_string()),
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     manual: false,
     output: OutputFormat::Json,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -223,13 +319,25 @@ fn to_command_env() {
     cd: None,
     check: "json".to_string(),
     command: None,
+    depends_on: vec![],
     env: BTreeMap::from([
       ("VAR".to_string(), "value".to_string()),
       ("OTHER".to_string(), "other".to_string()),
     ]),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     manual: false,
     output: OutputFormat::Json,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -251,10 +359,22 @@ fn to_command_stdio() {
     cd: None,
     check: "json".to_string(),
     command: None,
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     manual: false,
     output: OutputFormat::Stdio,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -276,10 +396,22 @@ fn to_command_args() {
     cd: None,
     check: "json".to_string(),
     command: None,
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     manual: false,
     output: OutputFormat::Json,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -300,10 +432,22 @@ fn to_command_args_list() {
     cd: None,
     check: "json".to_string(),
     command: None,
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     manual: false,
     output: OutputFormat::Json,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -326,10 +470,22 @@ fn to_command_missing_command() {
     cd: None,
     check: "missing".to_string(),
     command: None,
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     manual: false,
     output: OutputFormat::Json,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -351,10 +507,22 @@ fn to_string_single_arg() {
     cd: None,
     check: "check-name".to_string(),
     command: None,
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     manual: false,
     output: OutputFormat::Json,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -375,10 +543,22 @@ fn to_string_subcommand_single_arg() {
     cd: None,
     check: "check-name".to_string(),
     command: Some("subcommand".to_string()),
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     manual: false,
     output: OutputFormat::Json,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -399,10 +579,22 @@ fn to_string_subcommand_multiple_args() {
     cd: None,
     check: "check-name".to_string(),
     command: Some("subcommand".to_string()),
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     manual: false,
     output: OutputFormat::Json,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -423,10 +615,22 @@ fn to_string_subcommand_multiple_arg_values() {
     cd: None,
     check: "check-name".to_string(),
     command: Some("subcommand".to_string()),
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     manual: false,
     output: OutputFormat::Json,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -450,10 +654,22 @@ fn to_string_subcommand_multiple_arg_values_and_args() {
     cd: None,
     check: "check-name".to_string(),
     command: Some("subcommand".to_string()),
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     manual: false,
     output: OutputFormat::Json,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -471,10 +687,22 @@ fn to_string_cd() {
     cd: Some("../subdirectory".to_string()),
     check: "check-name".to_string(),
     command: None,
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     manual: false,
     output: OutputFormat::Json,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
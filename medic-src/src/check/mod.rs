@@ -6,70 +6,159 @@ mod check_test;
 mod check_output;
 mod output_format;
 
+use self::check_output::CheckOutput;
 pub use self::output_format::OutputFormat;
 use crate::cli::Flags;
+use crate::condition::{Condition, EnvCondition};
 use crate::context::Context;
 use crate::error::MedicError;
 use crate::extra;
 use crate::optional_styled::OptionalStyled;
 use crate::recoverable::{Recoverable, Remedy};
+use crate::retry::Retry;
 use crate::runnable::Runnable;
 use crate::theme::current_theme;
-use crate::util::StringOrList;
+use crate::util::{HumanDuration, StringOrList};
 
+use schemars::JsonSchema;
 use serde::Deserialize;
 use std::collections::BTreeMap;
 use std::fmt;
 use std::io::{BufRead, BufReader};
 use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
 use which::which;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq)]
 pub struct Check {
+  /// Flags passed to the command as `--flag <value>`, once per value when given a list.
   pub args: Option<BTreeMap<String, StringOrList>>,
+  /// Directory to change into before running.
   pub cd: Option<String>,
+  /// Name of the check. Runs `medic-check-{check}` from the PATH.
   pub check: String,
+  /// A subcommand passed as the first argument.
   pub command: Option<String>,
+  /// Ids of steps in the same list which must succeed before this one runs.
+  /// When one of them fails or is skipped, this step is skipped too.
+  #[serde(default)]
+  pub depends_on: Vec<String>,
+  /// Environment variables to set when running.
   #[serde(default)]
   pub env: BTreeMap<String, String>,
+  /// Identifies the step in the `depends_on` of other steps in the same list.
+  pub id: Option<String>,
+  /// A shell command which must succeed for this to run, for example `"test -f package.json"`.
+  pub r#if: Option<String>,
+  /// Environment variables which must be set, or a table of values they must have, for this to run.
+  pub if_env: Option<EnvCondition>,
+  /// Files, or glob patterns, whose contents decide whether the step is cached. Relative to the project directory.
+  #[serde(default)]
+  pub inputs: Vec<String>,
+  /// Print the remedy and quit instead of applying it.
   #[serde(default)]
   pub manual: bool,
+  /// The output format of the check, either `json` or `stdio`.
   #[serde(default)]
+  #[schemars(extend("default" = "json"))]
   pub output: OutputFormat,
+  /// Platforms on which to run, for example `Darwin`. Runs everywhere when empty.
   pub platform: Option<Vec<String>>,
+  /// Number of times to run the command again when it fails.
+  #[serde(default)]
+  pub retries: u32,
+  /// Double `retry_delay` after each retry, up to a minute.
+  #[serde(default)]
+  pub retry_backoff: bool,
+  /// How long to wait before retrying, for example `"2s"`. Defaults to one second.
+  pub retry_delay: Option<HumanDuration>,
+  /// Tags selected by `--only tag:<tag>` and `--skip tag:<tag>`.
+  #[serde(default)]
+  pub tags: Vec<String>,
+  /// Kill the command and fail when it runs longer than this, for example `"90s"`. Overrides `--timeout`.
+  pub timeout: Option<HumanDuration>,
+  /// A shell command which must fail for this to run.
+  pub unless: Option<String>,
+  /// Environment variables which must not be set, or a table of values they must not have, for this to run.
+  pub unless_env: Option<EnvCondition>,
+  /// Write STDERR of the check to the terminal as it happens.
   #[serde(default)]
   pub verbose: bool,
 }
 
 impl Runnable for Check {
+  fn condition(&self) -> Condition<'_> {
+    Condition {
+      cd: self.cd.as_deref(),
+      env: Some(&self.env),
+      r#if: self.r#if.as_deref(),
+      if_env: self.if_env.as_ref(),
+      unless: self.unless.as_deref(),
+      unless_env: self.unless_env.as_ref(),
+    }
+  }
+
+  fn depends_on(&self) -> &[String] {
+    &self.depends_on
+  }
+
+  fn id(&self) -> Option<&str> {
+    self.id.as_deref()
+  }
+
+  fn inputs(&self) -> &[String] {
+    &self.inputs
+  }
+
+  fn name(&self) -> Option<&str> {
+    Some(&self.check)
+  }
+
   fn platform(&self) -> &Option<Vec<String>> {
     &self.platform
   }
 
-  fn run(&self, progress: &mut retrogress::ProgressBar, _flags: &mut Flags, _ctx: &Context) -> Recoverable<()> {
+  fn run(&self, progress: &mut retrogress::ProgressBar, flags: &mut Flags, _ctx: &Context) -> Recoverable<()> {
     let verbose = self.verbose();
+    let timeout = self.timeout.map(Duration::from).or(flags.timeout);
+    let retry = Retry::new(self.retries, self.retry_delay, self.retry_backoff);
     let pb = progress.append(&self.to_string());
 
     match self.to_command() {
       Ok(mut command) => {
-        let output = if verbose {
-          command.stderr(Stdio::piped());
-          let mut child = command.spawn()?;
-          let stderr = child
-            .stderr
-            .take()
-            .ok_or("Error capturing stderr of check.")?;
-          let reader = BufReader::new(stderr);
-
-          reader
-            .lines()
-            .map_while(Result::ok)
-            .for_each(|line| progress.println(pb, &line));
-          child.wait_with_output()
-        } else {
-          command.output()
-        };
+        let output = retry.run(progress, pb, &self.to_string(), |progress| {
+          if verbose {
+            // Stdin is left to the terminal.
+            command.stderr(Stdio::piped());
+            let mut child = extra::command::spawn(&mut command, timeout, true)?;
+            let stderr = child
+              .stderr
+              .take()
+              .ok_or("Error capturing stderr of check.")?;
 
+            let mut err_progress = progress.clone();
+            let err_thr = thread::spawn(move || {
+              let reader = BufReader::new(stderr);
+              reader
+                .lines()
+                .map_while(Result::ok)
+                .for_each(|line| err_progress.println(pb, &line));
+            });
+
+            let res = extra::command::wait(child, timeout);
+            err_thr.join().unwrap();
+            res
+          } else {
+            command
+              .stdin(Stdio::null())
+              .stdout(Stdio::piped())
+              .stderr(Stdio::piped());
+            extra::command::output(&mut command, timeout, false)
+          }
+        });
+
+        let (output, timed_out) = extra::command::partial_output(output);
         match output {
           Ok(result) => {
             if result.status.success() {
@@ -77,7 +166,11 @@ impl Runnable for Check {
               Recoverable::Ok(())
             } else {
               progress.failed(pb);
-              let mut output = self.output.clone().parse(result, None);
+              // Partial output is rarely complete enough to parse.
+              let mut output = match timed_out {
+                Some(_) => CheckOutput::partial(result),
+                None => self.output.clone().parse(result, None),
+              };
               output.verbose(verbose);
               eprint!("{output}");
 
@@ -87,15 +180,15 @@ impl Runnable for Check {
                 remedy = Some(Remedy::new(remedy_str.clone(), self.cd.clone(), self.env.clone()));
               }
               if self.manual {
-                Recoverable::Manual(None, remedy)
+                Recoverable::Manual(timed_out, remedy)
               } else {
-                Recoverable::Err(None, remedy)
+                Recoverable::Err(timed_out, remedy)
               }
             }
           }
           Err(err) => {
             progress.failed(pb);
-            Recoverable::Err(Some(err.into()), None)
+            Recoverable::Err(Some(err), None)
           }
         }
       }
@@ -106,6 +199,10 @@ impl Runnable for Check {
     }
   }
 
+  fn tags(&self) -> &[String] {
+    &self.tags
+  }
+
   fn to_command(&self) -> Result<Command, MedicError> {
     let check_cmd = format!("medic-check-{}", self.check);
 
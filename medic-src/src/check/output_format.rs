@@ -1,14 +1,17 @@
 use super::check_output::CheckOutput;
 use crate::std_to_string;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
 pub enum OutputFormat {
   #[default]
   #[serde(rename(deserialize = "json"))]
+  #[schemars(rename = "json")]
   Json,
   #[serde(rename(deserialize = "stdio"))]
+  #[schemars(rename = "stdio")]
   Stdio,
 }
 
@@ -1,12 +1,33 @@
+use crate::filter::Filter;
+use crate::step::Jobs;
+use crate::theme::Theme;
+use crate::util::HumanDuration;
+use clap::Args;
 use std::path::PathBuf;
+use std::time::Duration;
+
+/// The subcommands of `medic`, which a workflow of the same name cannot be
+/// run in place of.
+pub const SUBCOMMANDS: [&str; 16] = [
+  "add", "audit", "config", "doctor", "help", "init", "list", "outdated", "run", "schema", "shipit", "stats", "test",
+  "update", "validate", "workflow",
+];
 
 #[derive(Clone)]
 pub struct Flags {
   pub auto_apply_remedy: bool,
   pub config_path: PathBuf,
+  pub dry_run: bool,
   pub interactive: bool,
+  pub jobs: Jobs,
+  pub keep_going: bool,
+  pub no_cache: bool,
+  pub only: Vec<Filter>,
   pub parallel: bool,
   pub recoverable: bool,
+  pub resume: bool,
+  pub skip: Vec<Filter>,
+  pub timeout: Option<Duration>,
 }
 
 impl Default for Flags {
@@ -14,9 +35,103 @@ impl Default for Flags {
     Self {
       auto_apply_remedy: false,
       config_path: "${PWD}/.config/medic.toml".into(),
+      dry_run: false,
       interactive: false,
+      jobs: Jobs::default(),
+      keep_going: false,
+      no_cache: false,
+      only: vec![],
       parallel: false,
       recoverable: true,
+      resume: false,
+      skip: vec![],
+      timeout: None,
+    }
+  }
+}
+
+// The arguments of every command which runs the steps of a manifest, for
+// `medic` and each `medic-*` binary to flatten into their own. Not a doc
+// comment, as clap would take it for the `about` of those commands.
+#[derive(Args, Debug)]
+pub struct ManifestArgs {
+  /// Path to a file where medic config can be found
+  #[clap(value_parser)]
+  #[arg(short, long, env = "MEDIC_CONFIG", default_value = "${PWD}/.config/medic.toml", value_hint = clap::ValueHint::FilePath)]
+  pub config: PathBuf,
+
+  /// Automatically apply suggested remedies
+  #[arg(short, long, env = "MEDIC_APPLY_REMEDIES", action)]
+  pub apply_remedies: bool,
+
+  /// Print the command each step would run, without running anything
+  #[arg(short = 'n', long, env = "MEDIC_DRY_RUN", action)]
+  pub dry_run: bool,
+
+  /// Provide interactive prompts when possible instead of failing
+  #[arg(short, long, env = "MEDIC_INTERACTIVE", action)]
+  pub interactive: bool,
+
+  /// Maximum number of steps to run at once with --parallel: a number, or
+  /// `auto` for one per available CPU
+  #[arg(short, long, env = "MEDIC_JOBS", default_value = "5")]
+  pub jobs: Jobs,
+
+  /// Run every step even when some fail, then list each failure with its
+  /// suggested remedy
+  #[arg(short, long, env = "MEDIC_KEEP_GOING", action)]
+  pub keep_going: bool,
+
+  /// Run every step, even those whose inputs have not changed since they
+  /// last succeeded
+  #[arg(long, env = "MEDIC_NO_CACHE", action)]
+  pub no_cache: bool,
+
+  /// Only run steps matching one of these filters, either `tag:<tag>` or
+  /// `name:<glob>`. May be given more than once
+  #[arg(long, env = "MEDIC_ONLY", value_delimiter = ',')]
+  pub only: Vec<Filter>,
+
+  /// Run sub-lists of steps in parallel (experimental)
+  #[arg(short, long, env = "MEDIC_PARALLEL", action)]
+  pub parallel: bool,
+
+  /// Skip steps which succeeded in the previous run, unless the manifest
+  /// has changed since
+  #[arg(short, long, env = "MEDIC_RESUME", action)]
+  pub resume: bool,
+
+  /// Skip steps matching any of these filters, either `tag:<tag>` or
+  /// `name:<glob>`. May be given more than once
+  #[arg(long, env = "MEDIC_SKIP", value_delimiter = ',')]
+  pub skip: Vec<Filter>,
+
+  /// Color theme
+  #[arg(short, long, env = "MEDIC_THEME", default_value = "auto")]
+  pub theme: Theme,
+
+  /// Kill and fail any step that runs longer than this, for example 90s or
+  /// 5m, unless the step sets its own `timeout`
+  #[arg(long, env = "MEDIC_TIMEOUT")]
+  pub timeout: Option<HumanDuration>,
+}
+
+impl From<ManifestArgs> for Flags {
+  fn from(args: ManifestArgs) -> Self {
+    Self {
+      auto_apply_remedy: args.apply_remedies,
+      config_path: args.config,
+      dry_run: args.dry_run,
+      interactive: args.interactive,
+      jobs: args.jobs,
+      keep_going: args.keep_going,
+      no_cache: args.no_cache,
+      only: args.only,
+      parallel: args.parallel,
+      resume: args.resume,
+      skip: args.skip,
+      timeout: args.timeout.map(Into::into),
+      ..Self::default()
     }
   }
 }
@@ -0,0 +1,193 @@
+// @related [subject](medic-src/src/condition/mod.rs)
+
+use super::{Condition, EnvCondition};
+use crate::fixture::Project;
+use crate::shell::ShellConfig;
+use crate::util::StringOrList;
+use std::collections::BTreeMap;
+
+fn set(names: &[&str]) -> EnvCondition {
+  EnvCondition::Set(StringOrList(names.iter().map(|name| name.to_string()).collect()))
+}
+
+fn equals(name: &str, value: &str) -> EnvCondition {
+  EnvCondition::Equals(BTreeMap::from([(name.to_string(), value.to_string())]))
+}
+
+#[test]
+fn test_deserialize() {
+  let config: ShellConfig = toml::from_str(
+    r#"
+    name = "Install packages"
+    shell = "npm ci"
+    if = "test -f package.json"
+    unless = "test -d node_modules"
+    if_env = { DATABASE = "postgres" }
+    unless_env = "CI"
+    "#,
+  )
+  .unwrap();
+
+  assert_eq!(config.r#if, Some("test -f package.json".to_string()));
+  assert_eq!(config.unless, Some("test -d node_modules".to_string()));
+  assert_eq!(config.if_env, Some(equals("DATABASE", "postgres")));
+  assert_eq!(config.unless_env, Some(set(&["CI"])));
+
+  let config: ShellConfig = toml::from_str(
+    r#"
+    name = "Deploy"
+    shell = "bin/deploy"
+    if_env = ["DEPLOY_KEY", "DEPLOY_HOST"]
+    "#,
+  )
+  .unwrap();
+  assert_eq!(config.if_env, Some(set(&["DEPLOY_KEY", "DEPLOY_HOST"])));
+}
+
+#[test]
+fn test_skip_reason_without_conditions() {
+  assert_eq!(Condition::default().env_skip_reason(), None);
+  assert_eq!(Condition::default().command_skip_reason(), None);
+}
+
+#[test]
+fn test_skip_reason_if() {
+  let condition = Condition {
+    r#if: Some("true"),
+    ..Default::default()
+  };
+  assert_eq!(condition.command_skip_reason(), None);
+
+  let condition = Condition {
+    r#if: Some("test -f does-not-exist"),
+    ..Default::default()
+  };
+  assert_eq!(
+    condition.command_skip_reason(),
+    Some("`test -f does-not-exist` failed".to_string())
+  );
+}
+
+#[test]
+fn test_skip_reason_unless() {
+  let condition = Condition {
+    unless: Some("false"),
+    ..Default::default()
+  };
+  assert_eq!(condition.command_skip_reason(), None);
+
+  let condition = Condition {
+    unless: Some("true"),
+    ..Default::default()
+  };
+  assert_eq!(condition.command_skip_reason(), Some("`true` succeeded".to_string()));
+}
+
+#[test]
+fn test_skip_reason_if_env() {
+  unsafe {
+    std::env::set_var("MEDIC_CONDITION_TEST_IF", "postgres");
+    std::env::set_var("MEDIC_CONDITION_TEST_IF_EMPTY", "");
+  }
+
+  let present = set(&["MEDIC_CONDITION_TEST_IF"]);
+  let empty = set(&["MEDIC_CONDITION_TEST_IF", "MEDIC_CONDITION_TEST_IF_EMPTY"]);
+  let matching = equals("MEDIC_CONDITION_TEST_IF", "postgres");
+  let different = equals("MEDIC_CONDITION_TEST_IF", "mysql");
+
+  let reason = |env| {
+    Condition {
+      if_env: Some(env),
+      ..Default::default()
+    }
+    .env_skip_reason()
+  };
+
+  assert_eq!(reason(&present), None);
+  assert_eq!(
+    reason(&empty),
+    Some("`MEDIC_CONDITION_TEST_IF_EMPTY` is not set".to_string())
+  );
+  assert_eq!(reason(&matching), None);
+  assert_eq!(
+    reason(&different),
+    Some("`MEDIC_CONDITION_TEST_IF` is not `mysql`".to_string())
+  );
+}
+
+#[test]
+fn test_skip_reason_unless_env() {
+  unsafe {
+    std::env::set_var("MEDIC_CONDITION_TEST_UNLESS", "true");
+  }
+
+  let present = set(&["MEDIC_CONDITION_TEST_UNLESS_MISSING", "MEDIC_CONDITION_TEST_UNLESS"]);
+  let missing = set(&["MEDIC_CONDITION_TEST_UNLESS_MISSING"]);
+  let matching = equals("MEDIC_CONDITION_TEST_UNLESS", "true");
+  let different = equals("MEDIC_CONDITION_TEST_UNLESS", "false");
+
+  let reason = |env| {
+    Condition {
+      unless_env: Some(env),
+      ..Default::default()
+    }
+    .env_skip_reason()
+  };
+
+  assert_eq!(
+    reason(&present),
+    Some("`MEDIC_CONDITION_TEST_UNLESS` is set".to_string())
+  );
+  assert_eq!(reason(&missing), None);
+  assert_eq!(
+    reason(&matching),
+    Some("`MEDIC_CONDITION_TEST_UNLESS` is `true`".to_string())
+  );
+  assert_eq!(reason(&different), None);
+}
+
+#[test]
+fn test_command_skip_reason_runs_in_cd() {
+  let dir = Project::new("");
+  std::fs::write(dir.join("package.json"), "{}").unwrap();
+  let cd = dir.to_string_lossy();
+
+  let condition = Condition {
+    cd: Some(&cd),
+    r#if: Some("test -f package.json"),
+    ..Default::default()
+  };
+  assert_eq!(condition.command_skip_reason(), None);
+
+  let condition = Condition {
+    cd: Some(&cd),
+    unless: Some("test -f package.json"),
+    ..Default::default()
+  };
+  assert_eq!(
+    condition.command_skip_reason(),
+    Some("`test -f package.json` succeeded".to_string())
+  );
+}
+
+#[test]
+fn test_command_skip_reason_runs_with_env() {
+  let env = BTreeMap::from([("MEDIC_CONDITION_TEST_DATABASE".to_string(), "postgres".to_string())]);
+
+  let condition = Condition {
+    env: Some(&env),
+    r#if: Some(r#"test "$MEDIC_CONDITION_TEST_DATABASE" = postgres"#),
+    ..Default::default()
+  };
+  assert_eq!(condition.command_skip_reason(), None);
+
+  let condition = Condition {
+    env: Some(&env),
+    unless: Some(r#"test -n "$MEDIC_CONDITION_TEST_DATABASE""#),
+    ..Default::default()
+  };
+  assert_eq!(
+    condition.command_skip_reason(),
+    Some(r#"`test -n "$MEDIC_CONDITION_TEST_DATABASE"` succeeded"#.to_string())
+  );
+}
@@ -0,0 +1,115 @@
+// @related [tests](medic-src/src/condition/condition_test.rs)
+
+#[cfg(test)]
+mod condition_test;
+
+use crate::extra;
+use crate::util::StringOrList;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::process::{Command, Stdio};
+
+/// Environment variables looked at by `if_env` and `unless_env`.
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq)]
+#[serde(untagged)]
+pub enum EnvCondition {
+  /// Names of variables which are set to a value other than an empty string.
+  Set(StringOrList),
+  /// Variables with exactly these values.
+  Equals(BTreeMap<String, String>),
+}
+
+impl EnvCondition {
+  // The first variable which does not match, described for a skip reason.
+  fn unmatched(&self) -> Option<String> {
+    match self {
+      EnvCondition::Set(names) => names
+        .into_iter()
+        .find(|name| !is_set(name))
+        .map(|name| format!("`{name}` is not set")),
+      EnvCondition::Equals(vars) => vars
+        .iter()
+        .find(|(name, value)| std::env::var(name).ok().as_ref() != Some(*value))
+        .map(|(name, value)| format!("`{name}` is not `{value}`")),
+    }
+  }
+
+  // The first variable which matches, described for a skip reason.
+  fn matched(&self) -> Option<String> {
+    match self {
+      EnvCondition::Set(names) => names
+        .into_iter()
+        .find(|name| is_set(name))
+        .map(|name| format!("`{name}` is set")),
+      EnvCondition::Equals(vars) => vars
+        .iter()
+        .find(|(name, value)| std::env::var(name).ok().as_ref() == Some(*value))
+        .map(|(name, value)| format!("`{name}` is `{value}`")),
+    }
+  }
+}
+
+/// The conditions a step sets on whether it runs, all of which must hold.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Condition<'a> {
+  /// The step's `cd`, in which `if` and `unless` are run.
+  pub cd: Option<&'a str>,
+  /// The step's `env`, with which `if` and `unless` are run.
+  pub env: Option<&'a BTreeMap<String, String>>,
+  /// A shell command which must succeed.
+  pub r#if: Option<&'a str>,
+  pub if_env: Option<&'a EnvCondition>,
+  /// A shell command which must fail.
+  pub unless: Option<&'a str>,
+  pub unless_env: Option<&'a EnvCondition>,
+}
+
+impl Condition<'_> {
+  /// Returns why the step should be skipped given `if_env` and `unless_env`.
+  pub fn env_skip_reason(&self) -> Option<String> {
+    self
+      .if_env
+      .and_then(EnvCondition::unmatched)
+      .or_else(|| self.unless_env.and_then(EnvCondition::matched))
+  }
+
+  /// Returns why the step should be skipped given `if` and `unless`, running
+  /// their commands to find out.
+  pub fn command_skip_reason(&self) -> Option<String> {
+    if let Some(shell) = self.r#if
+      && !self.succeeds(shell)
+    {
+      return Some(format!("`{shell}` failed"));
+    }
+    if let Some(shell) = self.unless
+      && self.succeeds(shell)
+    {
+      return Some(format!("`{shell}` succeeded"));
+    }
+    None
+  }
+
+  fn succeeds(&self, shell: &str) -> bool {
+    let mut command = Command::new("sh");
+    if let Some(dir) = self.cd {
+      command.current_dir(dir);
+    }
+    if let Some(env) = self.env {
+      extra::command::with_env(&mut command, env);
+    }
+    command
+      .arg("-c")
+      .arg(shell)
+      .stdin(Stdio::null())
+      .stdout(Stdio::null())
+      .stderr(Stdio::null())
+      .status()
+      .is_ok_and(|status| status.success())
+  }
+}
+
+fn is_set(name: &str) -> bool {
+  std::env::var_os(name).is_some_and(|value| !value.is_empty())
+}
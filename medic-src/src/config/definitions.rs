@@ -0,0 +1,76 @@
+// @related [tests](medic-src/src/config/definitions_test.rs)
+
+use super::loader;
+use crate::error::MedicError;
+
+use toml::{Table, Value};
+
+/// Replaces each `{ use = "name", ... }` step in `manifest` with the step of
+/// that name from its `[definitions]` table. Any other keys of the step
+/// override those of the definition, except `env`, which is merged.
+pub fn apply(manifest: &mut Table) -> Result<(), MedicError> {
+  let definitions = match manifest.get("definitions") {
+    None => Table::new(),
+    Some(Value::Table(definitions)) => definitions.clone(),
+    Some(_) => return Err("`definitions` must be a table of steps".into()),
+  };
+
+  for (section_name, steps) in loader::step_lists(manifest) {
+    expand_steps(steps, &definitions, &section_name)?;
+  }
+
+  Ok(())
+}
+
+fn expand_steps(steps: &mut [Value], definitions: &Table, section_name: &str) -> Result<(), MedicError> {
+  for step in steps.iter_mut() {
+    if let Some(nested) = loader::nested_steps_mut(step) {
+      expand_steps(nested, definitions, section_name)?;
+    } else if let Value::Table(table) = step
+      && table.contains_key("use")
+    {
+      *table = expand(table, definitions, section_name, &mut vec![])?;
+    }
+  }
+  Ok(())
+}
+
+// Definitions may themselves `use` other definitions; `stack` holds the names
+// being expanded so that cycles are reported rather than recursing forever.
+fn expand(step: &Table, definitions: &Table, section_name: &str, stack: &mut Vec<String>) -> Result<Table, MedicError> {
+  let Some(Value::String(name)) = step.get("use") else {
+    return Err(format!("`use` in [{section_name}] must be the name of a definition").into());
+  };
+
+  if stack.contains(name) {
+    stack.push(name.clone());
+    return Err(format!("Definition cycle detected in [definitions]: {}", stack.join(" -> ")).into());
+  }
+
+  let definition = match definitions.get(name) {
+    Some(Value::Table(definition)) => definition,
+    Some(_) => return Err(format!("Definition `{name}` must be a table").into()),
+    None => return Err(format!("Unknown definition `{name}` used in [{section_name}]").into()),
+  };
+
+  let mut expanded = if definition.contains_key("use") {
+    stack.push(name.clone());
+    let expanded = expand(definition, definitions, section_name, stack)?;
+    stack.pop();
+    expanded
+  } else {
+    definition.clone()
+  };
+
+  for (key, value) in step {
+    match (key.as_str(), expanded.get_mut(key), value) {
+      ("use", _, _) => {}
+      ("env", Some(Value::Table(env)), Value::Table(vars)) => env.extend(vars.clone()),
+      _ => {
+        expanded.insert(key.clone(), value.clone());
+      }
+    }
+  }
+
+  Ok(expanded)
+}
@@ -0,0 +1,91 @@
+// @related [subject](medic-src/src/config/definitions.rs)
+
+use super::definitions::*;
+use super::manifest::Manifest;
+use crate::AppResult;
+use crate::DoctorStep;
+use crate::ShipitStep;
+use crate::Step;
+use std::collections::BTreeMap;
+use std::path::Path;
+use toml::Table;
+
+fn load(path: &str) -> Manifest {
+  match Manifest::new(Path::new(path)) {
+    AppResult::Ok(manifest) => manifest,
+    AppResult::Err(err) => panic!("Unable to load manifest: {}", err.unwrap()),
+    AppResult::Quit => panic!("Unexpected quit"),
+  }
+}
+
+fn apply_error(toml: &str) -> String {
+  let mut manifest: Table = toml::from_str(toml).unwrap();
+  apply(&mut manifest).unwrap_err().to_string()
+}
+
+#[test]
+fn apply_expands_definitions() {
+  let manifest = load("../fixtures/definitions/medic.toml");
+  let checks = manifest.doctor.unwrap().checks;
+
+  let DoctorStep::Check(check) = &checks[0] else {
+    panic!("Expected a check, got {:?}", checks[0]);
+  };
+  assert_eq!(check.check, "tool-versions");
+  assert_eq!(check.command, Some("package-installed".to_string()));
+
+  let DoctorStep::Shell(shell) = &checks[1] else {
+    panic!("Expected a shell action, got {:?}", checks[1]);
+  };
+  assert_eq!(shell.name, "Setup again");
+  assert_eq!(shell.shell, "bin/dev/setup");
+  assert_eq!(
+    shell.env,
+    BTreeMap::from([
+      ("FORCE".to_string(), "true".to_string()),
+      ("QUIET".to_string(), "true".to_string()),
+    ])
+  );
+}
+
+#[test]
+fn apply_expands_nested_and_chained_definitions() {
+  let manifest = load("../fixtures/definitions/medic.toml");
+
+  let steps = manifest.update.unwrap().steps;
+  let Step::Steps(nested) = &steps[0] else {
+    panic!("Expected nested steps, got {:?}", steps[0]);
+  };
+  let Step::Shell(shell) = &nested[0] else {
+    panic!("Expected a shell action, got {:?}", nested[0]);
+  };
+  assert_eq!(shell.name, "Setup");
+  assert!(shell.verbose);
+
+  let steps = manifest.shipit.unwrap().steps;
+  let ShipitStep::Check(check) = &steps[0] else {
+    panic!("Expected a check, got {:?}", steps[0]);
+  };
+  assert_eq!(check.cd, Some("../fixtures/definitions/sub".to_string()));
+}
+
+#[test]
+fn apply_reports_unknown_definitions() {
+  assert_eq!(
+    apply_error(r#"doctor = { checks = [{ use = "missing" }] }"#),
+    "Unknown definition `missing` used in [doctor]"
+  );
+}
+
+#[test]
+fn apply_reports_cycles() {
+  assert_eq!(
+    apply_error(
+      r#"
+      definitions = { a = { use = "b" }, b = { use = "a" } }
+      doctor = { checks = [{ use = "a" }] }
+      "#
+    ),
+    "Definition cycle detected in [definitions]: a -> b -> a"
+  );
+}
@@ -0,0 +1,94 @@
+// @related [tests](medic-src/src/config/discovery_test.rs)
+
+use super::loader;
+use crate::error::MedicError;
+use crate::extra;
+
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// The manifest path used when none is given with `--config` or
+/// `MEDIC_CONFIG`.
+pub const DEFAULT_PATH: &str = "${PWD}/.config/medic.toml";
+
+const MANIFEST: &str = ".config/medic.toml";
+
+/// Expands environment variables in `path`. When `path` is the default and
+/// no manifest exists there, parent directories are searched instead.
+pub fn locate(path: &Path) -> Result<PathBuf, MedicError> {
+  let expanded = PathBuf::from(extra::env::subst(&path.to_string_lossy())?);
+  if expanded.exists() || path != Path::new(DEFAULT_PATH) {
+    return Ok(expanded);
+  }
+
+  Ok(find(&std::env::current_dir()?).unwrap_or(expanded))
+}
+
+/// Moves medic to the project directory of the manifest found in a parent
+/// directory by `locate`, so that the commands of steps run from there as
+/// they would when medic is run from the project directory itself.
+pub fn enter(path: &Path) -> Result<(), MedicError> {
+  let manifest = locate(path)?;
+  let expanded = PathBuf::from(extra::env::subst(&path.to_string_lossy())?);
+  if manifest == expanded {
+    return Ok(());
+  }
+
+  let dir = project_dir(&manifest);
+  std::env::set_current_dir(&dir)?;
+  unsafe {
+    std::env::set_var("PWD", &dir);
+  }
+  Ok(())
+}
+
+/// Joins the relative `cd` of each step in `manifest` onto `project_dir`,
+/// so that steps run in the same directory wherever medic is run from. As
+/// medic usually runs from the project directory, the `cd` of steps is left
+/// as written when it does.
+pub(crate) fn resolve_dirs(manifest: &mut Table, project_dir: &Path) {
+  let current_dir = std::env::current_dir().and_then(std::fs::canonicalize).ok();
+  if current_dir.is_some() && current_dir == std::fs::canonicalize(project_dir).ok() {
+    return;
+  }
+  for (_, steps) in loader::step_lists(manifest) {
+    resolve_steps(steps, project_dir);
+  }
+}
+
+fn resolve_steps(steps: &mut [Value], project_dir: &Path) {
+  for step in steps.iter_mut() {
+    if let Some(nested) = loader::nested_steps_mut(step) {
+      resolve_steps(nested, project_dir);
+    } else if let Some(Value::String(dir)) = step.get_mut("cd")
+      && Path::new(dir.as_str()).is_relative()
+    {
+      *dir = project_dir.join(&*dir).to_string_lossy().into_owned();
+    }
+  }
+}
+
+/// Searches `start` and its parents for `.config/medic.toml`, stopping at the
+/// root of a git repository or of the filesystem.
+pub fn find(start: &Path) -> Option<PathBuf> {
+  for dir in start.ancestors() {
+    let manifest = dir.join(MANIFEST);
+    if manifest.is_file() {
+      return Some(manifest);
+    }
+    if dir.join(".git").exists() {
+      return None;
+    }
+  }
+  None
+}
+
+/// The directory a manifest belongs to. For `.config/medic.toml`, this is
+/// the parent of `.config`.
+pub fn project_dir(manifest: &Path) -> PathBuf {
+  let dir = manifest.parent().unwrap_or(Path::new("."));
+  match dir.file_name() {
+    Some(name) if name == ".config" => dir.parent().unwrap_or(Path::new(".")).to_path_buf(),
+    _ => dir.to_path_buf(),
+  }
+}
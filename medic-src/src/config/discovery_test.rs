@@ -0,0 +1,110 @@
+// @related [subject](medic-src/src/config/discovery.rs)
+
+use super::discovery::*;
+use indoc::indoc;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::Table;
+
+fn project(name: &str) -> PathBuf {
+  let root = std::env::temp_dir().join(format!("medic-discovery-{name}-{}", std::process::id()));
+  let _ = fs::remove_dir_all(&root);
+  fs::create_dir_all(root.join("nested/deeper")).unwrap();
+  root
+}
+
+fn manifest(dir: &Path) -> PathBuf {
+  let path = dir.join(".config/medic.toml");
+  fs::create_dir_all(path.parent().unwrap()).unwrap();
+  fs::write(&path, "").unwrap();
+  path
+}
+
+#[test]
+fn find_walks_up_to_manifest() {
+  let root = project("walk");
+  let path = manifest(&root);
+
+  assert_eq!(find(&root.join("nested/deeper")), Some(path.clone()));
+  assert_eq!(find(&root), Some(path));
+
+  fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn find_prefers_nearest_manifest() {
+  let root = project("nearest");
+  manifest(&root);
+  let nested = manifest(&root.join("nested"));
+
+  assert_eq!(find(&root.join("nested/deeper")), Some(nested));
+
+  fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn find_stops_at_git_root() {
+  let root = project("git");
+  manifest(&root);
+  fs::create_dir_all(root.join("nested/.git")).unwrap();
+
+  assert_eq!(find(&root.join("nested/deeper")), None);
+
+  fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn locate_leaves_explicit_paths_alone() {
+  assert_eq!(
+    locate(Path::new("../fixtures/missing/medic.toml")).unwrap(),
+    PathBuf::from("../fixtures/missing/medic.toml")
+  );
+}
+
+#[test]
+fn locate_searches_parents_without_moving() {
+  let current_dir = std::env::current_dir().unwrap();
+  let found = locate(Path::new(DEFAULT_PATH)).unwrap();
+
+  assert_eq!(found, current_dir.parent().unwrap().join(".config/medic.toml"));
+  assert_eq!(std::env::current_dir().unwrap(), current_dir);
+}
+
+#[test]
+fn resolve_dirs_joins_relative_dirs_onto_project_dir() {
+  let mut manifest: Table = toml::from_str(indoc! {r#"
+    [update]
+    steps = [
+      { step = "setup", cd = "api" },
+      [{ shell = "bin/seed", cd = "/srv/db" }],
+      { shell = "bin/dev/up" },
+    ]
+  "#})
+  .unwrap();
+  resolve_dirs(&mut manifest, Path::new("/work/app"));
+
+  let steps = manifest["update"]["steps"].as_array().unwrap();
+  assert_eq!(steps[0]["cd"].as_str(), Some("/work/app/api"));
+  assert_eq!(steps[1][0]["cd"].as_str(), Some("/srv/db"));
+  assert_eq!(steps[2].get("cd"), None);
+}
+
+#[test]
+fn resolve_dirs_leaves_dirs_as_written_in_the_project_dir() {
+  let mut manifest: Table = toml::from_str("[update]\nsteps = [{ step = \"setup\", cd = \"api\" }]").unwrap();
+  resolve_dirs(&mut manifest, &std::env::current_dir().unwrap());
+
+  assert_eq!(manifest["update"]["steps"][0]["cd"].as_str(), Some("api"));
+}
+
+#[test]
+fn project_dir_is_parent_of_config_dir() {
+  assert_eq!(
+    project_dir(Path::new("/work/app/.config/medic.toml")),
+    PathBuf::from("/work/app")
+  );
+  assert_eq!(
+    project_dir(Path::new("/work/app/medic.toml")),
+    PathBuf::from("/work/app")
+  );
+}
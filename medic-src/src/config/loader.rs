@@ -0,0 +1,195 @@
+// @related [tests](medic-src/src/config/loader_test.rs)
+
+use crate::error::MedicError;
+use crate::extra;
+
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// Reads the manifest at `path` into a TOML table, recursively merging any
+/// files listed in its `include` key.
+///
+/// Entries from the including file come first, followed by each include in
+/// the order listed. Glob patterns are expanded in alphabetical order.
+/// Relative include paths resolve against the directory of the including
+/// file.
+pub fn load(path: &Path) -> Result<Table, MedicError> {
+  let mut sources = sources(path)?.into_iter();
+  let mut table = sources.next().map(|(_, table)| table).unwrap_or_default();
+  for (_, other) in sources {
+    merge(&mut table, other);
+  }
+  Ok(table)
+}
+
+/// Reads the manifest at `path` and each file it includes, recursively,
+/// without merging them, so that problems found once merged can be traced
+/// back to the file they came from. Files are listed in the order `load`
+/// merges them, the manifest at `path` first.
+pub(crate) fn sources(path: &Path) -> Result<Vec<(PathBuf, Table)>, MedicError> {
+  let mut sources = vec![];
+  load_file(path, &mut vec![], &mut sources)?;
+  Ok(sources)
+}
+
+fn load_file(path: &Path, stack: &mut Vec<PathBuf>, sources: &mut Vec<(PathBuf, Table)>) -> Result<(), MedicError> {
+  let canonical =
+    std::fs::canonicalize(path).map_err(|err| format!("Unable to read manifest {}\r\n{err}", path.display()))?;
+
+  if stack.contains(&canonical) {
+    let chain: Vec<String> = stack
+      .iter()
+      .chain(std::iter::once(&canonical))
+      .map(|p| p.display().to_string())
+      .collect();
+    return Err(
+      format!(
+        "Include cycle detected in manifest {}\r\n{}",
+        path.display(),
+        chain.join(" -> ")
+      )
+      .into(),
+    );
+  }
+
+  let contents = std::fs::read_to_string(&canonical)
+    .map_err(|err| format!("Unable to read manifest {}\r\n{err}", path.display()))?;
+  let mut table: Table =
+    toml::from_str(&contents).map_err(|err| format!("Unable to parse manifest {}\r\n{err}", path.display()))?;
+
+  let includes = match table.remove("include") {
+    Some(value) => include_patterns(value, path)?,
+    None => vec![],
+  };
+
+  let dir = canonical.parent().unwrap_or(Path::new("/")).to_path_buf();
+  sources.push((path.to_path_buf(), table));
+  stack.push(canonical);
+  for pattern in includes {
+    for included in resolve(&pattern, &dir, path)? {
+      load_file(&included, stack, sources)?;
+    }
+  }
+  stack.pop();
+
+  Ok(())
+}
+
+pub(crate) fn include_patterns(value: Value, path: &Path) -> Result<Vec<String>, MedicError> {
+  let invalid = || {
+    format!(
+      "`include` in manifest {} must be a string or a list of strings",
+      path.display()
+    )
+  };
+
+  match value {
+    Value::String(pattern) => Ok(vec![pattern]),
+    Value::Array(values) => values
+      .into_iter()
+      .map(|value| match value {
+        Value::String(pattern) => Ok(pattern),
+        _ => Err(invalid().into()),
+      })
+      .collect(),
+    _ => Err(invalid().into()),
+  }
+}
+
+pub(crate) fn resolve(pattern: &str, dir: &Path, including: &Path) -> Result<Vec<PathBuf>, MedicError> {
+  let expanded = dir.join(extra::env::subst(pattern)?);
+
+  if pattern.contains(['*', '?', '[']) {
+    let matches = glob::glob(&expanded.to_string_lossy()).map_err(|err| {
+      format!(
+        "Invalid include pattern `{pattern}` in manifest {}\r\n{err}",
+        including.display()
+      )
+    })?;
+    let mut paths = matches
+      .collect::<Result<Vec<PathBuf>, _>>()
+      .map_err(|err| {
+        format!(
+          "Unable to read include `{pattern}` in manifest {}\r\n{err}",
+          including.display()
+        )
+      })?;
+    paths.sort();
+    Ok(paths)
+  } else if expanded.exists() {
+    Ok(vec![expanded])
+  } else {
+    Err(
+      format!(
+        "Included manifest {} does not exist (included from {})",
+        expanded.display(),
+        including.display()
+      )
+      .into(),
+    )
+  }
+}
+
+/// Merges `other` into `base`. Tables are merged recursively and lists are
+/// appended; for any other value, `base` takes precedence.
+pub(crate) fn merge(base: &mut Table, other: Table) {
+  for (key, value) in other {
+    match (base.get_mut(&key), value) {
+      (Some(Value::Table(existing)), Value::Table(incoming)) => merge(existing, incoming),
+      (Some(Value::Array(existing)), Value::Array(incoming)) => existing.extend(incoming),
+      (Some(_), _) => {}
+      (None, value) => {
+        base.insert(key, value);
+      }
+    }
+  }
+}
+
+/// Every list of steps in `manifest`, labeled by its section: the `checks`
+/// or `steps` of each section, and the `steps` of each workflow.
+pub(crate) fn step_lists(manifest: &mut Table) -> Vec<(String, &mut Vec<Value>)> {
+  let mut lists = vec![];
+
+  for (section_name, section) in manifest.iter_mut() {
+    let Value::Table(section) = section else {
+      continue;
+    };
+    match section_name.as_str() {
+      "definitions" | "vars" => {}
+      "workflows" => {
+        for (name, workflow) in section.iter_mut() {
+          if let Value::Table(workflow) = workflow {
+            lists.extend(arrays(workflow).map(|steps| (format!("workflows.{name}"), steps)));
+          }
+        }
+      }
+      _ => lists.extend(arrays(section).map(|steps| (section_name.clone(), steps))),
+    }
+  }
+
+  lists
+}
+
+fn arrays(table: &mut Table) -> impl Iterator<Item = &mut Vec<Value>> {
+  table
+    .iter_mut()
+    .filter_map(|(_, value)| value.as_array_mut())
+}
+
+/// The steps of a nested list, written either as a list of steps or as a
+/// table with `steps` and options such as `jobs`.
+pub(crate) fn nested_steps(step: &Value) -> Option<&Vec<Value>> {
+  match step {
+    Value::Array(steps) => Some(steps),
+    Value::Table(table) => table.get("steps")?.as_array(),
+    _ => None,
+  }
+}
+
+pub(crate) fn nested_steps_mut(step: &mut Value) -> Option<&mut Vec<Value>> {
+  match step {
+    Value::Array(steps) => Some(steps),
+    Value::Table(table) => table.get_mut("steps")?.as_array_mut(),
+    _ => None,
+  }
+}
@@ -0,0 +1,149 @@
+// @related [subject](medic-src/src/config/loader.rs)
+
+use super::loader::*;
+use super::manifest::Manifest;
+use crate::AppResult;
+use crate::AuditStep;
+use crate::DoctorStep;
+use crate::Step;
+use indoc::indoc;
+use std::path::Path;
+use toml::Table;
+
+fn check_names<'a>(checks: impl Iterator<Item = &'a DoctorStep>) -> Vec<String> {
+  checks
+    .map(|step| match step {
+      DoctorStep::Check(check) => check.check.clone(),
+      other => other.to_string(),
+    })
+    .collect()
+}
+
+#[test]
+fn load_merges_includes_in_order() {
+  let manifest = match Manifest::new(Path::new("../fixtures/include/medic.toml")) {
+    AppResult::Ok(manifest) => manifest,
+    AppResult::Err(err) => panic!("Unable to load manifest: {}", err.unwrap()),
+    AppResult::Quit => panic!("Unexpected quit"),
+  };
+
+  assert_eq!(
+    check_names(manifest.doctor.unwrap().checks.iter()),
+    vec!["main", "a", "b"]
+  );
+
+  let audit: Vec<String> = manifest
+    .audit
+    .unwrap()
+    .checks
+    .iter()
+    .map(|step| match step {
+      AuditStep::Check(check) => check.check.clone(),
+      other => other.to_string(),
+    })
+    .collect();
+  assert_eq!(audit, vec!["shared", "extra"]);
+
+  let test = manifest.test.unwrap().checks;
+  assert_eq!(test.len(), 1);
+  assert!(matches!(&test[0], Step::Shell(shell) if shell.name == "b test"));
+}
+
+#[test]
+fn load_detects_include_cycles() {
+  let err = load(Path::new("../fixtures/include-cycle/medic.toml")).unwrap_err();
+  let msg = err.to_string();
+
+  assert!(msg.starts_with("Include cycle detected in manifest"), "{msg}");
+  assert!(msg.contains("include-cycle/medic.toml -> "), "{msg}");
+  assert!(msg.contains("include-cycle/other.toml -> "), "{msg}");
+}
+
+#[test]
+fn load_names_unparseable_includes() {
+  let err = load(Path::new("../fixtures/include-invalid/medic.toml")).unwrap_err();
+  let msg = err.to_string();
+
+  assert!(msg.starts_with("Unable to parse manifest "), "{msg}");
+  assert!(msg.contains("include-invalid/broken.toml"), "{msg}");
+}
+
+#[test]
+fn load_names_includes_with_invalid_steps() {
+  let err = match Manifest::new(Path::new("../fixtures/include-invalid-step/medic.toml")) {
+    AppResult::Err(err) => err.unwrap().to_string(),
+    AppResult::Ok(_) => panic!("Expected the manifest to be invalid"),
+    AppResult::Quit => panic!("Unexpected quit"),
+  };
+
+  assert!(err.starts_with("Unable to parse manifest "), "{err}");
+  assert!(err.contains("include-invalid-step/extra.toml"), "{err}");
+}
+
+#[test]
+fn sources_lists_each_file_in_merge_order() {
+  let sources = sources(Path::new("../fixtures/include/medic.toml")).unwrap();
+  let paths: Vec<String> = sources
+    .iter()
+    .map(|(path, _)| path.display().to_string())
+    .collect();
+
+  assert_eq!(paths[0], "../fixtures/include/medic.toml");
+  let included = [
+    "doctor/a.toml",
+    "doctor/b.toml",
+    "shared/medic.toml",
+    "shared/../extra.toml",
+  ];
+  assert_eq!(paths.len(), included.len() + 1, "{paths:?}");
+  for (path, expected) in paths[1..].iter().zip(included) {
+    assert!(path.ends_with(expected), "{paths:?}");
+  }
+  assert!(
+    sources
+      .iter()
+      .all(|(_, table)| !table.contains_key("include"))
+  );
+}
+
+#[test]
+fn load_names_missing_includes() {
+  let err = load(Path::new("../fixtures/include-missing/medic.toml")).unwrap_err();
+  let msg = err.to_string();
+
+  assert!(msg.contains("include-missing/nope.toml does not exist"), "{msg}");
+  assert!(
+    msg.contains("(included from ../fixtures/include-missing/medic.toml)"),
+    "{msg}"
+  );
+}
+
+#[test]
+fn merge_appends_lists_and_keeps_base_values() {
+  let mut base: Table = toml::from_str(indoc! {r#"
+    name = "base"
+    [doctor]
+    checks = [{ check = "first" }]
+    "#})
+  .unwrap();
+  let other: Table = toml::from_str(indoc! {r#"
+    name = "other"
+    [doctor]
+    checks = [{ check = "second" }]
+    [test]
+    checks = []
+    "#})
+  .unwrap();
+
+  merge(&mut base, other);
+
+  let expected: Table = toml::from_str(indoc! {r#"
+    name = "base"
+    [doctor]
+    checks = [{ check = "first" }, { check = "second" }]
+    [test]
+    checks = []
+    "#})
+  .unwrap();
+  assert_eq!(base, expected);
+}
@@ -0,0 +1,243 @@
+// @related [tests](medic-src/src/config/local_override_test.rs)
+
+use super::loader;
+use crate::error::MedicError;
+
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+const OVERRIDABLE_KEYS: [&str; 3] = ["cd", "env", "verbose"];
+
+/// Returns the path of the local override for the manifest at `path`, if one
+/// exists. For `.config/medic.toml`, this is `.config/medic.local.toml`.
+pub fn path(manifest_path: &Path) -> Option<PathBuf> {
+  let stem = manifest_path.file_stem()?.to_string_lossy();
+  let file_name = match manifest_path.extension() {
+    Some(ext) => format!("{stem}.local.{}", ext.to_string_lossy()),
+    None => format!("{stem}.local"),
+  };
+  let local = manifest_path.with_file_name(file_name);
+
+  if local.is_file() { Some(local) } else { None }
+}
+
+/// Layers the local override at `path` on top of `manifest`.
+///
+/// Each section of the override may `disable` steps by name, `override` the
+/// `cd`, `env` or `verbose` of steps by name, and append steps to the
+/// section's `checks` or `steps` list. Its `[vars]` replace those of the
+/// shared manifest.
+pub fn apply(manifest: &mut Table, path: &Path) -> Result<(), MedicError> {
+  let mut local = loader::load(path)?;
+
+  if let Some(Value::Table(local_vars)) = local.remove("vars") {
+    let vars = manifest
+      .entry("vars")
+      .or_insert_with(|| Value::Table(Table::new()));
+    if let Value::Table(vars) = vars {
+      vars.extend(local_vars);
+    }
+  }
+
+  for (section_name, section) in local.iter_mut() {
+    let Value::Table(section) = section else {
+      continue;
+    };
+
+    if section_name == "workflows" {
+      for (name, workflow) in section.iter_mut() {
+        let Value::Table(workflow) = workflow else {
+          continue;
+        };
+        let shared = manifest
+          .get_mut("workflows")
+          .and_then(Value::as_table_mut)
+          .and_then(|workflows| workflows.get_mut(name))
+          .and_then(Value::as_table_mut);
+        apply_section(shared, workflow, &format!("workflows.{name}"), path)?;
+      }
+    } else {
+      let shared = manifest.get_mut(section_name).and_then(Value::as_table_mut);
+      apply_section(shared, section, section_name, path)?;
+    }
+  }
+
+  loader::merge(manifest, local);
+  Ok(())
+}
+
+/// The names by which a step may be referenced: its `name`, the name of its
+/// check or step, and `<check>: <command>` when a subcommand is given.
+pub(crate) fn step_names(step: &Table) -> Vec<String> {
+  let mut names = vec![];
+  if let Some(name) = step.get("name").and_then(Value::as_str) {
+    names.push(name.to_string());
+  }
+  if let Some(plugin) = step
+    .get("check")
+    .or_else(|| step.get("step"))
+    .and_then(Value::as_str)
+  {
+    names.push(plugin.to_string());
+    if let Some(command) = step.get("command").and_then(Value::as_str) {
+      names.push(format!("{plugin}: {command}"));
+    }
+  }
+  names
+}
+
+// Disables and overrides steps of the shared section `shared` as described by
+// the local `section`, which is labeled `section_name` in errors.
+fn apply_section(
+  shared: Option<&mut Table>,
+  section: &mut Table,
+  section_name: &str,
+  path: &Path,
+) -> Result<(), MedicError> {
+  let disable = section.remove("disable");
+  let overrides = section.remove("override");
+  if disable.is_none() && overrides.is_none() {
+    return Ok(());
+  }
+
+  let steps = shared.and_then(steps_mut).ok_or_else(|| {
+    format!(
+      "Local override {} changes steps in [{section_name}], which has no steps",
+      path.display()
+    )
+  })?;
+
+  if let Some(disable) = disable {
+    for name in string_list(disable, path, section_name)? {
+      let before = count(steps, &name);
+      retain(steps, &name);
+      if before == 0 {
+        return Err(unknown_step(path, section_name, &name));
+      }
+    }
+  }
+
+  if let Some(overrides) = overrides {
+    let Value::Table(overrides) = overrides else {
+      return Err(
+        format!(
+          "`override` in [{section_name}] of local override {} must be a table of step names",
+          path.display()
+        )
+        .into(),
+      );
+    };
+
+    for (name, fields) in overrides {
+      let Value::Table(fields) = fields else {
+        return Err(format!("Override for `{name}` in [{section_name}] must be a table").into());
+      };
+      if let Some(key) = fields
+        .keys()
+        .find(|key| !OVERRIDABLE_KEYS.contains(&key.as_str()))
+      {
+        return Err(
+          format!(
+            "Override for `{name}` in [{section_name}] sets `{key}`, but only `cd`, `env` and `verbose` may be overridden"
+          )
+          .into(),
+        );
+      }
+      if override_steps(steps, &name, &fields) == 0 {
+        return Err(unknown_step(path, section_name, &name));
+      }
+    }
+  }
+
+  Ok(())
+}
+
+fn steps_mut(section: &mut Table) -> Option<&mut Vec<Value>> {
+  if section.contains_key("checks") {
+    section.get_mut("checks")?.as_array_mut()
+  } else {
+    section.get_mut("steps")?.as_array_mut()
+  }
+}
+
+fn matches(step: &Value, name: &str) -> bool {
+  step
+    .as_table()
+    .is_some_and(|table| step_names(table).iter().any(|n| n == name))
+}
+
+fn count(steps: &[Value], name: &str) -> usize {
+  steps
+    .iter()
+    .map(|step| match loader::nested_steps(step) {
+      Some(nested) => count(nested, name),
+      None if matches(step, name) => 1,
+      None => 0,
+    })
+    .sum()
+}
+
+fn retain(steps: &mut Vec<Value>, name: &str) {
+  steps.retain(|step| !matches(step, name));
+  for step in steps.iter_mut() {
+    if let Some(nested) = loader::nested_steps_mut(step) {
+      retain(nested, name);
+    }
+  }
+}
+
+fn override_steps(steps: &mut [Value], name: &str, fields: &Table) -> usize {
+  let mut overridden = 0;
+  for step in steps.iter_mut() {
+    if let Some(nested) = loader::nested_steps_mut(step) {
+      overridden += override_steps(nested, name, fields);
+      continue;
+    }
+    if !matches(step, name) {
+      continue;
+    }
+    let Some(table) = step.as_table_mut() else {
+      continue;
+    };
+    for (key, value) in fields {
+      match (key.as_str(), table.get_mut(key), value) {
+        ("env", Some(Value::Table(env)), Value::Table(vars)) => {
+          env.extend(vars.clone());
+        }
+        _ => {
+          table.insert(key.clone(), value.clone());
+        }
+      }
+    }
+    overridden += 1;
+  }
+  overridden
+}
+
+fn string_list(value: Value, path: &Path, section_name: &str) -> Result<Vec<String>, MedicError> {
+  let invalid = || {
+    MedicError::from(format!(
+      "`disable` in [{section_name}] of local override {} must be a list of step names",
+      path.display()
+    ))
+  };
+
+  match value {
+    Value::Array(values) => values
+      .into_iter()
+      .map(|value| match value {
+        Value::String(name) => Ok(name),
+        _ => Err(invalid()),
+      })
+      .collect(),
+    _ => Err(invalid()),
+  }
+}
+
+fn unknown_step(path: &Path, section_name: &str, name: &str) -> MedicError {
+  format!(
+    "Local override {} refers to unknown step `{name}` in [{section_name}]",
+    path.display()
+  )
+  .into()
+}
@@ -0,0 +1,127 @@
+// @related [subject](medic-src/src/config/local_override.rs)
+
+use super::local_override::*;
+use super::manifest::Manifest;
+use crate::AppResult;
+use crate::DoctorStep;
+use crate::Step;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+fn load(path: &str) -> Manifest {
+  match Manifest::new(Path::new(path)) {
+    AppResult::Ok(manifest) => manifest,
+    AppResult::Err(err) => panic!("Unable to load manifest: {}", err.unwrap()),
+    AppResult::Quit => panic!("Unexpected quit"),
+  }
+}
+
+#[test]
+fn path_finds_local_manifest() {
+  assert_eq!(
+    path(Path::new("../fixtures/local-override/medic.toml")),
+    Some(PathBuf::from("../fixtures/local-override/medic.local.toml"))
+  );
+}
+
+#[test]
+fn path_is_none_without_local_manifest() {
+  assert_eq!(path(Path::new("../fixtures/medic.toml")), None);
+}
+
+#[test]
+fn manifest_records_local_override() {
+  let manifest = load("../fixtures/local-override/medic.toml");
+  assert_eq!(
+    manifest.local_override,
+    Some(PathBuf::from("../fixtures/local-override/medic.local.toml"))
+  );
+
+  let manifest = load("../fixtures/medic.toml");
+  assert_eq!(manifest.local_override, None);
+}
+
+#[test]
+fn apply_disables_overrides_and_appends_steps() {
+  let manifest = load("../fixtures/local-override/medic.toml");
+  let checks = manifest.doctor.unwrap().checks;
+
+  assert_eq!(checks.len(), 3);
+  assert!(matches!(&checks[0], DoctorStep::Check(check) if check.check == "homebrew"));
+
+  let DoctorStep::Shell(database) = &checks[1] else {
+    panic!("Expected shell step, got {:?}", checks[1]);
+  };
+  assert_eq!(database.name, "Database");
+  assert!(database.verbose);
+  assert_eq!(
+    database.env,
+    BTreeMap::from([
+      ("PGHOST".to_string(), "localhost".to_string()),
+      ("PGPORT".to_string(), "5433".to_string()),
+    ])
+  );
+
+  assert!(matches!(&checks[2], DoctorStep::Shell(shell) if shell.name == "Personal check"));
+}
+
+#[test]
+fn apply_reaches_nested_steps() {
+  let manifest = load("../fixtures/local-override/medic.toml");
+  let checks = manifest.test.unwrap().checks;
+
+  assert_eq!(checks.len(), 2);
+  assert!(
+    matches!(&checks[0], Step::Shell(shell) if shell.cd == Some("../fixtures/local-override/crates/core".to_string()))
+  );
+
+  let Step::Steps(nested) = &checks[1] else {
+    panic!("Expected nested steps, got {:?}", checks[1]);
+  };
+  assert_eq!(nested.len(), 1);
+  assert!(matches!(&nested[0], Step::Shell(shell) if shell.name == "Lint"));
+}
+
+#[test]
+fn apply_overrides_workflow_steps() {
+  let manifest = load("../fixtures/workflows/medic.toml");
+  let steps = &manifest.workflows["seed"].steps;
+
+  let Step::Shell(shell) = &steps[0] else {
+    panic!("Expected a shell action, got {:?}", steps[0]);
+  };
+  assert_eq!(shell.env, BTreeMap::from([("SEED".to_string(), "small".to_string())]));
+  assert_eq!(manifest.workflows["release-notes"].steps.len(), 1);
+}
+
+#[test]
+fn apply_rejects_unknown_steps() {
+  let result = Manifest::new(Path::new("../fixtures/local-override-unknown/medic.toml"));
+  let AppResult::Err(Some(err)) = result else {
+    panic!("Expected an error");
+  };
+
+  assert_eq!(
+    err.to_string(),
+    "Local override ../fixtures/local-override-unknown/medic.local.toml refers to unknown step `homebreq` in [doctor]"
+  );
+}
+
+#[test]
+fn step_names_include_name_plugin_and_command() {
+  let step: toml::Table = toml::from_str(r#"check = "tool-versions""#).unwrap();
+  assert_eq!(step_names(&step), vec!["tool-versions"]);
+
+  let step: toml::Table = toml::from_str(
+    r#"
+    name = "Rust plugin"
+    check = "tool-versions"
+    command = "plugin-installed"
+    "#,
+  )
+  .unwrap();
+  assert_eq!(
+    step_names(&step),
+    vec!["Rust plugin", "tool-versions", "tool-versions: plugin-installed"]
+  );
+}
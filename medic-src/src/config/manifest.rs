@@ -1,46 +1,93 @@
 // @related [tests](medic-src/src/config/manifest_test.rs)
 
+use super::{definitions, discovery, loader, local_override, vars};
 use crate::AppResult;
 use crate::AuditStep;
 use crate::DoctorStep;
 use crate::OutdatedCheck;
 use crate::ShipitStep;
 use crate::Step;
-use crate::extra;
+use crate::error::MedicError;
+use crate::optional_styled::OptionalStyled;
+use crate::step::graph;
+use crate::theme::current_theme;
+use crate::validate;
 
+use schemars::JsonSchema;
 use serde::Deserialize;
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use toml::Table;
 
-#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Deserialize, Eq, JsonSchema, PartialEq)]
 pub struct Manifest {
+  /// Lints, type checks, dependency audits, etc, run by `medic audit`.
   pub audit: Option<AuditConfig>,
+  /// Checks ensuring the project is set up for development, run by `medic doctor`.
   pub doctor: Option<DoctorConfig>,
+  #[serde(skip)]
+  pub local_override: Option<PathBuf>,
+  /// Checks for outdated dependencies, run by `medic outdated`.
   pub outdated: Option<OutdatedConfig>,
+  /// Steps to ship changes, run by `medic shipit`.
   pub shipit: Option<ShipitConfig>,
+  /// Tests run by `medic test`.
   pub test: Option<TestConfig>,
+  /// Steps to update the project with upstream changes, run by `medic update`.
   pub update: Option<UpdateConfig>,
+  /// Values referenced in steps as `${vars.name}`.
+  #[serde(default)]
+  pub vars: BTreeMap<String, String>,
+  /// Named workflows, run by `medic workflow <name>`.
+  #[serde(default)]
+  pub workflows: BTreeMap<String, WorkflowConfig>,
 }
 
 impl Manifest {
   pub fn new(path: &Path) -> AppResult<Manifest> {
-    let path_expansion = extra::env::subst(path.to_str().unwrap())?;
-    let expanded_path = std::path::Path::new(&path_expansion);
+    let (manifest, _) = Self::load(path)?;
+    AppResult::Ok(manifest)
+  }
+
+  /// Reads the manifest at `path`, along with the table it was read from
+  /// once includes, definitions, the local override and vars were applied.
+  pub fn load(path: &Path) -> AppResult<(Manifest, Table)> {
+    let path_expansion = discovery::locate(path)?;
+    let expanded_path = path_expansion.as_path();
 
     unsafe {
       std::env::set_var("MEDIC_CONFIG", path);
     }
 
     if expanded_path.exists() {
-      match std::fs::read_to_string(expanded_path) {
-        Ok(manifest_contents) => match toml::from_str(&manifest_contents) {
-          Ok(manifest) => AppResult::Ok(manifest),
-          Err(err) => AppResult::Err(Some(
-            format!("Unable to parse manifest {expanded_path:?}\r\n{err}")
-              .replace('"', "")
-              .into(),
-          )),
-        },
-        Err(err) => AppResult::Err(Some(err.into())),
+      let (table, local_override) = resolve(expanded_path)?;
+
+      match toml::Value::Table(table.clone()).try_into::<Manifest>() {
+        Ok(mut manifest) => {
+          manifest.schedule()?;
+          let manifest = Manifest {
+            local_override,
+            ..manifest
+          };
+          AppResult::Ok((manifest, table))
+        }
+        Err(err) => {
+          let diagnostics = validate::validate(expanded_path);
+          let (source, details) = if diagnostics.is_empty() {
+            failing_source(expanded_path, &table)
+              .unwrap_or_else(|| (expanded_path.to_path_buf(), err.to_string().replace('"', "")))
+          } else {
+            let details = diagnostics
+              .iter()
+              .map(|diagnostic| diagnostic.to_string())
+              .collect::<Vec<_>>()
+              .join("\r\n");
+            (expanded_path.to_path_buf(), details)
+          };
+          AppResult::Err(Some(
+            format!("Unable to parse manifest {}\r\n{details}", source.display()).into(),
+          ))
+        }
       }
     } else {
       AppResult::Err(Some(
@@ -48,34 +95,128 @@ impl Manifest {
       ))
     }
   }
+
+  // Orders each list of steps so that steps come after those they depend on,
+  // rejecting unknown ids and cycles before anything runs.
+  fn schedule(&mut self) -> Result<(), MedicError> {
+    if let Some(audit) = &mut self.audit {
+      graph::sort(&mut audit.checks, "audit")?;
+    }
+    if let Some(doctor) = &mut self.doctor {
+      graph::sort(&mut doctor.checks, "doctor")?;
+    }
+    if let Some(shipit) = &mut self.shipit {
+      graph::sort(&mut shipit.steps, "shipit")?;
+    }
+    if let Some(test) = &mut self.test {
+      schedule_steps(&mut test.checks, "test")?;
+    }
+    if let Some(update) = &mut self.update {
+      schedule_steps(&mut update.steps, "update")?;
+    }
+    for (name, workflow) in &mut self.workflows {
+      schedule_steps(&mut workflow.steps, &format!("workflows.{name}"))?;
+    }
+    Ok(())
+  }
+
+  /// Tells the user when a local override manifest is layered on top of the
+  /// shared manifest.
+  pub fn notify_local_override(&self) {
+    if let Some(local_path) = &self.local_override {
+      eprintln!(
+        "{} {}",
+        OptionalStyled::new("Using local overrides from", current_theme().warning_style.clone()),
+        OptionalStyled::new(local_path.to_string_lossy(), current_theme().highlight_style.clone()),
+      );
+    }
+  }
 }
 
-#[derive(Debug, Deserialize, Eq, PartialEq)]
+// Nested lists of steps are scheduled on their own, as ids are only visible
+// within the list that declares them.
+fn schedule_steps(steps: &mut Vec<Step>, section_name: &str) -> Result<(), MedicError> {
+  graph::sort(steps, section_name)?;
+  for step in steps {
+    match step {
+      Step::Group(group) => schedule_steps(&mut group.steps, section_name)?,
+      Step::Steps(nested) => schedule_steps(nested, section_name)?,
+      _ => {}
+    }
+  }
+  Ok(())
+}
+
+/// Loads the manifest at `path` as a table, with includes, definitions, its
+/// local override and vars applied, and the `cd` of steps resolved from its
+/// project directory. Returns the path of the local override when there is
+/// one.
+pub(crate) fn resolve(path: &Path) -> Result<(Table, Option<PathBuf>), MedicError> {
+  let mut table = loader::load(path)?;
+  definitions::apply(&mut table)?;
+  let local_override = local_override::path(path);
+  if let Some(local_path) = &local_override {
+    local_override::apply(&mut table, local_path)?;
+    // Steps added by the local override may also use definitions.
+    definitions::apply(&mut table)?;
+  }
+  vars::apply(&mut table)?;
+  discovery::resolve_dirs(&mut table, &discovery::project_dir(path));
+  Ok((table, local_override))
+}
+
+// Steps which `use` a definition can only be read once every file is merged,
+// so when the merged manifest cannot be read, each file is read again on its
+// own, with the definitions and vars of the whole manifest, to find the one
+// at fault. Returns its path and why it cannot be read.
+fn failing_source(path: &Path, resolved: &Table) -> Option<(PathBuf, String)> {
+  loader::sources(path)
+    .ok()?
+    .into_iter()
+    .find_map(|(source, mut table)| {
+      for key in ["definitions", "vars"] {
+        if let Some(value) = resolved.get(key) {
+          table.insert(key.to_string(), value.clone());
+        }
+      }
+      definitions::apply(&mut table).ok()?;
+      vars::apply(&mut table).ok()?;
+      let err = toml::Value::Table(table).try_into::<Manifest>().err()?;
+      Some((source, err.to_string().replace('"', "")))
+    })
+}
+
+#[derive(Debug, Deserialize, Eq, JsonSchema, PartialEq)]
 pub struct AuditConfig {
   pub checks: Vec<AuditStep>,
 }
 
-#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Deserialize, Eq, JsonSchema, PartialEq)]
 pub struct DoctorConfig {
   pub checks: Vec<DoctorStep>,
 }
 
-#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Deserialize, Eq, JsonSchema, PartialEq)]
 pub struct OutdatedConfig {
   pub checks: Vec<OutdatedCheck>,
 }
 
-#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Deserialize, Eq, JsonSchema, PartialEq)]
 pub struct ShipitConfig {
   pub steps: Vec<ShipitStep>,
 }
 
-#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Deserialize, Eq, JsonSchema, PartialEq)]
 pub struct TestConfig {
   pub checks: Vec<Step>,
 }
 
-#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Deserialize, Eq, JsonSchema, PartialEq)]
 pub struct UpdateConfig {
   pub steps: Vec<Step>,
 }
+
+#[derive(Debug, Deserialize, Eq, JsonSchema, PartialEq)]
+pub struct WorkflowConfig {
+  pub steps: Vec<Step>,
+}
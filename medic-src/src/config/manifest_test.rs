@@ -36,16 +36,40 @@ fn deserialize_audit() {
           AuditStep::Shell(ShellConfig {
             allow_failure: false,
             cd: None,
+            depends_on: vec![],
+            id: None,
+            r#if: None,
+            if_env: None,
+            inputs: vec![],
             platform: None,
             env: BTreeMap::default(),
             inline: false,
             manual: false,
             name: "Shell check".to_string(),
             remedy: None,
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
             shell: "do something".to_string(),
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
             verbose: false,
           }),
           AuditStep::Check(Check {
+            depends_on: vec![],
+            id: None,
+            r#if: None,
+            if_env: None,
+            inputs: vec![],
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
             verbose: false,
             args: None,
             cd: None,
@@ -60,19 +84,34 @@ fn deserialize_audit() {
             args: None,
             cd: None,
             command: Some("clippy".to_string()),
+            depends_on: vec![],
             env: BTreeMap::default(),
+            id: None,
+            r#if: None,
+            if_env: None,
+            inputs: vec![],
             name: None,
             platform: None,
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
             step: "rust".to_string(),
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
             verbose: false,
           }),
         ]
       }),
       doctor: None,
+      local_override: None,
       outdated: None,
       shipit: None,
       test: None,
       update: None,
+      vars: BTreeMap::default(),
+      workflows: BTreeMap::default(),
     }
   );
 }
@@ -98,13 +137,25 @@ fn deserialize_doctor() {
           DoctorStep::Shell(ShellConfig {
             allow_failure: false,
             cd: None,
+            depends_on: vec![],
             env: BTreeMap::default(),
+            id: None,
+            r#if: None,
+            if_env: None,
             inline: false,
+            inputs: vec![],
             manual: false,
             name: "Shell check".to_string(),
             platform: None,
             remedy: None,
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
             shell: "do something".to_string(),
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
             verbose: false,
           }),
           DoctorStep::Check(Check {
@@ -112,28 +163,55 @@ fn deserialize_doctor() {
             cd: None,
             check: "rust".to_string(),
             command: Some("format-check".to_string()),
+            depends_on: vec![],
             env: BTreeMap::default(),
+            id: None,
+            r#if: None,
+            if_env: None,
+            inputs: vec![],
             manual: false,
             output: OutputFormat::Json,
             platform: None,
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
             verbose: false,
           }),
           DoctorStep::Step(StepConfig {
             args: None,
             cd: None,
             command: Some("clippy".to_string()),
+            depends_on: vec![],
             env: BTreeMap::default(),
+            id: None,
+            r#if: None,
+            if_env: None,
+            inputs: vec![],
             name: None,
             platform: None,
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
             step: "rust".to_string(),
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
             verbose: false,
           }),
         ]
       }),
+      local_override: None,
       outdated: None,
       shipit: None,
       test: None,
       update: None,
+      vars: BTreeMap::default(),
+      workflows: BTreeMap::default(),
     }
   );
 }
@@ -154,29 +232,50 @@ fn deserialize_outdated() {
     Manifest {
       audit: None,
       doctor: None,
+      local_override: None,
       outdated: Some(OutdatedConfig {
         checks: vec![
           OutdatedCheck {
             args: None,
             cd: None,
             check: "rust".to_string(),
+            r#if: None,
+            if_env: None,
             name: None,
             platform: None,
             remedy: None,
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
           },
           OutdatedCheck {
             args: None,
             cd: Some("assets".to_string()),
             check: "node".to_string(),
+            r#if: None,
+            if_env: None,
             name: None,
             platform: None,
             remedy: Some("npm update".to_string()),
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
           },
         ]
       }),
       shipit: None,
       test: None,
       update: None,
+      vars: BTreeMap::default(),
+      workflows: BTreeMap::default(),
     }
   );
 }
@@ -201,6 +300,7 @@ fn deserialize_shipit() {
     Manifest {
       audit: None,
       doctor: None,
+      local_override: None,
       outdated: None,
       shipit: Some(ShipitConfig {
         steps: vec![
@@ -212,38 +312,76 @@ fn deserialize_shipit() {
             cd: None,
             check: "rust".to_string(),
             command: None,
+            depends_on: vec![],
             env: BTreeMap::default(),
+            id: None,
+            r#if: None,
+            if_env: None,
+            inputs: vec![],
             manual: false,
             output: OutputFormat::Json,
             platform: None,
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
             verbose: false
           }),
           ShipitStep::Shell(ShellConfig {
             allow_failure: false,
             cd: None,
+            depends_on: vec![],
             env: BTreeMap::default(),
+            id: None,
+            r#if: None,
+            if_env: None,
             inline: false,
+            inputs: vec![],
             manual: false,
             name: "Do stuff".to_string(),
             platform: None,
             remedy: None,
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
             shell: "do something".to_string(),
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
             verbose: false,
           }),
           ShipitStep::Step(StepConfig {
             args: None,
             cd: None,
             command: None,
+            depends_on: vec![],
             env: BTreeMap::default(),
+            id: None,
+            r#if: None,
+            if_env: None,
+            inputs: vec![],
             name: None,
             platform: None,
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
             step: "rust".to_string(),
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
             verbose: false
           })
         ]
       }),
       test: None,
       update: None,
+      vars: BTreeMap::default(),
+      workflows: BTreeMap::default(),
     }
   );
 }
@@ -266,6 +404,7 @@ fn deserialize_test() {
     Manifest {
       audit: None,
       doctor: None,
+      local_override: None,
       outdated: None,
       shipit: None,
       test: Some(TestConfig {
@@ -276,37 +415,75 @@ fn deserialize_test() {
             cd: None,
             check: "rust".to_string(),
             command: None,
+            depends_on: vec![],
             env: BTreeMap::default(),
+            id: None,
+            r#if: None,
+            if_env: None,
+            inputs: vec![],
             manual: false,
             output: OutputFormat::Json,
             platform: None,
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
             verbose: false
           }),
           Step::Shell(ShellConfig {
             allow_failure: false,
             cd: None,
+            depends_on: vec![],
             env: BTreeMap::default(),
+            id: None,
+            r#if: None,
+            if_env: None,
             inline: false,
+            inputs: vec![],
             manual: false,
             name: "Do stuff".to_string(),
             platform: None,
             remedy: None,
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
             shell: "do something".to_string(),
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
             verbose: false
           }),
           Step::Step(StepConfig {
             args: None,
             cd: None,
             command: None,
+            depends_on: vec![],
             env: BTreeMap::default(),
+            id: None,
+            r#if: None,
+            if_env: None,
+            inputs: vec![],
             name: None,
             platform: None,
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
             step: "rust".to_string(),
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
             verbose: false
           })
         ]
       }),
       update: None,
+      vars: BTreeMap::default(),
+      workflows: BTreeMap::default(),
     }
   );
 }
@@ -329,6 +506,7 @@ fn deserialize_update() {
     Manifest {
       audit: None,
       doctor: None,
+      local_override: None,
       outdated: None,
       shipit: None,
       test: None,
@@ -340,36 +518,74 @@ fn deserialize_update() {
             cd: None,
             check: "rust".to_string(),
             command: None,
+            depends_on: vec![],
             env: BTreeMap::default(),
+            id: None,
+            r#if: None,
+            if_env: None,
+            inputs: vec![],
             manual: false,
             output: OutputFormat::Json,
             platform: None,
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
             verbose: false
           }),
           Step::Shell(ShellConfig {
             allow_failure: false,
             cd: None,
+            depends_on: vec![],
             env: BTreeMap::default(),
+            id: None,
+            r#if: None,
+            if_env: None,
             inline: false,
+            inputs: vec![],
             manual: false,
             name: "Do stuff".to_string(),
             platform: None,
             remedy: None,
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
             shell: "do something".to_string(),
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
             verbose: false
           }),
           Step::Step(StepConfig {
             args: None,
             cd: None,
             command: None,
+            depends_on: vec![],
             env: BTreeMap::default(),
+            id: None,
+            r#if: None,
+            if_env: None,
+            inputs: vec![],
             name: None,
             platform: None,
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
             step: "rust".to_string(),
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
             verbose: false
           })
         ]
       }),
+      vars: BTreeMap::default(),
+      workflows: BTreeMap::default(),
     }
   );
 }
@@ -394,12 +610,18 @@ fn deserialize_nested_steps() {
     Manifest {
       audit: None,
       doctor: None,
+      local_override: None,
       outdated: None,
       shipit: None,
       test: Some(TestConfig {
         checks: vec![
           Step::Shell(ShellConfig {
             allow_failure: false,
+            depends_on: vec![],
+            id: None,
+            r#if: None,
+            if_env: None,
+            inputs: vec![],
             platform: None,
             cd: None,
             env: BTreeMap::default(),
@@ -407,12 +629,24 @@ fn deserialize_nested_steps() {
             manual: false,
             name: "First step".to_string(),
             remedy: None,
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
             shell: "echo 'Step 1'".to_string(),
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
             verbose: false
           }),
           Step::Steps(vec![
             Step::Shell(ShellConfig {
               allow_failure: false,
+              depends_on: vec![],
+              id: None,
+              r#if: None,
+              if_env: None,
+              inputs: vec![],
               platform: None,
               cd: None,
               env: BTreeMap::default(),
@@ -420,11 +654,23 @@ fn deserialize_nested_steps() {
               manual: false,
               name: "Nested step 1".to_string(),
               remedy: None,
+              retries: 0,
+              retry_backoff: false,
+              retry_delay: None,
               shell: "echo 'Nested 1'".to_string(),
+              tags: vec![],
+              timeout: None,
+              unless: None,
+              unless_env: None,
               verbose: false
             }),
             Step::Shell(ShellConfig {
               allow_failure: false,
+              depends_on: vec![],
+              id: None,
+              r#if: None,
+              if_env: None,
+              inputs: vec![],
               platform: None,
               cd: None,
               env: BTreeMap::default(),
@@ -432,12 +678,24 @@ fn deserialize_nested_steps() {
               manual: false,
               name: "Nested step 2".to_string(),
               remedy: None,
+              retries: 0,
+              retry_backoff: false,
+              retry_delay: None,
               shell: "echo 'Nested 2'".to_string(),
+              tags: vec![],
+              timeout: None,
+              unless: None,
+              unless_env: None,
               verbose: false
             }),
           ]),
           Step::Shell(ShellConfig {
             allow_failure: false,
+            depends_on: vec![],
+            id: None,
+            r#if: None,
+            if_env: None,
+            inputs: vec![],
             platform: None,
             cd: None,
             env: BTreeMap::default(),
@@ -445,12 +703,141 @@ fn deserialize_nested_steps() {
             manual: false,
             name: "Last step".to_string(),
             remedy: None,
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
             shell: "echo 'Step 3'".to_string(),
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
             verbose: false
           }),
         ]
       }),
       update: None,
+      vars: BTreeMap::default(),
+      workflows: BTreeMap::default(),
     }
   );
 }
+
+#[test]
+fn deserialize_single_nested_step() {
+  let toml = indoc! {r#"
+    [test]
+    checks = [
+      [
+        { name = "Nested step", shell = "echo 'Nested'" },
+      ],
+    ]
+    "#};
+
+  let manifest: Manifest = toml::from_str(toml).expect("Unable to parse Manifest from toml");
+  assert_eq!(
+    manifest.test.unwrap().checks,
+    vec![Step::Steps(vec![Step::Shell(ShellConfig {
+      allow_failure: false,
+      depends_on: vec![],
+      id: None,
+      r#if: None,
+      if_env: None,
+      inputs: vec![],
+      platform: None,
+      cd: None,
+      env: BTreeMap::default(),
+      inline: false,
+      manual: false,
+      name: "Nested step".to_string(),
+      remedy: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
+      shell: "echo 'Nested'".to_string(),
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
+      verbose: false
+    })])]
+  );
+}
+
+#[test]
+fn deserialize_workflows() {
+  let toml = indoc! {r#"
+    [workflows.seed]
+    steps = [
+      { step = "db", command = "seed" },
+      { doctor = {} },
+    ]
+    "#};
+
+  let manifest: Manifest = toml::from_str(toml).expect("Unable to parse Manifest from toml");
+  assert_eq!(
+    manifest.workflows,
+    BTreeMap::from([(
+      "seed".to_string(),
+      WorkflowConfig {
+        steps: vec![
+          Step::Step(StepConfig {
+            args: None,
+            cd: None,
+            command: Some("seed".to_string()),
+            depends_on: vec![],
+            env: BTreeMap::default(),
+            id: None,
+            r#if: None,
+            if_env: None,
+            inputs: vec![],
+            name: None,
+            platform: None,
+            retries: 0,
+            retry_backoff: false,
+            retry_delay: None,
+            step: "db".to_string(),
+            tags: vec![],
+            timeout: None,
+            unless: None,
+            unless_env: None,
+            verbose: false
+          }),
+          Step::Doctor(step::DoctorConfig { doctor: NoopConfig {} }),
+        ]
+      }
+    )])
+  );
+}
+
+#[test]
+fn new_sorts_steps_by_dependencies() {
+  let manifest = match Manifest::new(std::path::Path::new("../fixtures/depends-on/medic.toml")) {
+    crate::AppResult::Ok(manifest) => manifest,
+    crate::AppResult::Err(err) => panic!("Unable to load manifest: {}", err.unwrap()),
+    crate::AppResult::Quit => panic!("Unexpected quit"),
+  };
+  let ids = |steps: &[Step]| -> Vec<Option<String>> {
+    steps
+      .iter()
+      .map(|step| crate::runnable::Runnable::id(step).map(String::from))
+      .collect()
+  };
+
+  let checks = manifest.test.unwrap().checks;
+  assert_eq!(ids(&checks[..2]), vec![Some("build".into()), Some("unit".into())]);
+  let Step::Steps(nested) = &checks[2] else {
+    panic!("Expected nested steps, got {:?}", checks[2]);
+  };
+  assert_eq!(ids(nested), vec![Some("server".into()), Some("integration".into())]);
+}
+
+#[test]
+fn new_rejects_dependency_cycles() {
+  match Manifest::new(std::path::Path::new("../fixtures/depends-on-cycle/medic.toml")) {
+    crate::AppResult::Err(Some(err)) => assert_eq!(
+      err.to_string(),
+      "Dependency cycle detected in [test]: build -> test -> build"
+    ),
+    _ => panic!("Expected a dependency cycle error"),
+  }
+}
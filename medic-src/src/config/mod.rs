@@ -1,5 +1,21 @@
 #[cfg(test)]
+mod definitions_test;
+#[cfg(test)]
+mod discovery_test;
+#[cfg(test)]
+mod loader_test;
+#[cfg(test)]
+mod local_override_test;
+#[cfg(test)]
 mod manifest_test;
+#[cfg(test)]
+mod vars_test;
+
+pub(crate) mod definitions;
+pub mod discovery;
+pub(crate) mod loader;
+pub(crate) mod local_override;
+pub(crate) mod vars;
 
 pub mod manifest;
 pub use manifest::Manifest;
@@ -0,0 +1,111 @@
+// @related [tests](medic-src/src/config/vars_test.rs)
+
+use super::{loader, local_override};
+use crate::error::MedicError;
+
+use toml::{Table, Value};
+
+/// Keys of a step in which `${vars.name}` references are replaced.
+pub(crate) const INTERPOLATED_KEYS: [&str; 5] = ["args", "cd", "env", "remedy", "shell"];
+
+const PREFIX: &str = "${vars.";
+
+/// Replaces `${vars.name}` references in the steps of `manifest` with values
+/// from its `[vars]` table. References to undefined variables are errors.
+pub fn apply(manifest: &mut Table) -> Result<(), MedicError> {
+  let vars = match manifest.get("vars") {
+    None => Table::new(),
+    Some(Value::Table(vars)) => vars.clone(),
+    Some(_) => return Err("`vars` must be a table of strings".into()),
+  };
+  if let Some((name, _)) = vars.iter().find(|(_, value)| !value.is_str()) {
+    return Err(format!("Variable `{name}` in [vars] must be a string").into());
+  }
+
+  for (section_name, steps) in loader::step_lists(manifest) {
+    interpolate_steps(steps, &vars, &section_name)?;
+  }
+
+  Ok(())
+}
+
+/// Returns the byte offset and name of each `${vars.name}` reference in
+/// `string`.
+pub(crate) fn references(string: &str) -> Vec<(usize, &str)> {
+  let mut references = vec![];
+  let mut start = 0;
+
+  while let Some(found) = string[start..].find(PREFIX) {
+    let offset = start + found;
+    let name_start = offset + PREFIX.len();
+    let Some(len) = string[name_start..].find('}') else {
+      break;
+    };
+    references.push((offset, &string[name_start..name_start + len]));
+    start = name_start + len + 1;
+  }
+
+  references
+}
+
+fn interpolate_steps(steps: &mut [Value], vars: &Table, section_name: &str) -> Result<(), MedicError> {
+  for step in steps.iter_mut() {
+    if let Some(nested) = loader::nested_steps_mut(step) {
+      interpolate_steps(nested, vars, section_name)?;
+    } else if let Value::Table(step) = step {
+      let names = local_override::step_names(step);
+      for key in INTERPOLATED_KEYS {
+        if let Some(value) = step.get_mut(key) {
+          interpolate_value(value, vars).map_err(|name| {
+            let step_name = names
+              .first()
+              .map_or(String::new(), |name| format!(" step `{name}`"));
+            MedicError::from(format!(
+              "Undefined variable `{name}` referenced by `{key}` of [{section_name}]{step_name}"
+            ))
+          })?;
+        }
+      }
+    }
+  }
+  Ok(())
+}
+
+// Strings are interpolated wherever they appear, so that `args` lists and
+// `env` tables are handled the same way as `shell` and `cd`. Returns the name
+// of the first undefined variable.
+fn interpolate_value(value: &mut Value, vars: &Table) -> Result<(), String> {
+  match value {
+    Value::String(string) => *string = interpolate(string, vars)?,
+    Value::Array(values) => {
+      for value in values {
+        interpolate_value(value, vars)?;
+      }
+    }
+    Value::Table(table) => {
+      for (_, value) in table.iter_mut() {
+        interpolate_value(value, vars)?;
+      }
+    }
+    _ => {}
+  }
+  Ok(())
+}
+
+fn interpolate(string: &str, vars: &Table) -> Result<String, String> {
+  let mut interpolated = String::new();
+  let mut rest = 0;
+
+  for (offset, name) in references(string) {
+    let value = vars
+      .get(name)
+      .and_then(Value::as_str)
+      .ok_or_else(|| name.to_string())?;
+    interpolated.push_str(&string[rest..offset]);
+    interpolated.push_str(value);
+    rest = offset + PREFIX.len() + name.len() + 1;
+  }
+  interpolated.push_str(&string[rest..]);
+
+  Ok(interpolated)
+}
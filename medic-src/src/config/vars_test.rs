@@ -0,0 +1,121 @@
+// @related [subject](medic-src/src/config/vars.rs)
+
+use super::manifest::Manifest;
+use super::vars::*;
+use crate::AppResult;
+use crate::DoctorStep;
+use crate::Step;
+use crate::util::StringOrList;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+fn load(path: &str) -> AppResult<Manifest> {
+  Manifest::new(Path::new(path))
+}
+
+fn loaded(path: &str) -> Manifest {
+  match load(path) {
+    AppResult::Ok(manifest) => manifest,
+    AppResult::Err(err) => panic!("Unable to load manifest: {}", err.unwrap()),
+    AppResult::Quit => panic!("Unexpected quit"),
+  }
+}
+
+#[test]
+fn references_finds_names_and_offsets() {
+  assert_eq!(
+    references("${vars.bin}/setup --${vars.plugin} ${HOME} ${vars.unterminated"),
+    vec![(0, "bin"), (20, "plugin")]
+  );
+  assert_eq!(references("no variables"), vec![]);
+}
+
+#[test]
+fn apply_interpolates_steps() {
+  let manifest = loaded("../fixtures/vars/medic.toml");
+  let checks = manifest.doctor.unwrap().checks;
+
+  let DoctorStep::Check(check) = &checks[0] else {
+    panic!("Expected a check, got {:?}", checks[0]);
+  };
+  assert_eq!(
+    check.args,
+    Some(BTreeMap::from([(
+      "plugin".to_string(),
+      StringOrList(vec!["rust".to_string()])
+    )]))
+  );
+
+  let DoctorStep::Check(check) = &checks[1] else {
+    panic!("Expected a check, got {:?}", checks[1]);
+  };
+  assert_eq!(
+    check.args,
+    Some(BTreeMap::from([(
+      "target".to_string(),
+      StringOrList(vec![
+        "aarch64-apple-darwin".to_string(),
+        "x86_64-apple-darwin".to_string()
+      ])
+    )]))
+  );
+
+  let DoctorStep::Shell(shell) = &checks[2] else {
+    panic!("Expected a shell action, got {:?}", checks[2]);
+  };
+  assert_eq!(shell.shell, "bin/dev/setup --rust");
+  assert_eq!(shell.remedy, Some("bin/dev/setup".to_string()));
+  assert_eq!(
+    shell.env,
+    BTreeMap::from([
+      ("HOME_DIR".to_string(), "${HOME}".to_string()),
+      ("PLUGIN".to_string(), "rust".to_string()),
+    ])
+  );
+
+  let update = manifest.update.unwrap().steps;
+  let Step::Steps(nested) = &update[0] else {
+    panic!("Expected nested steps, got {:?}", update[0]);
+  };
+  let Step::Step(step) = &nested[0] else {
+    panic!("Expected a step, got {:?}", nested[0]);
+  };
+  assert_eq!(step.cd, Some("../fixtures/vars/bin/dev".to_string()));
+
+  assert_eq!(manifest.vars["plugin"], "rust");
+}
+
+#[test]
+fn apply_reports_undefined_variables() {
+  match load("../fixtures/vars-undefined/medic.toml") {
+    AppResult::Err(Some(err)) => assert_eq!(
+      err.to_string(),
+      "Undefined variable `plugin` referenced by `shell` of [doctor] step `Setup`"
+    ),
+    AppResult::Err(None) => panic!("Expected an error message"),
+    AppResult::Ok(_) => panic!("Expected an error, got a manifest"),
+    AppResult::Quit => panic!("Unexpected quit"),
+  }
+}
+
+#[test]
+fn apply_requires_string_values() {
+  let mut manifest: toml::Table = toml::from_str("vars = { retries = 3 }").unwrap();
+
+  assert_eq!(
+    apply(&mut manifest).unwrap_err().to_string(),
+    "Variable `retries` in [vars] must be a string"
+  );
+}
+
+#[test]
+fn apply_prefers_local_override_vars() {
+  let manifest = loaded("../fixtures/vars-local/medic.toml");
+  let checks = manifest.test.unwrap().checks;
+
+  let Step::Shell(shell) = &checks[0] else {
+    panic!("Expected a shell action, got {:?}", checks[0]);
+  };
+  assert_eq!(shell.name, "${vars.name}");
+  assert_eq!(shell.cd, Some("../fixtures/vars-local/mine".to_string()));
+}
@@ -24,6 +24,18 @@ impl Context {
       Some(platform_list) => platform_list.iter().any(|p| p == &self.platform),
     }
   }
+
+  /// Returns why a step limited to `platforms` does not run on this platform.
+  pub fn platform_skip_reason(&self, platforms: &Option<Vec<String>>) -> Option<String> {
+    match platforms {
+      Some(platform_list) if !self.matches_platform(platforms) => Some(format!(
+        "only runs on {}, not {}",
+        platform_list.join(", "),
+        self.platform
+      )),
+      _ => None,
+    }
+  }
 }
 
 fn current_platform() -> String {
@@ -84,4 +96,17 @@ mod tests {
     };
     assert!(!context.matches_platform(&Some(vec!["SomethingElse".into(), "Other".into()])));
   }
+
+  #[test]
+  fn test_platform_skip_reason() {
+    let context = Context {
+      platform: "Something".into(),
+    };
+    assert_eq!(context.platform_skip_reason(&None), None);
+    assert_eq!(context.platform_skip_reason(&Some(vec!["Something".into()])), None);
+    assert_eq!(
+      context.platform_skip_reason(&Some(vec!["Darwin".into(), "Other".into()])),
+      Some("only runs on Darwin, Other, not Something".into())
+    );
+  }
 }
@@ -1,4 +1,5 @@
 use crate::cli::Flags;
+use crate::condition::Condition;
 use crate::context::Context;
 use crate::error::MedicError;
 use crate::recoverable::Recoverable;
@@ -8,11 +9,12 @@ pub use crate::step::step_config::StepConfig;
 
 use crate::Check;
 use crate::runnable::Runnable;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use std::fmt;
 use std::process::Command;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq)]
 #[serde(untagged)]
 pub enum DoctorStep {
   Check(Check),
@@ -29,6 +31,46 @@ impl Runnable for DoctorStep {
     }
   }
 
+  fn condition(&self) -> Condition<'_> {
+    match self {
+      DoctorStep::Check(config) => config.condition(),
+      DoctorStep::Shell(config) => config.condition(),
+      DoctorStep::Step(config) => config.condition(),
+    }
+  }
+
+  fn depends_on(&self) -> &[String] {
+    match self {
+      DoctorStep::Check(config) => config.depends_on(),
+      DoctorStep::Shell(config) => config.depends_on(),
+      DoctorStep::Step(config) => config.depends_on(),
+    }
+  }
+
+  fn id(&self) -> Option<&str> {
+    match self {
+      DoctorStep::Check(config) => config.id(),
+      DoctorStep::Shell(config) => config.id(),
+      DoctorStep::Step(config) => config.id(),
+    }
+  }
+
+  fn inputs(&self) -> &[String] {
+    match self {
+      DoctorStep::Check(config) => config.inputs(),
+      DoctorStep::Shell(config) => config.inputs(),
+      DoctorStep::Step(config) => config.inputs(),
+    }
+  }
+
+  fn name(&self) -> Option<&str> {
+    match self {
+      DoctorStep::Check(config) => config.name(),
+      DoctorStep::Shell(config) => config.name(),
+      DoctorStep::Step(config) => config.name(),
+    }
+  }
+
   fn platform(&self) -> &Option<Vec<String>> {
     match self {
       DoctorStep::Check(config) => config.platform(),
@@ -45,6 +87,14 @@ impl Runnable for DoctorStep {
     }
   }
 
+  fn tags(&self) -> &[String] {
+    match self {
+      DoctorStep::Check(config) => config.tags(),
+      DoctorStep::Shell(config) => config.tags(),
+      DoctorStep::Step(config) => config.tags(),
+    }
+  }
+
   fn to_command(&self) -> Result<Command, MedicError> {
     match self {
       DoctorStep::Check(config) => config.to_command(),
@@ -0,0 +1,100 @@
+// @related [subject](medic-src/src/dry_run/mod.rs)
+
+use super::describe;
+use crate::Check;
+use crate::cli::Flags;
+use crate::context::Context;
+use crate::fixture::Project;
+use crate::progress;
+use crate::recoverable::Recoverable;
+use crate::runnable;
+use crate::shell::ShellConfig;
+use crate::step::Step;
+use std::collections::BTreeMap;
+use std::sync::Once;
+
+static INIT: Once = Once::new();
+pub fn initialize() {
+  INIT.call_once(|| {
+    if crate::theme::THEME.get().is_none() {
+      let theme = crate::theme::dark_theme();
+      crate::theme::set_theme(theme);
+    }
+  });
+}
+
+fn lines(runnable: &impl runnable::Runnable) -> Vec<String> {
+  initialize();
+  describe(runnable)
+    .iter()
+    .map(|line| console::strip_ansi_codes(line).to_string())
+    .collect()
+}
+
+fn shell(shell: &str) -> ShellConfig {
+  ShellConfig::new("Step".into(), shell.into(), None, None, false)
+}
+
+#[test]
+fn test_describe_shell() {
+  let mut config = shell("echo hi");
+  config.cd = Some("/tmp".into());
+  config.env = BTreeMap::from([("MEDIC_DRY_RUN_TEST".into(), "value".into())]);
+
+  assert_eq!(
+    lines(&config),
+    vec!["(cd /tmp && MEDIC_DRY_RUN_TEST=value sh -c 'echo hi')"]
+  );
+}
+
+#[test]
+fn test_describe_conditions() {
+  let mut config = shell("npm ci");
+  config.r#if = Some("test -f package.json".into());
+  config.unless = Some("test -d node_modules".into());
+
+  assert_eq!(
+    lines(&config),
+    vec![
+      "if `test -f package.json` succeeds",
+      "unless `test -d node_modules` succeeds",
+      "sh -c 'npm ci'",
+    ]
+  );
+}
+
+#[test]
+fn test_describe_missing_executable() {
+  let check: Check = toml::from_str(r#"check = "does-not-exist""#).unwrap();
+
+  assert_eq!(
+    lines(&check),
+    vec!["✗ executable medic-check-does-not-exist not found in PATH"]
+  );
+}
+
+#[test]
+fn test_dry_run_does_not_run_steps_or_conditions() {
+  initialize();
+  let dir = Project::new("");
+
+  let mut conditional = shell(&format!("touch {}/step", dir.display()));
+  conditional.unless = Some(format!("touch {}/condition", dir.display()));
+  let step = Step::Steps(vec![
+    Step::Shell(conditional),
+    Step::Steps(vec![Step::Shell(shell(&format!("touch {}/nested", dir.display())))]),
+  ]);
+
+  let mut flags = Flags {
+    dry_run: true,
+    parallel: true,
+    ..Flags::default()
+  };
+  let mut progress = progress::new(&flags);
+  let result = runnable::run_unless_skipped(&step, &mut progress, &mut flags, &Context::new());
+
+  assert!(matches!(result, Recoverable::Ok(())));
+  for file in ["step", "condition", "nested"] {
+    assert!(!dir.join(file).exists(), "{file} was touched");
+  }
+}
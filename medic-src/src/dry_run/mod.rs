@@ -0,0 +1,67 @@
+// @related [tests](medic-src/src/dry_run/dry_run_test.rs)
+
+#[cfg(test)]
+mod dry_run_test;
+
+use crate::extra;
+use crate::optional_styled::OptionalStyled;
+use crate::runnable::Runnable;
+use crate::theme::current_theme;
+
+use retrogress::ProgressBar;
+use which::which;
+
+/// Prints what running `runnable` would do, without running anything.
+pub fn print(runnable: &impl Runnable, progress: &mut ProgressBar) {
+  progress.print_inline(&format!(
+    "{} {runnable}",
+    OptionalStyled::new("•", current_theme().highlight_style.clone())
+  ));
+  for line in describe(runnable) {
+    progress.print_inline(&format!("    {line}"));
+  }
+}
+
+/// The lines shown by `--dry-run` beneath a step: the conditions it would
+/// check, then the command it would run, with its directory and
+/// environment, or why that command could not be run.
+pub fn describe(runnable: &impl Runnable) -> Vec<String> {
+  let mut lines = vec![];
+  let condition = runnable.condition();
+
+  if let Some(shell) = condition.r#if {
+    lines.push(dim(format!("if `{shell}` succeeds")));
+  }
+  if let Some(shell) = condition.unless {
+    lines.push(dim(format!("unless `{shell}` succeeds")));
+  }
+
+  match runnable.to_command() {
+    Ok(command) => {
+      lines
+        .push(OptionalStyled::new(extra::command::describe(&command), current_theme().args_style.clone()).to_string());
+      let program = command.get_program();
+      if which(program).is_err() {
+        lines.push(error(format!(
+          "executable {} not found in PATH",
+          program.to_string_lossy()
+        )));
+      }
+    }
+    Err(err) => lines.push(error(err.to_string())),
+  }
+
+  lines
+}
+
+fn dim(line: String) -> String {
+  OptionalStyled::new(line, current_theme().dim_style.clone()).to_string()
+}
+
+fn error(line: String) -> String {
+  format!(
+    "{} {}",
+    OptionalStyled::new("✗", current_theme().error_style.clone()),
+    OptionalStyled::new(line, current_theme().error_style.clone())
+  )
+}
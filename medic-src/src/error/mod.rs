@@ -8,6 +8,9 @@ pub enum MedicError {
   OsString(std::ffi::OsString),
   Message(String),
   Other(Box<dyn std::error::Error + Send + Sync>),
+  /// A command killed after running longer than its timeout, with the output
+  /// it wrote until then.
+  TimedOut(std::time::Duration, std::process::Output),
 }
 
 impl fmt::Display for MedicError {
@@ -19,6 +22,7 @@ impl fmt::Display for MedicError {
       MedicError::OsString(os) => write!(f, "Invalid OsString: {os:?}"),
       MedicError::Message(msg) => write!(f, "{msg}"),
       MedicError::Other(e) => e.fmt(f),
+      MedicError::TimedOut(timeout, _) => write!(f, "Timed out after {}", humantime::format_duration(*timeout)),
     }
   }
 }
@@ -32,6 +36,7 @@ impl std::error::Error for MedicError {
       MedicError::OsString(_) => None,
       MedicError::Message(_) => None,
       MedicError::Other(e) => Some(e.as_ref()),
+      MedicError::TimedOut(_, _) => None,
     }
   }
 }
@@ -1,5 +1,15 @@
+use crate::error::MedicError;
+use crate::interrupt;
+
 use std::collections::BTreeMap;
-use std::process::Command;
+use std::io::Read;
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, Output};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
 
 pub fn to_string(command: &String, dir: &Option<String>) -> String {
   match dir {
@@ -24,8 +34,11 @@ pub fn new(cmd: &str, dir: &Option<String>, env: &BTreeMap<String, String>) -> C
 
 pub fn with_dir(cmd: &mut Command, dir: &Option<String>) {
   if let Some(dir) = dir {
-    let expanded = std::fs::canonicalize(dir).unwrap();
-    cmd.current_dir(&expanded);
+    // A directory that does not exist yet fails when the command is spawned.
+    match std::fs::canonicalize(dir) {
+      Ok(expanded) => cmd.current_dir(&expanded),
+      Err(_) => cmd.current_dir(dir),
+    };
   };
 }
 
@@ -40,3 +53,147 @@ pub fn with_env(cmd: &mut Command, env: &BTreeMap<String, String>) {
 
   cmd.env_clear().envs(&filtered_env);
 }
+
+/// Renders `command` as a shell command line, including its directory and
+/// any environment variables that differ from those medic was run with.
+pub fn describe(command: &Command) -> String {
+  let mut parts = vec![];
+  for (key, value) in command.get_envs() {
+    if let Some(value) = value
+      && std::env::var_os(key).as_deref() != Some(value)
+    {
+      parts.push(format!("{}={}", key.to_string_lossy(), quote(&value.to_string_lossy())));
+    }
+  }
+  parts.push(quote(&command.get_program().to_string_lossy()));
+  parts.extend(command.get_args().map(|arg| quote(&arg.to_string_lossy())));
+
+  let line = parts.join(" ");
+  match command.get_current_dir() {
+    Some(dir) => format!("(cd {} && {line})", quote(&dir.to_string_lossy())),
+    None => line,
+  }
+}
+
+fn quote(arg: &str) -> String {
+  let safe = |c: char| c.is_ascii_alphanumeric() || "%+,-./:=@_".contains(c);
+  if !arg.is_empty() && arg.chars().all(safe) {
+    arg.to_string()
+  } else {
+    format!("'{}'", arg.replace('\'', r"'\''"))
+  }
+}
+
+/// Spawns `command` in medic's process group, so that it may use the
+/// terminal and is sent Ctrl-C along with medic. A command with a timeout is
+/// given a process group of its own, so that anything it starts is
+/// interrupted or killed along with it, unless it reads from the `terminal`:
+/// a background process group reading from the terminal would be stopped.
+pub fn spawn(command: &mut Command, timeout: Option<Duration>, terminal: bool) -> std::io::Result<Child> {
+  let group = timeout.is_some() && !terminal;
+  if group {
+    command.process_group(0);
+  }
+  interrupt::spawn(command, group)
+}
+
+/// Runs `command` and collects its output, failing with
+/// `MedicError::TimedOut` when it runs longer than `timeout`. Its stdio should
+/// be set beforehand, as the defaults of `Command::output` do not apply.
+pub fn output(command: &mut Command, timeout: Option<Duration>, terminal: bool) -> Result<Output, MedicError> {
+  wait(spawn(command, timeout, terminal)?, timeout)
+}
+
+/// Waits for `child` and collects whatever output has not already been taken
+/// from it, like `Child::wait_with_output`. When `timeout` passes first,
+/// `child` is killed along with its process group, when it has its own, and the output so far is returned in
+/// `MedicError::TimedOut`.
+pub fn wait(child: Child, timeout: Option<Duration>) -> Result<Output, MedicError> {
+  let pid = child.id();
+  let result = wait_until(child, timeout);
+  interrupt::finished(pid);
+  result
+}
+
+fn wait_until(mut child: Child, timeout: Option<Duration>) -> Result<Output, MedicError> {
+  let Some(timeout) = timeout else {
+    return Ok(child.wait_with_output()?);
+  };
+
+  let stdout = collect(child.stdout.take());
+  let stderr = collect(child.stderr.take());
+  let deadline = Instant::now() + timeout;
+
+  loop {
+    if let Some(status) = child.try_wait()? {
+      return Ok(Output {
+        status,
+        stdout: stdout.finish(None),
+        stderr: stderr.finish(None),
+      });
+    }
+    if Instant::now() >= deadline {
+      interrupt::kill(child.id());
+      let status = child.wait()?;
+      // Anything left running outside the process group may hold the pipes
+      // open, so readers are given only a moment to drain them.
+      let grace = Some(Instant::now() + Duration::from_millis(100));
+      let output = Output {
+        status,
+        stdout: stdout.finish(grace),
+        stderr: stderr.finish(grace),
+      };
+      return Err(MedicError::TimedOut(timeout, output));
+    }
+    thread::sleep(POLL_INTERVAL);
+  }
+}
+
+/// Separates a timeout from the output written before the command was killed,
+/// so that it fails like any other unsuccessful command. Returns the timeout
+/// error alongside.
+pub fn partial_output(result: Result<Output, MedicError>) -> (Result<Output, MedicError>, Option<MedicError>) {
+  match result {
+    Err(MedicError::TimedOut(timeout, output)) => (Ok(output.clone()), Some(MedicError::TimedOut(timeout, output))),
+    result => (result, None),
+  }
+}
+
+struct Collector {
+  buffer: Arc<Mutex<Vec<u8>>>,
+  reader: Option<thread::JoinHandle<()>>,
+}
+
+impl Collector {
+  fn finish(self, deadline: Option<Instant>) -> Vec<u8> {
+    if let Some(reader) = self.reader {
+      match deadline {
+        None => {
+          let _ = reader.join();
+        }
+        Some(deadline) => {
+          while !reader.is_finished() && Instant::now() < deadline {
+            thread::sleep(POLL_INTERVAL);
+          }
+        }
+      }
+    }
+    std::mem::take(&mut *self.buffer.lock().unwrap())
+  }
+}
+
+fn collect<R: Read + Send + 'static>(pipe: Option<R>) -> Collector {
+  let buffer = Arc::new(Mutex::new(vec![]));
+  let reader = pipe.map(|mut pipe| {
+    let buffer = Arc::clone(&buffer);
+    thread::spawn(move || {
+      let mut chunk = [0; 4096];
+      while let Ok(len) = pipe.read(&mut chunk)
+        && len > 0
+      {
+        buffer.lock().unwrap().extend_from_slice(&chunk[..len]);
+      }
+    })
+  });
+  Collector { buffer, reader }
+}
@@ -0,0 +1,71 @@
+// @related [subject](medic-src/src/filter/mod.rs)
+
+use super::{Filter, selects};
+use crate::shell::ShellConfig;
+use crate::step::Step;
+
+fn shell(name: &str, tags: &[&str]) -> ShellConfig {
+  let mut config = ShellConfig::new(name.into(), "true".into(), None, None, false);
+  config.tags = tags.iter().map(|tag| tag.to_string()).collect();
+  config
+}
+
+fn filters(filters: &[&str]) -> Vec<Filter> {
+  filters
+    .iter()
+    .map(|filter| filter.parse().unwrap())
+    .collect()
+}
+
+#[test]
+fn test_from_str() {
+  assert_eq!("tag:fast".parse::<Filter>(), Ok(Filter::Tag("fast".into())));
+  assert_eq!(
+    "name:Unit*".parse::<Filter>(),
+    Ok(Filter::Name(glob::Pattern::new("Unit*").unwrap()))
+  );
+  assert_eq!(
+    "fast".parse::<Filter>(),
+    Err("invalid filter `fast`; expected `tag:<tag>` or `name:<glob>`".into())
+  );
+  assert!("tag:".parse::<Filter>().is_err());
+  assert!("name:[".parse::<Filter>().is_err());
+}
+
+#[test]
+fn test_display() {
+  assert_eq!(Filter::Tag("fast".into()).to_string(), "tag:fast");
+  assert_eq!("name:*tests".parse::<Filter>().unwrap().to_string(), "name:*tests");
+}
+
+#[test]
+fn test_selects_without_filters() {
+  assert!(selects(&shell("Lint", &[]), &[], &[]));
+}
+
+#[test]
+fn test_selects_only() {
+  let only = filters(&["tag:fast", "name:*tests"]);
+
+  assert!(selects(&shell("Lint", &["fast"]), &only, &[]));
+  assert!(selects(&shell("Unit tests", &[]), &only, &[]));
+  assert!(!selects(&shell("Deploy", &["slow"]), &only, &[]));
+}
+
+#[test]
+fn test_selects_skip() {
+  let only = filters(&["tag:fast"]);
+  let skip = filters(&["tag:slow"]);
+
+  assert!(selects(&shell("Lint", &["fast"]), &only, &skip));
+  assert!(!selects(&shell("Browser tests", &["fast", "slow"]), &only, &skip));
+  assert!(!selects(&shell("Deploy", &["slow"]), &[], &skip));
+}
+
+#[test]
+fn test_selects_nested_steps() {
+  let nested = Step::Steps(vec![Step::Shell(shell("Lint", &["fast"]))]);
+
+  assert!(selects(&nested, &filters(&["tag:slow"]), &[]));
+  assert!(selects(&nested, &[], &filters(&["name:*"])));
+}
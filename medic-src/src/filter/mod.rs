@@ -0,0 +1,66 @@
+// @related [tests](medic-src/src/filter/filter_test.rs)
+
+#[cfg(test)]
+mod filter_test;
+
+use crate::runnable::Runnable;
+
+use glob::Pattern;
+use std::fmt;
+use std::str::FromStr;
+
+/// Selects steps by tag or name, from `--only` and `--skip`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Filter {
+  /// Steps whose name matches a glob, from `name:<glob>`.
+  Name(Pattern),
+  /// Steps with a tag, from `tag:<tag>`.
+  Tag(String),
+}
+
+impl Filter {
+  pub fn matches(&self, name: &str, tags: &[String]) -> bool {
+    match self {
+      Filter::Name(pattern) => pattern.matches(name),
+      Filter::Tag(tag) => tags.contains(tag),
+    }
+  }
+}
+
+impl FromStr for Filter {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.split_once(':') {
+      Some(("tag", tag)) if !tag.is_empty() => Ok(Filter::Tag(tag.to_string())),
+      Some(("name", glob)) if !glob.is_empty() => Pattern::new(glob)
+        .map(Filter::Name)
+        .map_err(|err| format!("invalid name glob `{glob}`: {err}")),
+      _ => Err(format!("invalid filter `{s}`; expected `tag:<tag>` or `name:<glob>`")),
+    }
+  }
+}
+
+impl fmt::Display for Filter {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Filter::Name(pattern) => write!(f, "name:{pattern}"),
+      Filter::Tag(tag) => write!(f, "tag:{tag}"),
+    }
+  }
+}
+
+/// Whether `runnable` should run, given the filters from `--only` and
+/// `--skip`. A step runs when it matches any `only` filter, or there are
+/// none, and matches no `skip` filter. Steps without a name, such as nested
+/// lists of steps or shipit's audit, test and update sections, always run
+/// so that the steps inside them can be filtered.
+pub fn selects(runnable: &impl Runnable, only: &[Filter], skip: &[Filter]) -> bool {
+  let Some(name) = runnable.name() else {
+    return true;
+  };
+  let tags = runnable.tags();
+
+  (only.is_empty() || only.iter().any(|filter| filter.matches(name, tags)))
+    && !skip.iter().any(|filter| filter.matches(name, tags))
+}
@@ -0,0 +1,50 @@
+//! A temporary project for tests which read or write files beside a
+//! manifest. The directory is removed when the project is dropped.
+
+use crate::cli::Flags;
+use crate::shell::ShellConfig;
+
+use std::ops::Deref;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+use tempfile::TempDir;
+
+// Steps failing with `--keep-going` are counted across the process, so tests
+// which run steps with it must not overlap.
+static KEEP_GOING: Mutex<()> = Mutex::new(());
+
+pub struct Project(TempDir);
+
+impl Project {
+  /// A project whose `medic.toml` holds `manifest`.
+  pub fn new(manifest: &str) -> Self {
+    let dir = tempfile::Builder::new().prefix("medic-").tempdir().unwrap();
+    std::fs::write(dir.path().join("medic.toml"), manifest).unwrap();
+    Self(dir)
+  }
+
+  /// Flags which run medic with the project's manifest.
+  pub fn flags(&self) -> Flags {
+    Flags {
+      config_path: self.join("medic.toml"),
+      ..Flags::default()
+    }
+  }
+}
+
+impl Deref for Project {
+  type Target = Path;
+
+  fn deref(&self) -> &Path {
+    self.0.path()
+  }
+}
+
+pub fn shell(name: &str, shell: &str) -> ShellConfig {
+  ShellConfig::new(name.into(), shell.into(), None, None, false)
+}
+
+/// Held by tests which count failures saved with `--keep-going`.
+pub fn keep_going() -> MutexGuard<'static, ()> {
+  KEEP_GOING.lock().unwrap_or_else(|err| err.into_inner())
+}
@@ -0,0 +1,91 @@
+// @related [subject](medic-src/src/history/mod.rs)
+
+use super::{History, median, slow_after};
+use crate::fixture::Project;
+use std::time::Duration;
+
+#[test]
+fn test_median() {
+  assert_eq!(median(&[]), 0);
+  assert_eq!(median(&[300, 100, 200]), 200);
+  assert_eq!(median(&[400, 100, 200, 300]), 250);
+}
+
+#[test]
+fn test_record_saves_durations_by_section() {
+  let dir = Project::new("");
+
+  let mut history = History::load(&dir.join("medic.toml")).unwrap();
+  history.record("update", "\x1b[36mDeps\x1b[0m", Duration::from_millis(1_200));
+  history.record("update", "Deps", Duration::from_millis(1_400));
+  history.record("workflows/deploy", "Push", Duration::from_millis(300));
+  assert!(dir.join(".medic/history.json").exists());
+
+  let history = History::load(&dir.join("medic.toml")).unwrap();
+  assert_eq!(
+    history.sections().collect::<Vec<_>>(),
+    vec!["update", "workflows/deploy"]
+  );
+  assert_eq!(history.steps("update").unwrap()["Deps"], vec![1_200, 1_400]);
+  assert_eq!(history.steps("workflows/deploy").unwrap()["Push"], vec![300]);
+  assert!(history.steps("test").is_none());
+}
+
+#[test]
+fn test_record_keeps_recent_runs() {
+  let dir = Project::new("");
+
+  let mut history = History::load(&dir.join("medic.toml")).unwrap();
+  for millis in 0..60 {
+    history.record("test", "Unit", Duration::from_millis(millis));
+  }
+  let runs = &history.steps("test").unwrap()["Unit"];
+  assert_eq!(runs.len(), 50);
+  assert_eq!(runs[0], 10);
+}
+
+#[test]
+fn test_record_returns_usual_duration_of_slow_steps() {
+  let dir = Project::new("");
+
+  let mut history = History::load(&dir.join("medic.toml")).unwrap();
+  assert_eq!(history.record("test", "Unit", Duration::from_secs(2)), None);
+  assert_eq!(history.record("test", "Unit", Duration::from_secs(3)), None);
+  // Too few runs to know how long the step usually takes.
+  assert_eq!(history.record("test", "Unit", Duration::from_secs(9)), None);
+  assert_eq!(
+    history.record("test", "Unit", Duration::from_secs(10)),
+    Some(Duration::from_secs(3))
+  );
+  assert_eq!(history.record("test", "Unit", Duration::from_secs(4)), None);
+
+  // Steps which take under a second are not slow for taking twice as long.
+  assert_eq!(history.record("test", "Lint", Duration::from_millis(100)), None);
+  assert_eq!(history.record("test", "Lint", Duration::from_millis(100)), None);
+  assert_eq!(history.record("test", "Lint", Duration::from_millis(100)), None);
+  assert_eq!(history.record("test", "Lint", Duration::from_millis(900)), None);
+}
+
+#[test]
+fn test_usual_needs_enough_runs() {
+  let dir = Project::new("");
+
+  let mut history = History::load(&dir.join("medic.toml")).unwrap();
+  history.record("test", "Unit", Duration::from_secs(2));
+  history.record("test", "Unit", Duration::from_secs(4));
+  assert_eq!(history.usual("test", "Unit"), None);
+
+  history.record("test", "Unit", Duration::from_secs(3));
+  assert_eq!(
+    history.usual("test", "\x1b[36mUnit\x1b[0m"),
+    Some(Duration::from_secs(3))
+  );
+  assert_eq!(history.usual("test", "Lint"), None);
+  assert_eq!(history.usual("update", "Unit"), None);
+}
+
+#[test]
+fn test_slow_after() {
+  assert_eq!(slow_after(Duration::from_secs(3)), Duration::from_secs(6));
+  assert_eq!(slow_after(Duration::from_millis(100)), Duration::from_millis(1_100));
+}
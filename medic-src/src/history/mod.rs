@@ -0,0 +1,155 @@
+// @related [tests](medic-src/src/history/history_test.rs)
+
+#[cfg(test)]
+mod history_test;
+
+use crate::config::discovery;
+use crate::error::MedicError;
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+const HISTORY_FILE: &str = ".medic/history.json";
+
+// The history of the project whose steps are running, with the subcommand or
+// workflow they belong to.
+static RECORDING: Mutex<Option<(String, History)>> = Mutex::new(None);
+
+// The number of runs of each step to keep.
+const KEPT: usize = 50;
+
+// A step is slow when it takes this many times as long as it usually does,
+// and at least `SLOW_MARGIN` longer, once it has run `SLOW_RUNS` times.
+const SLOW_FACTOR: u32 = 2;
+const SLOW_MARGIN: Duration = Duration::from_secs(1);
+const SLOW_RUNS: usize = 3;
+
+/// How long each step took in the runs in which it passed, by the
+/// subcommand or workflow it belongs to, oldest first.
+#[derive(Debug, Default, PartialEq)]
+pub struct History {
+  path: PathBuf,
+  sections: BTreeMap<String, BTreeMap<String, Vec<u64>>>,
+}
+
+impl History {
+  /// Loads the history of the project the manifest at `config_path`
+  /// belongs to. History which cannot be read is started over.
+  pub fn load(config_path: &Path) -> Result<Self, MedicError> {
+    let manifest = discovery::locate(config_path)?;
+    let path = discovery::project_dir(&manifest).join(HISTORY_FILE);
+    let sections = std::fs::read_to_string(&path)
+      .ok()
+      .and_then(|json| serde_json::from_str(&json).ok())
+      .unwrap_or_default();
+
+    Ok(Self { path, sections })
+  }
+
+  /// The names of the subcommands and workflows with recorded steps.
+  pub fn sections(&self) -> impl Iterator<Item = &str> {
+    self.sections.keys().map(String::as_str)
+  }
+
+  /// How long each step of `section` took, in milliseconds, oldest first.
+  pub fn steps(&self, section: &str) -> Option<&BTreeMap<String, Vec<u64>>> {
+    self.sections.get(section)
+  }
+
+  /// Records that `step` of `section` passed in `duration`. When the step
+  /// took much longer than it usually does, returns its usual duration.
+  /// Failing to save only means that the run is forgotten.
+  pub fn record(&mut self, section: &str, step: &str, duration: Duration) -> Option<Duration> {
+    let usual = self
+      .usual(section, step)
+      .filter(|usual| duration > slow_after(*usual));
+    let runs = self
+      .sections
+      .entry(section.to_string())
+      .or_default()
+      .entry(console::strip_ansi_codes(step).to_string())
+      .or_default();
+
+    runs.push(duration.as_millis() as u64);
+    if runs.len() > KEPT {
+      runs.drain(..runs.len() - KEPT);
+    }
+    self.save();
+    usual
+  }
+
+  /// How long `step` of `section` usually takes, once it has passed often
+  /// enough to tell.
+  pub fn usual(&self, section: &str, step: &str) -> Option<Duration> {
+    let runs = self
+      .steps(section)?
+      .get(console::strip_ansi_codes(step).as_ref())?;
+    (runs.len() >= SLOW_RUNS).then(|| Duration::from_millis(median(runs)))
+  }
+
+  fn save(&self) {
+    if let Some(dir) = self.path.parent() {
+      let _ = std::fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(&self.sections) {
+      let _ = std::fs::write(&self.path, json);
+    }
+  }
+}
+
+/// Records how long each step which passes takes under `section`, in the
+/// history of the project the manifest at `config_path` belongs to, until
+/// the returned guard is dropped.
+pub fn start(section: &str, config_path: &Path) -> Result<Recording, MedicError> {
+  let history = History::load(config_path)?;
+  *RECORDING.lock().unwrap() = Some((section.to_string(), history));
+  Ok(Recording)
+}
+
+/// Stops recording step durations when dropped.
+pub struct Recording;
+
+impl Drop for Recording {
+  fn drop(&mut self) {
+    RECORDING.lock().unwrap().take();
+  }
+}
+
+/// Records that `step` passed in `duration`, when steps are being recorded.
+/// When the step took much longer than it usually does, returns its usual
+/// duration.
+pub(crate) fn passed(step: &str, duration: Duration) -> Option<Duration> {
+  let mut recording = RECORDING.lock().unwrap();
+  let (section, history) = recording.as_mut()?;
+  history.record(section, step, duration)
+}
+
+/// How long `step` usually takes, when steps are being recorded and it has
+/// passed often enough to tell.
+pub(crate) fn usual(step: &str) -> Option<Duration> {
+  let recording = RECORDING.lock().unwrap();
+  let (section, history) = recording.as_ref()?;
+  history.usual(section, step)
+}
+
+/// How long a step which usually takes `usual` may run before it is slow.
+pub(crate) fn slow_after(usual: Duration) -> Duration {
+  (usual * SLOW_FACTOR).max(usual + SLOW_MARGIN)
+}
+
+/// The middle of `runs`, or the mean of the two middle runs.
+pub fn median(runs: &[u64]) -> u64 {
+  if runs.is_empty() {
+    return 0;
+  }
+  let mut sorted = runs.to_vec();
+  sorted.sort_unstable();
+  let middle = sorted.len() / 2;
+  if sorted.len().is_multiple_of(2) {
+    (sorted[middle - 1] + sorted[middle]) / 2
+  } else {
+    sorted[middle]
+  }
+}
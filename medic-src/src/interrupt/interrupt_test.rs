@@ -0,0 +1,124 @@
+// @related [subject](medic-src/src/interrupt/mod.rs)
+
+use super::{RUNNING, done, quit_unless_done, running, sleep, stop};
+use crate::extra::command;
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Commands with a timeout are given a process group of their own.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+fn shell(cmd: &str) -> Command {
+  let mut command = Command::new("sh");
+  command
+    .arg("-c")
+    .arg(cmd)
+    .stdin(Stdio::null())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped());
+  command
+}
+
+fn is_running(pid: u32) -> bool {
+  RUNNING.lock().unwrap().contains_key(&pid)
+}
+
+#[test]
+fn test_spawned_commands_are_remembered_until_they_exit() {
+  let child = {
+    let _step = running("Sleep");
+    command::spawn(&mut shell("sleep 0.1"), Some(TIMEOUT), false).unwrap()
+  };
+  let pid = child.id();
+  assert_eq!(RUNNING.lock().unwrap()[&pid].step, "Sleep");
+
+  command::wait(child, None).unwrap();
+  assert!(!is_running(pid));
+}
+
+#[test]
+fn test_stop_signals_the_process_group() {
+  // The signal reaches the `sleep` started by the shell, not only the shell.
+  let child = command::spawn(&mut shell("sleep 10 & wait; echo interrupted"), Some(TIMEOUT), false).unwrap();
+  let pid = child.id();
+  let waiting = thread::spawn(move || command::wait(child, None).unwrap());
+
+  thread::sleep(Duration::from_millis(100));
+  let started = Instant::now();
+  stop(&[pid], libc::SIGTERM, Duration::from_secs(5));
+  let output = waiting.join().unwrap();
+
+  assert!(started.elapsed() < Duration::from_secs(5));
+  assert_eq!(output.status.signal(), Some(libc::SIGTERM));
+  assert!(!is_running(pid));
+}
+
+#[test]
+fn test_stop_kills_commands_which_ignore_the_signal() {
+  let child = command::spawn(&mut shell("trap '' TERM; sleep 10"), Some(TIMEOUT), false).unwrap();
+  let pid = child.id();
+  let waiting = thread::spawn(move || command::wait(child, None).unwrap());
+
+  thread::sleep(Duration::from_millis(100));
+  let started = Instant::now();
+  stop(&[pid], libc::SIGTERM, Duration::from_millis(200));
+  let output = waiting.join().unwrap();
+
+  assert!(started.elapsed() < Duration::from_secs(5));
+  assert_eq!(output.status.signal(), Some(libc::SIGKILL));
+}
+
+#[test]
+fn test_commands_without_a_timeout_share_medics_process_group() {
+  let child = command::spawn(&mut shell("sleep 0.2"), None, false).unwrap();
+  let group = unsafe { libc::getpgid(child.id() as libc::pid_t) };
+  assert_eq!(group, unsafe { libc::getpgrp() });
+  assert!(!RUNNING.lock().unwrap()[&child.id()].group);
+  command::wait(child, None).unwrap();
+
+  let child = command::spawn(&mut shell("sleep 0.2"), Some(TIMEOUT), false).unwrap();
+  let group = unsafe { libc::getpgid(child.id() as libc::pid_t) };
+  assert_eq!(group, child.id() as libc::pid_t);
+  command::wait(child, None).unwrap();
+}
+
+#[test]
+fn test_commands_reading_from_the_terminal_share_medics_process_group() {
+  let mut command = shell("sleep 0.2");
+  command.stdin(Stdio::inherit());
+  let child = command::spawn(&mut command, Some(TIMEOUT), true).unwrap();
+  let group = unsafe { libc::getpgid(child.id() as libc::pid_t) };
+  assert_eq!(group, unsafe { libc::getpgrp() });
+  assert!(!RUNNING.lock().unwrap()[&child.id()].group);
+  command::wait(child, Some(TIMEOUT)).unwrap();
+}
+
+#[test]
+fn test_commands_reading_from_the_terminal_are_killed_on_timeout() {
+  let mut command = shell("sleep 10");
+  command.stdin(Stdio::inherit());
+  let started = Instant::now();
+  let result = command::output(&mut command, Some(Duration::from_millis(100)), true);
+
+  assert!(started.elapsed() < Duration::from_secs(5));
+  match result {
+    Err(crate::error::MedicError::TimedOut(_, output)) => assert_eq!(output.status.signal(), Some(libc::SIGKILL)),
+    result => panic!("expected a timeout, got {result:?}"),
+  }
+}
+
+#[test]
+fn test_sleep_waits_out_the_duration() {
+  let started = Instant::now();
+  assert!(sleep(Duration::from_millis(50)));
+  assert!(started.elapsed() >= Duration::from_millis(50));
+}
+
+#[test]
+fn test_watcher_leaves_medic_to_exit_once_done() {
+  // Quitting would end the test run with status 75.
+  done();
+  quit_unless_done(Duration::ZERO);
+}
@@ -0,0 +1,216 @@
+// @related [tests](medic-src/src/interrupt/interrupt_test.rs)
+
+#[cfg(test)]
+mod interrupt_test;
+
+use crate::QUIT_STATUS_CODE;
+use crate::optional_styled::OptionalStyled;
+use crate::theme::current_theme;
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::process::{Child, Command};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+// How long interrupted steps are given to exit before they are killed.
+const GRACE: Duration = Duration::from_secs(5);
+
+// Set by the signal handler, which may do little else safely.
+static SIGNAL: AtomicI32 = AtomicI32::new(0);
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+// Set once medic has stopped running steps, after which it exits by itself.
+static DONE: AtomicBool = AtomicBool::new(false);
+
+// The processes started by steps which are still running, by pid.
+static RUNNING: Mutex<BTreeMap<u32, Running>> = Mutex::new(BTreeMap::new());
+
+thread_local! {
+  // The step being run on this thread, to which the processes it starts
+  // belong.
+  static STEP: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+#[derive(Clone, Debug)]
+struct Running {
+  // Processes sharing medic's process group, so that they may use the
+  // terminal, are sent Ctrl-C by the terminal itself.
+  group: bool,
+  step: String,
+}
+
+/// Handles SIGINT and SIGTERM by forwarding them to the steps which are
+/// running, or to their process groups when they have their own. Steps still running after a grace period are
+/// killed, or straight away when the signal is sent again. Medic then quits
+/// once the interrupted steps return, or at once when no step was running.
+pub fn install() {
+  extern "C" fn on_signal(signal: libc::c_int) {
+    SIGNAL.store(signal, Ordering::SeqCst);
+  }
+
+  unsafe {
+    libc::signal(libc::SIGINT, on_signal as *const () as libc::sighandler_t);
+    libc::signal(libc::SIGTERM, on_signal as *const () as libc::sighandler_t);
+  }
+  thread::spawn(watch);
+}
+
+/// Whether medic has been interrupted, after which no further steps run.
+pub fn interrupted() -> bool {
+  INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Tells the watcher that medic has stopped running steps and is reporting
+/// on them, so that an interrupt no longer makes it quit in the meantime.
+pub fn done() {
+  DONE.store(true, Ordering::SeqCst);
+}
+
+/// Sleeps for `duration`, or until medic is interrupted. Returns whether it
+/// slept the whole time.
+pub(crate) fn sleep(duration: Duration) -> bool {
+  let deadline = Instant::now() + duration;
+  while !interrupted() {
+    let left = deadline.saturating_duration_since(Instant::now());
+    if left.is_zero() {
+      return true;
+    }
+    thread::sleep(left.min(POLL_INTERVAL));
+  }
+  false
+}
+
+/// Marks the processes started on this thread as belonging to `step` until
+/// the returned guard is dropped.
+pub fn running(step: &str) -> StepGuard {
+  let previous = STEP.with(|current| current.replace(Some(step.to_string())));
+  StepGuard(previous)
+}
+
+pub struct StepGuard(Option<String>);
+
+impl Drop for StepGuard {
+  fn drop(&mut self) {
+    STEP.with(|current| *current.borrow_mut() = self.0.take());
+  }
+}
+
+/// Spawns `command`, remembering it until `finished` is called with its pid.
+/// With `group`, the command has been given a process group of its own.
+/// Nothing more is spawned once medic has been interrupted.
+pub(crate) fn spawn(command: &mut Command, group: bool) -> std::io::Result<Child> {
+  let mut running = RUNNING.lock().unwrap();
+  if interrupted() {
+    return Err(std::io::Error::new(std::io::ErrorKind::Interrupted, "Interrupted"));
+  }
+
+  let child = command.spawn()?;
+  let step = STEP
+    .with(|current| current.borrow().clone())
+    .unwrap_or_else(|| command.get_program().to_string_lossy().into_owned());
+  running.insert(child.id(), Running { group, step });
+  Ok(child)
+}
+
+/// Forgets a process once it has exited.
+pub(crate) fn finished(pid: u32) {
+  RUNNING.lock().unwrap().remove(&pid);
+}
+
+/// Kills a process which has run out of time, along with its process group
+/// when it has its own.
+pub(crate) fn kill(pid: u32) {
+  send(&[pid], libc::SIGKILL);
+}
+
+fn watch() {
+  let signal = loop {
+    match SIGNAL.swap(0, Ordering::SeqCst) {
+      0 => thread::sleep(POLL_INTERVAL),
+      signal => break signal,
+    }
+  };
+
+  let (pids, mut steps): (Vec<u32>, Vec<String>) = {
+    let running = RUNNING.lock().unwrap();
+    INTERRUPTED.store(true, Ordering::SeqCst);
+    running
+      .iter()
+      .map(|(pid, running)| (*pid, running.step.clone()))
+      .unzip()
+  };
+  if pids.is_empty() {
+    quit_unless_done(Duration::ZERO);
+    return;
+  }
+
+  steps.sort();
+  steps.dedup();
+  eprintln!();
+  for step in steps {
+    eprintln!(
+      "{} {step}",
+      OptionalStyled::new("✗ Interrupted", current_theme().error_style.clone())
+    );
+  }
+
+  stop(&pids, signal, GRACE);
+
+  // Interrupted steps return to the main thread, which reports on them and
+  // quits. Should it be unable to, medic quits anyway.
+  quit_unless_done(GRACE);
+}
+
+// Quits unless medic stops running steps within `grace`.
+fn quit_unless_done(grace: Duration) {
+  let deadline = Instant::now() + grace;
+  while !DONE.load(Ordering::SeqCst) {
+    if Instant::now() >= deadline {
+      quit();
+    }
+    thread::sleep(POLL_INTERVAL);
+  }
+}
+
+/// Sends `signal` to the processes `pids`, then kills those still running
+/// after `grace`, or as soon as another signal is received.
+fn stop(pids: &[u32], signal: libc::c_int, grace: Duration) {
+  send(pids, signal);
+
+  let deadline = Instant::now() + grace;
+  while Instant::now() < deadline && SIGNAL.load(Ordering::SeqCst) == 0 {
+    let running = RUNNING.lock().unwrap();
+    if pids.iter().all(|pid| !running.contains_key(pid)) {
+      return;
+    }
+    drop(running);
+    thread::sleep(POLL_INTERVAL);
+  }
+  SIGNAL.store(0, Ordering::SeqCst);
+  send(pids, libc::SIGKILL);
+}
+
+fn send(pids: &[u32], signal: libc::c_int) {
+  let running = RUNNING.lock().unwrap();
+  for pid in pids {
+    match running.get(pid) {
+      Some(Running { group: true, .. }) => unsafe {
+        libc::killpg(*pid as libc::pid_t, signal);
+      },
+      Some(Running { group: false, .. }) if signal != libc::SIGINT => unsafe {
+        libc::kill(*pid as libc::pid_t, signal);
+      },
+      _ => {}
+    }
+  }
+}
+
+fn quit() -> ! {
+  let _ = console::Term::stdout().show_cursor();
+  let _ = console::Term::stderr().show_cursor();
+  std::process::exit(QUIT_STATUS_CODE);
+}
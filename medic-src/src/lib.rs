@@ -3,25 +3,41 @@
 
 pub mod app_result;
 pub mod audit;
+pub mod cache;
 pub mod check;
 pub mod cli;
+pub mod condition;
 pub mod config;
 pub mod context;
 pub mod doctor;
+pub mod dry_run;
 pub mod error;
 pub mod extra;
+pub mod filter;
+pub mod history;
+pub mod interrupt;
+pub mod list;
 pub mod noop_config;
 pub mod outdated;
 pub mod progress;
 pub mod recoverable;
+pub mod report;
+pub mod retry;
 pub mod runnable;
+pub mod schema;
 pub mod semaphore;
 pub mod shell;
 pub mod shipit;
+pub mod show;
+pub mod state;
+pub mod stats;
 pub mod step;
 pub mod theme;
 pub mod util;
+pub mod validate;
 
+#[cfg(test)]
+mod fixture;
 mod optional_styled;
 
 pub use app_result::AppResult;
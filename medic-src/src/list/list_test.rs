@@ -0,0 +1,150 @@
+// @related [subject](medic-src/src/list/mod.rs)
+
+use super::{Entry, Section, sections, to_tree};
+use crate::config::Manifest;
+use indoc::indoc;
+use std::sync::Once;
+
+static INIT: Once = Once::new();
+pub fn initialize() {
+  INIT.call_once(|| {
+    if crate::theme::THEME.get().is_none() {
+      let theme = crate::theme::dark_theme();
+      crate::theme::set_theme(theme);
+    }
+  });
+}
+
+fn manifest() -> Manifest {
+  initialize();
+  toml::from_str(indoc! {r#"
+    [doctor]
+    checks = [
+      { check = "homebrew" },
+      { check = "tool-versions", output = "stdio" },
+      { name = "Xcode", shell = "xcode-select -p", platform = ["Darwin"], remedy = "xcode-select --install" },
+    ]
+
+    [test]
+    checks = [
+      { doctor = {} },
+      [
+        { name = "Unit", shell = "cargo test", tags = ["fast"] },
+        { step = "rust", command = "test" },
+      ],
+    ]
+
+    [shipit]
+    steps = [
+      { test = {} },
+      { name = "Push", shell = "git push" },
+    ]
+
+    [workflows.deploy]
+    steps = [{ name = "Deploy", shell = "bin/deploy" }]
+  "#})
+  .unwrap()
+}
+
+fn entry(name: &str, kind: &str) -> Entry {
+  Entry {
+    name: name.into(),
+    kind: kind.into(),
+    platform: None,
+    remedy: false,
+    tags: vec![],
+    steps: vec![],
+  }
+}
+
+fn names(sections: &[Section]) -> Vec<&str> {
+  sections
+    .iter()
+    .map(|section| section.name.as_str())
+    .collect()
+}
+
+#[test]
+fn sections_lists_every_section() {
+  let manifest = manifest();
+  let sections = sections(&manifest, None).unwrap();
+
+  assert_eq!(names(&sections), vec!["doctor", "test", "shipit", "workflows.deploy"]);
+}
+
+#[test]
+fn sections_finds_one_section_or_workflow() {
+  let manifest = manifest();
+
+  assert_eq!(names(&sections(&manifest, Some("test")).unwrap()), vec!["test"]);
+  assert_eq!(
+    sections(&manifest, Some("deploy")).unwrap(),
+    vec![Section {
+      name: "workflows.deploy".into(),
+      steps: vec![entry("Deploy (bin/deploy)", "shell")],
+    }]
+  );
+  assert_eq!(
+    sections(&manifest, Some("release"))
+      .unwrap_err()
+      .to_string(),
+    "No steps named `release` found in medic config."
+  );
+}
+
+#[test]
+fn sections_describe_steps() {
+  let manifest = manifest();
+  let doctor = sections(&manifest, Some("doctor")).unwrap().remove(0);
+
+  assert_eq!(
+    doctor.steps,
+    vec![
+      Entry {
+        remedy: true,
+        ..entry("homebrew", "check")
+      },
+      entry("tool-versions", "check"),
+      Entry {
+        platform: Some(vec!["Darwin".into()]),
+        remedy: true,
+        ..entry("Xcode (xcode-select -p)", "shell")
+      },
+    ]
+  );
+}
+
+#[test]
+fn to_tree_expands_nested_steps() {
+  let manifest = manifest();
+  let shipit = sections(&manifest, Some("shipit")).unwrap().remove(0);
+
+  assert_eq!(
+    console::strip_ansi_codes(&to_tree(&shipit)),
+    indoc! {"
+      shipit
+      ├── test
+      │   ├── doctor
+      │   │   ├── homebrew [check; remedy]
+      │   │   ├── tool-versions [check]
+      │   │   └── Xcode (xcode-select -p) [shell; platform: Darwin; remedy]
+      │   └── steps
+      │       ├── Unit (cargo test) [shell; tags: fast]
+      │       └── rust: test! [step]
+      └── Push (git push) [shell]
+    "}
+  );
+}
+
+#[test]
+fn entries_serialize_to_json() {
+  let entry = Entry {
+    tags: vec!["fast".into()],
+    ..entry("Unit (cargo test)", "shell")
+  };
+
+  assert_eq!(
+    serde_json::to_value(&entry).unwrap(),
+    serde_json::json!({ "name": "Unit (cargo test)", "type": "shell", "remedy": false, "tags": ["fast"] })
+  );
+}
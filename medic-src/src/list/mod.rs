@@ -0,0 +1,279 @@
+// @related [tests](medic-src/src/list/list_test.rs)
+
+#[cfg(test)]
+mod list_test;
+
+use crate::check::OutputFormat;
+use crate::config::Manifest;
+use crate::error::MedicError;
+use crate::optional_styled::OptionalStyled;
+use crate::outdated::OutdatedCheck;
+use crate::runnable::Runnable;
+use crate::shell::ShellConfig;
+use crate::shipit::ShipitStep;
+use crate::step::StepConfig;
+use crate::theme::current_theme;
+use crate::{AppResult, AuditStep, Check, DoctorStep, Step};
+
+use serde::Serialize;
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+
+pub(crate) const SECTIONS: [&str; 6] = ["doctor", "audit", "test", "update", "outdated", "shipit"];
+
+/// A list of steps run by a subcommand or workflow.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Section {
+  pub name: String,
+  pub steps: Vec<Entry>,
+}
+
+/// A step as shown by `medic list`. Steps which run other steps, such as
+/// nested lists or shipit's `audit`, list those steps beneath them.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Entry {
+  pub name: String,
+  /// One of `check`, `step`, `shell` or `outdated`, or for steps which run
+  /// other steps, `steps` or the name of the section they run.
+  #[serde(rename = "type")]
+  pub kind: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub platform: Option<Vec<String>>,
+  /// Whether the step may suggest a remedy when it fails. Shell actions and
+  /// outdated checks may be given one, while checks with JSON output may
+  /// report their own. Checks with `output = "stdio"` cannot.
+  pub remedy: bool,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub tags: Vec<String>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub steps: Vec<Entry>,
+}
+
+/// Prints the steps of `workflow`, or of every subcommand and workflow in
+/// the manifest at `path`, as a tree or as JSON.
+pub fn run(path: &Path, workflow: Option<&str>, json: bool) -> AppResult<()> {
+  let manifest = Manifest::new(path)?;
+  let sections = sections(&manifest, workflow)?;
+
+  let output = if json {
+    serde_json::to_string_pretty(&sections).map_err(|err| MedicError::from(format!("Unable to list steps: {err}")))?
+  } else {
+    sections.iter().map(to_tree).collect::<Vec<_>>().join("\n")
+  };
+
+  match writeln!(std::io::stdout(), "{}", output.trim_end()) {
+    Err(err) if err.kind() != ErrorKind::BrokenPipe => AppResult::Err(Some(err.into())),
+    _ => AppResult::Ok(()),
+  }
+}
+
+/// The sections to list: the one named `workflow`, or every section in the
+/// manifest.
+pub fn sections(manifest: &Manifest, workflow: Option<&str>) -> Result<Vec<Section>, MedicError> {
+  let workflows = manifest
+    .workflows
+    .keys()
+    .map(|name| format!("workflows.{name}"));
+  let all: Vec<String> = SECTIONS
+    .iter()
+    .map(|name| name.to_string())
+    .chain(workflows)
+    .collect();
+
+  match workflow {
+    None => Ok(
+      all
+        .iter()
+        .filter_map(|name| {
+          section(manifest, name).map(|steps| Section {
+            name: name.clone(),
+            steps,
+          })
+        })
+        .collect(),
+    ),
+    Some(name) => {
+      let key = if SECTIONS.contains(&name) {
+        name.to_string()
+      } else {
+        format!("workflows.{name}")
+      };
+      match section(manifest, &key) {
+        Some(steps) => Ok(vec![Section { name: key, steps }]),
+        None => Err(format!("No steps named `{name}` found in medic config.").into()),
+      }
+    }
+  }
+}
+
+fn section(manifest: &Manifest, name: &str) -> Option<Vec<Entry>> {
+  match name {
+    "audit" => manifest
+      .audit
+      .as_ref()
+      .map(|audit| audit.checks.iter().map(audit_step).collect()),
+    "doctor" => manifest
+      .doctor
+      .as_ref()
+      .map(|doctor| doctor.checks.iter().map(doctor_step).collect()),
+    "outdated" => manifest
+      .outdated
+      .as_ref()
+      .map(|outdated| outdated.checks.iter().map(outdated_check).collect()),
+    "shipit" => manifest.shipit.as_ref().map(|shipit| {
+      shipit
+        .steps
+        .iter()
+        .map(|step| shipit_step(step, manifest))
+        .collect()
+    }),
+    "test" => manifest
+      .test
+      .as_ref()
+      .map(|test| steps(&test.checks, manifest)),
+    "update" => manifest
+      .update
+      .as_ref()
+      .map(|update| steps(&update.steps, manifest)),
+    name => name
+      .strip_prefix("workflows.")
+      .and_then(|workflow| manifest.workflows.get(workflow))
+      .map(|workflow| steps(&workflow.steps, manifest)),
+  }
+}
+
+fn steps(steps: &[Step], manifest: &Manifest) -> Vec<Entry> {
+  steps
+    .iter()
+    .map(|step| self::step(step, manifest))
+    .collect()
+}
+
+fn step(step: &Step, manifest: &Manifest) -> Entry {
+  match step {
+    Step::Check(config) => check(config),
+    Step::Doctor(_) => nested("doctor", section(manifest, "doctor")),
+    Step::Group(group) => nested("steps", Some(steps(&group.steps, manifest))),
+    Step::Shell(config) => shell(config),
+    Step::Step(config) => step_config(config),
+    Step::Steps(nested_steps) => nested("steps", Some(steps(nested_steps, manifest))),
+  }
+}
+
+fn audit_step(step: &AuditStep) -> Entry {
+  match step {
+    AuditStep::Check(config) => check(config),
+    AuditStep::Shell(config) => shell(config),
+    AuditStep::Step(config) => step_config(config),
+  }
+}
+
+fn doctor_step(step: &DoctorStep) -> Entry {
+  match step {
+    DoctorStep::Check(config) => check(config),
+    DoctorStep::Shell(config) => shell(config),
+    DoctorStep::Step(config) => step_config(config),
+  }
+}
+
+fn shipit_step(step: &ShipitStep, manifest: &Manifest) -> Entry {
+  match step {
+    ShipitStep::Audit(_) => nested("audit", section(manifest, "audit")),
+    ShipitStep::Check(config) => check(config),
+    ShipitStep::Shell(config) => shell(config),
+    ShipitStep::Step(config) => step_config(config),
+    ShipitStep::Test(_) => nested("test", section(manifest, "test")),
+    ShipitStep::Update(_) => nested("update", section(manifest, "update")),
+  }
+}
+
+fn check(config: &Check) -> Entry {
+  leaf(config, "check", config.output == OutputFormat::Json)
+}
+
+fn outdated_check(config: &OutdatedCheck) -> Entry {
+  leaf(config, "outdated", config.remedy.is_some())
+}
+
+fn shell(config: &ShellConfig) -> Entry {
+  leaf(config, "shell", config.remedy.is_some())
+}
+
+fn step_config(config: &StepConfig) -> Entry {
+  leaf(config, "step", false)
+}
+
+fn leaf(runnable: &impl Runnable, kind: &str, remedy: bool) -> Entry {
+  Entry {
+    name: console::strip_ansi_codes(&runnable.to_string()).into_owned(),
+    kind: kind.to_string(),
+    platform: runnable
+      .platform()
+      .clone()
+      .filter(|platforms| !platforms.is_empty()),
+    remedy,
+    tags: runnable.tags().to_vec(),
+    steps: vec![],
+  }
+}
+
+fn nested(kind: &str, steps: Option<Vec<Entry>>) -> Entry {
+  Entry {
+    name: kind.to_string(),
+    kind: kind.to_string(),
+    platform: None,
+    remedy: false,
+    tags: vec![],
+    steps: steps.unwrap_or_default(),
+  }
+}
+
+/// Renders `section` as a tree, one step per line.
+pub fn to_tree(section: &Section) -> String {
+  let mut output = format!(
+    "{}\n",
+    OptionalStyled::new(&section.name, current_theme().highlight_style.clone())
+  );
+  render_entries(&mut output, &section.steps, "");
+  output
+}
+
+fn render_entries(output: &mut String, entries: &[Entry], prefix: &str) {
+  for (index, entry) in entries.iter().enumerate() {
+    let last = index == entries.len() - 1;
+    let (branch, indent) = if last {
+      ("└── ", "    ")
+    } else {
+      ("├── ", "│   ")
+    };
+
+    // Steps which run other steps are named after what they run.
+    let mut details = vec![];
+    if entry.kind != entry.name {
+      details.push(entry.kind.clone());
+    }
+    if let Some(platforms) = &entry.platform {
+      details.push(format!("platform: {}", platforms.join(", ")));
+    }
+    if !entry.tags.is_empty() {
+      details.push(format!("tags: {}", entry.tags.join(", ")));
+    }
+    if entry.remedy {
+      details.push("remedy".to_string());
+    }
+
+    let mut line = format!(
+      "{prefix}{branch}{}",
+      OptionalStyled::new(&entry.name, current_theme().text_style.clone())
+    );
+    if !details.is_empty() {
+      line.push_str(&format!(
+        " {}",
+        OptionalStyled::new(format!("[{}]", details.join("; ")), current_theme().dim_style.clone())
+      ));
+    }
+    output.push_str(&line);
+    output.push('\n');
+    render_entries(output, &entry.steps, &format!("{prefix}{indent}"));
+  }
+}
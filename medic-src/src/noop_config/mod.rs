@@ -1,4 +1,8 @@
+use schemars::JsonSchema;
 use serde::Deserialize;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+// Unknown fields are denied so that a list holding a single step is not
+// mistaken for a `NoopConfig` when deserializing untagged enums.
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq)]
+#[serde(deny_unknown_fields)]
 pub struct NoopConfig {}
@@ -2,64 +2,116 @@
 
 use super::summary::OutdatedSummary;
 use crate::cli::Flags;
+use crate::condition::{Condition, EnvCondition};
 use crate::context::Context;
 use crate::error::MedicError;
 use crate::optional_styled::OptionalStyled;
 use crate::recoverable::{Recoverable, Remedy};
+use crate::retry::Retry;
 use crate::runnable::Runnable;
 use crate::std_to_string;
 use crate::theme::current_theme;
-use crate::util::StringOrList;
+use crate::util::{HumanDuration, StringOrList};
 use console::style;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use std::collections::BTreeMap;
 use std::fmt;
 use std::io::{BufRead, BufReader};
 use std::process::{Command, Stdio};
 use std::thread;
+use std::time::Duration;
 use which::which;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq)]
 pub struct OutdatedCheck {
+  /// Flags passed to the command as `--flag <value>`, once per value when given a list.
   pub args: Option<BTreeMap<String, StringOrList>>,
+  /// Directory to change into before running.
   pub cd: Option<String>,
+  /// Name of the check. Runs `medic-outdated-{check}` from the PATH.
   pub check: String,
+  /// A shell command which must succeed for this to run, for example `"test -f package.json"`.
+  pub r#if: Option<String>,
+  /// Environment variables which must be set, or a table of values they must have, for this to run.
+  pub if_env: Option<EnvCondition>,
+  /// Description shown when running, in place of the command.
   pub name: Option<String>,
+  /// Platforms on which to run, for example `Darwin`. Runs everywhere when empty.
   pub platform: Option<Vec<String>>,
+  /// A command suggested to update outdated dependencies.
   pub remedy: Option<String>,
+  /// Number of times to run the command again when it fails.
+  #[serde(default)]
+  pub retries: u32,
+  /// Double `retry_delay` after each retry, up to a minute.
+  #[serde(default)]
+  pub retry_backoff: bool,
+  /// How long to wait before retrying, for example `"2s"`. Defaults to one second.
+  pub retry_delay: Option<HumanDuration>,
+  /// Tags selected by `--only tag:<tag>` and `--skip tag:<tag>`.
+  #[serde(default)]
+  pub tags: Vec<String>,
+  /// Kill the command and fail when it runs longer than this, for example `"90s"`. Overrides `--timeout`.
+  pub timeout: Option<HumanDuration>,
+  /// A shell command which must fail for this to run.
+  pub unless: Option<String>,
+  /// Environment variables which must not be set, or a table of values they must not have, for this to run.
+  pub unless_env: Option<EnvCondition>,
 }
 
 impl Runnable for OutdatedCheck {
+  fn condition(&self) -> Condition<'_> {
+    Condition {
+      cd: self.cd.as_deref(),
+      env: None,
+      r#if: self.r#if.as_deref(),
+      if_env: self.if_env.as_ref(),
+      unless: self.unless.as_deref(),
+      unless_env: self.unless_env.as_ref(),
+    }
+  }
+
+  fn name(&self) -> Option<&str> {
+    Some(self.name.as_deref().unwrap_or(&self.check))
+  }
+
   fn platform(&self) -> &Option<Vec<String>> {
     &self.platform
   }
 
-  fn run(&self, progress: &mut retrogress::ProgressBar, _flags: &mut Flags, _ctx: &Context) -> Recoverable<()> {
+  fn run(&self, progress: &mut retrogress::ProgressBar, flags: &mut Flags, _ctx: &Context) -> Recoverable<()> {
+    let timeout = self.timeout.map(Duration::from).or(flags.timeout);
+    let retry = Retry::new(self.retries, self.retry_delay, self.retry_backoff);
     let command_name = self.to_string();
     let pb = progress.append(&command_name);
 
     match self.to_command() {
       Ok(mut command) => {
+        // Stdin is left to the terminal.
         command.stdout(Stdio::piped()).stderr(Stdio::piped());
 
-        let mut child = command.spawn()?;
-        let stderr = child
-          .stderr
-          .take()
-          .ok_or("Error capturing stderr of outdated check.")?;
-        let mut err_progress = progress.clone();
-        let command_name_err = command_name.clone();
-
-        let err_thr = thread::spawn(move || {
-          let reader = BufReader::new(stderr);
-          reader.lines().map_while(Result::ok).for_each(|line| {
-            let msg = line.split("::").last().unwrap_or("");
-            err_progress.set_message(pb, format!("{command_name_err}\t{}", style(msg).dim()));
-          })
-        });
+        let output = retry.run(progress, pb, &command_name, |progress| {
+          let mut child = crate::extra::command::spawn(&mut command, timeout, true)?;
+          let stderr = child
+            .stderr
+            .take()
+            .ok_or("Error capturing stderr of outdated check.")?;
+          let mut err_progress = progress.clone();
+          let command_name_err = command_name.clone();
+
+          let err_thr = thread::spawn(move || {
+            let reader = BufReader::new(stderr);
+            reader.lines().map_while(Result::ok).for_each(|line| {
+              let msg = line.split("::").last().unwrap_or("");
+              err_progress.set_message(pb, format!("{command_name_err}\t{}", style(msg).dim()));
+            })
+          });
 
-        let output = child.wait_with_output();
-        err_thr.join().unwrap();
+          let output = crate::extra::command::wait(child, timeout);
+          err_thr.join().unwrap();
+          output
+        });
 
         progress.set_message(pb, command_name);
 
@@ -120,13 +172,17 @@ impl Runnable for OutdatedCheck {
           }
           Err(err) => {
             progress.failed(pb);
-            Recoverable::Err(Some(err.into()), None)
+            Recoverable::Err(Some(err), None)
           }
         }
       }
       Err(err) => Recoverable::Err(Some(format!("Failed to parse command: {err}").into()), None),
     }
   }
+  fn tags(&self) -> &[String] {
+    &self.tags
+  }
+
   fn to_command(&self) -> Result<Command, MedicError> {
     let check_cmd = format!("medic-outdated-{}", self.check);
     if let Err(_err) = which(&check_cmd) {
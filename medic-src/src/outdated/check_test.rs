@@ -32,9 +32,18 @@ fn deserialize_string() {
       args: None,
       cd: None,
       check: "outdated-name".to_string(),
+      r#if: None,
+      if_env: None,
       name: None,
       platform: None,
       remedy: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
     }
   )
 }
@@ -56,9 +65,18 @@ fn deserialize_args_string() {
       )])),
       cd: None,
       check: "outdated-name".to_string(),
+      r#if: None,
+      if_env: None,
       name: None,
       platform: None,
       remedy: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
     }
   )
 }
@@ -80,9 +98,18 @@ fn deserialize_args_value_list_string() {
       )])),
       cd: None,
       check: "outdated-name".to_string(),
+      r#if: None,
+      if_env: None,
       name: None,
       platform: None,
       remedy: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
     }
   )
 }
@@ -101,9 +128,18 @@ fn deserialize_cd_string() {
       args: None,
       cd: Some("./subdirectory".to_string()),
       check: "outdated-name".to_string(),
+      r#if: None,
+      if_env: None,
       name: None,
       platform: None,
       remedy: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
     }
   )
 }
@@ -122,9 +158,18 @@ fn deserialize_name_string() {
       args: None,
       cd: None,
       check: "outdated-name".to_string(),
+      r#if: None,
+      if_env: None,
       name: Some("Check for outdated things".to_string()),
       platform: None,
       remedy: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
     }
   )
 }
@@ -135,9 +180,18 @@ fn to_command() {
     args: None,
     cd: None,
     check: "thing".to_string(),
+    r#if: None,
+    if_env: None,
     name: None,
     platform: None,
     remedy: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
   };
 
   let cmd = check.to_command().unwrap();
@@ -151,9 +205,18 @@ fn to_command_cd_relative() -> Result<(), MedicError> {
     args: None,
     cd: Some("../fixtures/bin".to_string()),
     check: "thing".to_string(),
+    r#if: None,
+    if_env: None,
     name: None,
     platform: None,
     remedy: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
   };
 
   let path_expansion = extra::env::subst("${PWD}/fixtures/bin").unwrap();
@@ -171,9 +234,18 @@ fn to_command_cd_absolute() -> Result<(), MedicError> {
     args: None,
     cd: Some("/tmp".to_string()),
     check: "thing".to_string(),
+    r#if: None,
+    if_env: None,
     name: None,
     platform: None,
     remedy: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
   };
 
   let cmd = check.to_command()?;
@@ -193,9 +265,18 @@ fn to_command_cd_bad_directory() {
     args: None,
     cd: Some("does-not-exist".to_string()),
     check: "thing".to_string(),
+    r#if: None,
+    if_env: None,
     name: None,
     platform: None,
     remedy: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
   };
 
   let e = check.to_command().err().unwrap();
@@ -208,9 +289,18 @@ fn to_command_missing_command() {
     args: None,
     cd: None,
     check: "missing".to_string(),
+    r#if: None,
+    if_env: None,
     name: None,
     platform: None,
     remedy: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
   };
 
   let e = check.to_command().err().unwrap();
@@ -227,9 +317,18 @@ fn to_string() {
     args: None,
     cd: None,
     check: "thing".to_string(),
+    r#if: None,
+    if_env: None,
     name: None,
     platform: None,
     remedy: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
   };
 
   assert_eq!(
@@ -245,9 +344,18 @@ fn to_string_name() {
     args: None,
     cd: None,
     check: "thing".to_string(),
+    r#if: None,
+    if_env: None,
     name: Some("do things".to_string()),
     platform: None,
     remedy: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
   };
 
   assert_eq!(format!("{check}"), "\u{1b}[36mdo things\u{1b}[0m")
@@ -260,9 +368,18 @@ fn to_string_cd() {
     args: None,
     cd: Some("../subdirectory".to_string()),
     check: "thing".to_string(),
+    r#if: None,
+    if_env: None,
     name: None,
     platform: None,
     remedy: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
   };
 
   assert_eq!(
@@ -0,0 +1,288 @@
+// @related [tests](medic-src/src/report/report_test.rs)
+
+#[cfg(test)]
+mod report_test;
+
+use crate::error::MedicError;
+use crate::interrupt;
+use crate::optional_styled::OptionalStyled;
+use crate::recoverable::Remedy;
+use crate::theme::current_theme;
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// How each step ended, in the order they finished.
+static FINISHED: Mutex<Vec<Outcome>> = Mutex::new(vec![]);
+
+// Steps which failed at first but passed when retried, with the number of
+// attempts they took.
+static RETRIED: Mutex<Vec<(String, u32)>> = Mutex::new(vec![]);
+
+// Steps which failed with `--keep-going`, with their error and remedy.
+static FAILED: Mutex<Vec<Failure>> = Mutex::new(vec![]);
+
+/// How a step ended, as shown in the summary printed at the end of a run.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Status {
+  Cached,
+  Failed,
+  Interrupted,
+  Manual,
+  Optional,
+  Passed,
+  Remedied,
+  Skipped(String),
+}
+
+impl Status {
+  const ORDER: [&str; 8] = [
+    "passed",
+    "remedied",
+    "cached",
+    "skipped",
+    "optional failure",
+    "manual",
+    "failed",
+    "interrupted",
+  ];
+
+  fn label(&self) -> &'static str {
+    match self {
+      Status::Cached => "cached",
+      Status::Failed => "failed",
+      Status::Interrupted => "interrupted",
+      Status::Manual => "manual",
+      Status::Optional => "optional failure",
+      Status::Passed => "passed",
+      Status::Remedied => "remedied",
+      Status::Skipped(_) => "skipped",
+    }
+  }
+
+  fn symbol(&self) -> OptionalStyled {
+    let theme = current_theme();
+    match self {
+      Status::Cached | Status::Passed | Status::Remedied => OptionalStyled::new("✓", theme.success_style.clone()),
+      Status::Failed | Status::Interrupted => OptionalStyled::new("✗", theme.error_style.clone()),
+      Status::Manual | Status::Optional => OptionalStyled::new("!", theme.warning_style.clone()),
+      Status::Skipped(_) => OptionalStyled::new("…", theme.warning_style.clone()),
+    }
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct Outcome {
+  pub duration: Duration,
+  pub started: Instant,
+  pub status: Status,
+  pub step: String,
+}
+
+struct Failure {
+  error: Option<String>,
+  remedy: Option<String>,
+  step: String,
+}
+
+pub(crate) fn passed_after_retry(step: &str, attempts: u32) {
+  RETRIED.lock().unwrap().push((step.to_string(), attempts));
+}
+
+/// Records how `step`, which started at `started`, ended. A step which runs
+/// again, as when the list it is in is run again after a remedy, replaces
+/// how it ended before.
+pub(crate) fn finished(step: &str, status: Status, started: Instant) {
+  let outcome = Outcome {
+    duration: started.elapsed(),
+    started,
+    status,
+    step: step.to_string(),
+  };
+  let mut finished = FINISHED.lock().unwrap();
+  match finished.iter_mut().find(|outcome| outcome.step == step) {
+    Some(previous) => *previous = outcome,
+    None => finished.push(outcome),
+  }
+}
+
+/// How each step has ended so far in this run.
+pub fn outcomes() -> Vec<Outcome> {
+  FINISHED.lock().unwrap().clone()
+}
+
+/// How `step` last ended in this run, if it has finished.
+pub fn outcome_of(step: &str) -> Option<Outcome> {
+  FINISHED
+    .lock()
+    .unwrap()
+    .iter()
+    .rev()
+    .find(|outcome| outcome.step == step)
+    .cloned()
+}
+
+pub(crate) fn failed(step: &str, error: Option<&MedicError>, remedy: Option<&Remedy>) {
+  FAILED.lock().unwrap().push(Failure {
+    error: error
+      .map(ToString::to_string)
+      .filter(|error| !error.trim().is_empty()),
+    remedy: remedy.map(ToString::to_string),
+    step: step.to_string(),
+  });
+}
+
+/// The number of steps which have failed with `--keep-going`.
+pub fn failure_count() -> usize {
+  FAILED.lock().unwrap().len()
+}
+
+/// The error ending a list of steps in which `count` steps failed with
+/// `--keep-going`.
+pub fn failed_steps(count: usize) -> MedicError {
+  match count {
+    1 => "1 step failed".into(),
+    count => format!("{count} steps failed").into(),
+  }
+}
+
+/// Prints what a run leaves worth knowing once it ends: a summary of how
+/// each step ended and how long it took, the steps which only passed after
+/// being retried, and every step which failed with `--keep-going`.
+pub fn print() {
+  interrupt::done();
+  let finished = std::mem::take(&mut *FINISHED.lock().unwrap());
+  for line in summary(&finished) {
+    eprintln!("{line}");
+  }
+  print_retried();
+  print_failed();
+}
+
+/// The lines of the summary table for `outcomes`, ending with the number of
+/// steps with each status and the time from the first starting to the last
+/// finishing.
+pub fn summary(outcomes: &[Outcome]) -> Vec<String> {
+  if outcomes.is_empty() {
+    return vec![];
+  }
+
+  let width = outcomes
+    .iter()
+    .map(|outcome| outcome.status.label().len())
+    .max()
+    .unwrap_or_default();
+  let mut lines = vec![
+    String::new(),
+    OptionalStyled::new("Summary:", current_theme().text_style.clone()).to_string(),
+  ];
+  for outcome in outcomes {
+    let mut line = format!(
+      "  {} {:width$} {:>7}  {}",
+      outcome.status.symbol(),
+      outcome.status.label(),
+      elapsed(outcome.duration),
+      outcome.step
+    );
+    if let Status::Skipped(reason) = &outcome.status {
+      line.push_str(&format!(
+        " {}",
+        OptionalStyled::new(format!("({reason})"), current_theme().dim_style.clone())
+      ));
+    }
+    lines.push(line);
+  }
+
+  let counts = Status::ORDER
+    .iter()
+    .filter_map(|label| {
+      let count = outcomes
+        .iter()
+        .filter(|outcome| outcome.status.label() == *label)
+        .count();
+      (count > 0).then(|| format!("{count} {label}"))
+    })
+    .collect::<Vec<_>>();
+  let started = outcomes
+    .iter()
+    .map(|outcome| outcome.started)
+    .min()
+    .unwrap_or_else(Instant::now);
+  let total = outcomes
+    .iter()
+    .map(|outcome| outcome.started + outcome.duration - started)
+    .max()
+    .unwrap_or_default();
+  let steps = if outcomes.len() == 1 { "step" } else { "steps" };
+  lines.push(format!(
+    "  {} {steps} in {}: {}",
+    outcomes.len(),
+    elapsed(total),
+    counts.join(", ")
+  ));
+  lines
+}
+
+/// A duration short enough to fit a column, such as `0.4s` or `2m 05s`.
+pub fn elapsed(duration: Duration) -> String {
+  let secs = duration.as_secs();
+  if secs < 60 {
+    format!("{:.1}s", duration.as_secs_f64())
+  } else if secs < 3600 {
+    format!("{}m {:02}s", secs / 60, secs % 60)
+  } else {
+    format!("{}h {:02}m", secs / 3600, secs % 3600 / 60)
+  }
+}
+
+fn print_retried() {
+  let retried = std::mem::take(&mut *RETRIED.lock().unwrap());
+  if retried.is_empty() {
+    return;
+  }
+
+  eprintln!();
+  eprintln!(
+    "{}",
+    OptionalStyled::new("Passed only after retrying:", current_theme().warning_style.clone())
+  );
+  for (step, attempts) in retried {
+    eprintln!(
+      "  {} {step} {}",
+      OptionalStyled::new("!", current_theme().warning_style.clone()),
+      OptionalStyled::new(format!("({attempts} attempts)"), current_theme().dim_style.clone())
+    );
+  }
+}
+
+fn print_failed() {
+  let failed = std::mem::take(&mut *FAILED.lock().unwrap());
+  if failed.is_empty() {
+    return;
+  }
+
+  eprintln!();
+  eprintln!(
+    "{}",
+    OptionalStyled::new("Failed:", current_theme().error_style.clone())
+  );
+  for failure in failed {
+    eprintln!(
+      "  {} {}",
+      OptionalStyled::new("✗", current_theme().error_style.clone()),
+      failure.step
+    );
+    if let Some(error) = failure.error {
+      for line in error.trim().lines() {
+        eprintln!("      {line}");
+      }
+    }
+    if let Some(remedy) = failure.remedy {
+      eprintln!(
+        "      {} {}",
+        OptionalStyled::new("Suggested remedy:", current_theme().text_style.clone()),
+        OptionalStyled::new(remedy, current_theme().warning_style.clone())
+      );
+    }
+  }
+}
@@ -0,0 +1,93 @@
+// @related [subject](medic-src/src/report/mod.rs)
+
+use super::{Outcome, Status, elapsed, summary};
+use std::sync::{LazyLock, Once};
+use std::time::{Duration, Instant};
+
+static INIT: Once = Once::new();
+pub fn initialize() {
+  INIT.call_once(|| {
+    if crate::theme::THEME.get().is_none() {
+      let theme = crate::theme::dark_theme();
+      crate::theme::set_theme(theme);
+    }
+  });
+}
+
+static START: LazyLock<Instant> = LazyLock::new(Instant::now);
+
+fn outcome(step: &str, status: Status, started: u64, millis: u64) -> Outcome {
+  Outcome {
+    duration: Duration::from_millis(millis),
+    started: *START + Duration::from_millis(started),
+    status,
+    step: step.into(),
+  }
+}
+
+fn plain(lines: Vec<String>) -> Vec<String> {
+  lines
+    .iter()
+    .map(|line| console::strip_ansi_codes(line).to_string())
+    .collect()
+}
+
+#[test]
+fn test_elapsed() {
+  assert_eq!(elapsed(Duration::from_millis(420)), "0.4s");
+  assert_eq!(elapsed(Duration::from_millis(12_340)), "12.3s");
+  assert_eq!(elapsed(Duration::from_secs(125)), "2m 05s");
+  assert_eq!(elapsed(Duration::from_secs(3_900)), "1h 05m");
+}
+
+#[test]
+fn test_summary_of_no_steps_is_empty() {
+  assert!(summary(&[]).is_empty());
+}
+
+#[test]
+fn test_summary_lists_each_step_with_totals() {
+  initialize();
+  let lines = plain(summary(&[
+    outcome("Compile", Status::Passed, 0, 1_200),
+    outcome("Lint", Status::Optional, 1_200, 300),
+    outcome("Brew", Status::Skipped("only runs on macos".into()), 1_500, 0),
+    outcome("Test", Status::Failed, 1_500, 4_500),
+    outcome("Format", Status::Passed, 6_000, 100),
+  ]));
+
+  assert_eq!(
+    lines,
+    vec![
+      "",
+      "Summary:",
+      "  ✓ passed              1.2s  Compile",
+      "  ! optional failure    0.3s  Lint",
+      "  … skipped             0.0s  Brew (only runs on macos)",
+      "  ✗ failed              4.5s  Test",
+      "  ✓ passed              0.1s  Format",
+      "  5 steps in 6.1s: 2 passed, 1 skipped, 1 optional failure, 1 failed",
+    ]
+  );
+}
+
+#[test]
+fn test_summary_of_one_step() {
+  initialize();
+  let lines = plain(summary(&[outcome("Compile", Status::Remedied, 0, 50)]));
+
+  assert_eq!(lines[2], "  ✓ remedied    0.1s  Compile");
+  assert_eq!(lines[3], "  1 step in 0.1s: 1 remedied");
+}
+
+#[test]
+fn test_summary_totals_time_from_first_start_to_last_finish() {
+  initialize();
+  let lines = plain(summary(&[
+    outcome("Unit tests", Status::Passed, 100, 2_000),
+    outcome("Lint", Status::Passed, 100, 500),
+    outcome("Browser tests", Status::Passed, 600, 1_000),
+  ]));
+
+  assert_eq!(lines[5], "  3 steps in 2.0s: 3 passed");
+}
@@ -0,0 +1,99 @@
+// @related [tests](medic-src/src/retry/retry_test.rs)
+
+#[cfg(test)]
+mod retry_test;
+
+use crate::error::MedicError;
+use crate::interrupt;
+use crate::optional_styled::OptionalStyled;
+use crate::report;
+use crate::theme::current_theme;
+use crate::util::HumanDuration;
+
+use retrogress::ProgressBar;
+use retrogress::progress::Ref;
+use std::process::Output;
+use std::time::Duration;
+
+const DEFAULT_DELAY: Duration = Duration::from_secs(1);
+
+// With backoff, delays stop doubling once they reach this, unless
+// `retry_delay` is longer still.
+const MAX_BACKOFF_DELAY: Duration = Duration::from_secs(60);
+
+/// How often a failing command is run again before its step fails.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Retry {
+  pub backoff: bool,
+  pub delay: Duration,
+  pub retries: u32,
+}
+
+impl Retry {
+  pub fn new(retries: u32, delay: Option<HumanDuration>, backoff: bool) -> Self {
+    Self {
+      backoff,
+      delay: delay.map_or(DEFAULT_DELAY, Duration::from),
+      retries,
+    }
+  }
+
+  /// The time to wait before each retry, doubling every time with `backoff`
+  /// up to a minute.
+  pub fn delays(&self) -> impl Iterator<Item = Duration> + '_ {
+    (0..self.retries).map(|retry| match self.backoff {
+      true => self
+        .delay
+        .saturating_mul(2u32.saturating_pow(retry))
+        .min(MAX_BACKOFF_DELAY.max(self.delay)),
+      false => self.delay,
+    })
+  }
+
+  /// Runs `attempt` until its command succeeds or every retry has failed,
+  /// returning the output of the last attempt. While retrying, the progress
+  /// bar `pb` of the step called `name` shows the attempt being made. Medic
+  /// being interrupted ends the wait for the next attempt.
+  pub fn run(
+    &self,
+    progress: &mut ProgressBar,
+    pb: Ref,
+    name: &str,
+    mut attempt: impl FnMut(&mut ProgressBar) -> Result<Output, MedicError>,
+  ) -> Result<Output, MedicError> {
+    let mut delays = self.delays();
+    let mut number = 1;
+
+    loop {
+      let result = attempt(progress);
+      let succeeded = matches!(&result, Ok(output) if output.status.success());
+      let delay = if succeeded || interrupt::interrupted() {
+        None
+      } else {
+        delays.next()
+      };
+
+      let Some(delay) = delay else {
+        if succeeded && number > 1 {
+          report::passed_after_retry(name, number);
+        }
+        return result;
+      };
+
+      number += 1;
+      progress.set_message(
+        pb,
+        format!(
+          "{name} {}",
+          OptionalStyled::new(
+            format!("(attempt {number} of {})", self.retries + 1),
+            current_theme().dim_style.clone()
+          )
+        ),
+      );
+      if !interrupt::sleep(delay) {
+        return result;
+      }
+    }
+  }
+}
@@ -0,0 +1,91 @@
+// @related [subject](medic-src/src/retry/mod.rs)
+
+use super::Retry;
+use crate::cli::Flags;
+use crate::progress;
+use std::process::Command;
+use std::sync::Once;
+use std::time::Duration;
+
+static INIT: Once = Once::new();
+pub fn initialize() {
+  INIT.call_once(|| {
+    if crate::theme::THEME.get().is_none() {
+      let theme = crate::theme::dark_theme();
+      crate::theme::set_theme(theme);
+    }
+  });
+}
+
+#[test]
+fn test_new() {
+  assert_eq!(
+    Retry::new(2, None, false),
+    Retry {
+      backoff: false,
+      delay: Duration::from_secs(1),
+      retries: 2,
+    }
+  );
+  assert_eq!(
+    Retry::new(1, Some("250ms".parse().unwrap()), true).delay,
+    Duration::from_millis(250)
+  );
+}
+
+#[test]
+fn test_delays() {
+  let delays: Vec<Duration> = Retry::new(3, Some("2s".parse().unwrap()), false)
+    .delays()
+    .collect();
+  assert_eq!(delays, vec![Duration::from_secs(2); 3]);
+
+  let delays: Vec<Duration> = Retry::new(3, Some("2s".parse().unwrap()), true)
+    .delays()
+    .collect();
+  assert_eq!(
+    delays,
+    vec![Duration::from_secs(2), Duration::from_secs(4), Duration::from_secs(8)]
+  );
+
+  let delays: Vec<Duration> = Retry::new(8, Some("20s".parse().unwrap()), true)
+    .delays()
+    .collect();
+  assert_eq!(delays[1], Duration::from_secs(40));
+  assert_eq!(delays[2..], [Duration::from_secs(60); 6]);
+
+  let delays: Vec<Duration> = Retry::new(2, Some("2m".parse().unwrap()), true)
+    .delays()
+    .collect();
+  assert_eq!(delays, vec![Duration::from_secs(120); 2]);
+}
+
+fn run(retry: Retry, failures: usize) -> (bool, usize) {
+  initialize();
+  let mut progress = progress::new(&Flags::default());
+  let pb = progress.append("Flaky");
+  let mut attempts = 0;
+
+  let output = retry.run(&mut progress, pb, "Flaky", |_| {
+    attempts += 1;
+    let shell = if attempts > failures { "true" } else { "false" };
+    Ok(Command::new("sh").arg("-c").arg(shell).output()?)
+  });
+  (output.unwrap().status.success(), attempts)
+}
+
+#[test]
+fn test_run_retries_until_success() {
+  let retry = Retry::new(3, Some("1ms".parse().unwrap()), true);
+
+  assert_eq!(run(retry, 0), (true, 1));
+  assert_eq!(run(retry, 2), (true, 3));
+}
+
+#[test]
+fn test_run_stops_when_retries_are_exhausted() {
+  let retry = Retry::new(2, Some("1ms".parse().unwrap()), false);
+
+  assert_eq!(run(retry, 5), (false, 3));
+  assert_eq!(run(Retry::new(0, None, false), 5), (false, 1));
+}
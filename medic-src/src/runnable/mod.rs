@@ -1,48 +1,107 @@
 use crate::AppResult;
+use crate::cache::Fingerprint;
 use crate::cli::Flags;
+use crate::condition::Condition;
 use crate::context::Context;
+use crate::dry_run;
 use crate::error::MedicError;
+use crate::extra;
+use crate::filter;
+use crate::history;
+use crate::interrupt;
 use crate::optional_styled::OptionalStyled;
 use crate::recoverable::{Recoverable, Remedy};
+use crate::report::{self, Status};
 use crate::theme::current_theme;
 use arboard::Clipboard;
 use console::Term;
 use retrogress::ProgressBar;
 use std::io::{BufRead, BufReader};
 use std::process::Stdio;
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::sync::{LazyLock, Mutex};
 use std::thread;
+use std::time::Instant;
 
 // In auto-apply mode, medic should exit when a check fails after the remedy
 // has been applied.
 static RERUN: LazyLock<Mutex<bool>> = LazyLock::new(|| Mutex::new(false));
 
-pub trait Runnable: std::fmt::Display + Clone {
+pub trait Runnable: std::fmt::Debug + std::fmt::Display + Clone {
   fn allow_failure(&self) -> bool {
     false
   }
 
+  fn condition(&self) -> Condition<'_> {
+    Condition::default()
+  }
+
+  fn depends_on(&self) -> &[String] {
+    &[]
+  }
+
+  fn id(&self) -> Option<&str> {
+    None
+  }
+
+  /// Files whose contents, along with the step's definition, decide whether
+  /// the step is cached.
+  fn inputs(&self) -> &[String] {
+    &[]
+  }
+
+  /// The name matched by `--only name:<glob>` and `--skip name:<glob>`.
+  /// Steps which only run other steps have none.
+  fn name(&self) -> Option<&str> {
+    None
+  }
+
   fn platform(&self) -> &Option<Vec<String>> {
     &None
   }
 
   fn run(&self, progress: &mut ProgressBar, flags: &mut Flags, context: &Context) -> Recoverable<()>;
+
+  fn tags(&self) -> &[String] {
+    &[]
+  }
+
   fn to_command(&self) -> Result<std::process::Command, MedicError>;
   fn verbose(&self) -> bool {
     false
   }
 }
 
+/// Runs `runnable`, applying or asking about its remedy when it fails, and
+/// records how it ended for the summary printed at the end of the run.
 pub fn run(runnable: impl Runnable, progress: &mut ProgressBar, flags: &mut Flags, context: &Context) -> AppResult<()> {
-  if !context.matches_platform(runnable.platform()) {
-    progress.print_inline(&format!(
-      "{} {runnable} {}",
-      OptionalStyled::new("…", current_theme().warning_style.clone()),
-      OptionalStyled::new("(skipped)", current_theme().warning_style.clone())
-    ));
-    return AppResult::Ok(());
+  run_with_status(runnable, progress, flags, context).0
+}
+
+/// Runs `runnable` as `run` does, also returning how it ended, or `None`
+/// when it was filtered out or only printed by `--dry-run`.
+pub fn run_with_status(
+  runnable: impl Runnable,
+  progress: &mut ProgressBar,
+  flags: &mut Flags,
+  context: &Context,
+) -> (AppResult<()>, Option<Status>) {
+  let started = Instant::now();
+  let mut status = None;
+  let result = run_and_remedy(runnable.clone(), progress, flags, context, &mut status);
+  if let Some(status) = &status {
+    finished(&runnable, status.clone(), started, progress, flags);
   }
+  (result, status)
+}
 
+fn run_and_remedy(
+  runnable: impl Runnable,
+  progress: &mut ProgressBar,
+  flags: &mut Flags,
+  context: &Context,
+  status: &mut Option<Status>,
+) -> AppResult<()> {
   if flags.auto_apply_remedy {
     unsafe {
       std::env::set_var("MEDIC_APPLY_REMEDIES", "true");
@@ -54,18 +113,36 @@ pub fn run(runnable: impl Runnable, progress: &mut ProgressBar, flags: &mut Flag
     }
   }
 
-  match runnable.run(progress, flags, context) {
+  let result = match attempt(&runnable, progress, flags, context) {
+    Attempt::Cached => {
+      *status = Some(Status::Cached);
+      Recoverable::Ok(())
+    }
+    Attempt::Filtered | Attempt::Printed => return AppResult::Ok(()),
+    Attempt::Ran(result) => result,
+    Attempt::Skipped(reason) => {
+      *status = Some(Status::Skipped(reason));
+      Recoverable::Ok(())
+    }
+  };
+  if interrupt::interrupted() {
+    *status = Some(Status::Interrupted);
+    return AppResult::Quit;
+  }
+
+  match result {
     Recoverable::Ok(ok) => {
       let mut rerun = LazyLock::force(&RERUN).lock().unwrap();
+      status.get_or_insert(if *rerun { Status::Remedied } else { Status::Passed });
       *rerun = false;
       AppResult::Ok(ok)
     }
     Recoverable::Err(err, None) => {
       if flags.interactive && flags.recoverable && !flags.auto_apply_remedy {
         eprintln!();
-        ask(&runnable, None, progress, AppResult::Err(err), flags, context)
+        ask(&runnable, None, progress, AppResult::Err(err), flags, context, status)
       } else {
-        AppResult::Err(err)
+        fail(&runnable, err, None, flags, status)
       }
     }
     Recoverable::Err(err, Some(remedy)) => {
@@ -83,7 +160,7 @@ pub fn run(runnable: impl Runnable, progress: &mut ProgressBar, flags: &mut Flag
               current_theme().error_style.clone()
             )
           );
-          return AppResult::Err(err);
+          return fail(&runnable, err, Some(&remedy), flags, status);
         }
         eprintln!(
           "— {} —",
@@ -94,7 +171,7 @@ pub fn run(runnable: impl Runnable, progress: &mut ProgressBar, flags: &mut Flag
         let mut rerun = LazyLock::force(&RERUN).lock().unwrap();
         *rerun = true;
         drop(rerun);
-        return run(runnable.clone(), progress, flags, context);
+        return run_and_remedy(runnable, progress, flags, context, status);
       }
 
       eprint!(
@@ -103,23 +180,36 @@ pub fn run(runnable: impl Runnable, progress: &mut ProgressBar, flags: &mut Flag
         OptionalStyled::new(remedy.to_string(), current_theme().warning_style.clone()),
       );
 
-      let _ = Clipboard::new()
+      let copied = Clipboard::new()
         .and_then(|mut clipboard| clipboard.set_text(remedy.to_string()))
         .map(|_| {
           eprintln!(
             "  {}",
             OptionalStyled::new("(it's in the clipboard)", current_theme().dim_style.clone()),
           );
-        });
+        })
+        .is_ok();
 
       if flags.interactive {
         eprintln!();
-        ask(&runnable, Some(remedy), progress, AppResult::Err(err), flags, context)
+        ask(
+          &runnable,
+          Some(remedy),
+          progress,
+          AppResult::Err(err),
+          flags,
+          context,
+          status,
+        )
       } else {
-        AppResult::Err(err)
+        if flags.keep_going && !copied {
+          eprintln!();
+        }
+        fail(&runnable, err, Some(&remedy), flags, status)
       }
     }
     Recoverable::Manual(_err, remedy) => {
+      *status = Some(Status::Manual);
       let remedy = remedy.unwrap().to_string();
       eprint!(
         "{} {}",
@@ -136,8 +226,12 @@ pub fn run(runnable: impl Runnable, progress: &mut ProgressBar, flags: &mut Flag
         });
       AppResult::Quit
     }
-    Recoverable::Nonrecoverable(err) => AppResult::Err(Some(err)),
+    Recoverable::Nonrecoverable(err) => {
+      *status = Some(Status::Failed);
+      AppResult::Err(Some(err))
+    }
     Recoverable::Optional(ok, None) => {
+      *status = Some(Status::Optional);
       eprintln!();
       eprintln!(
         "{}",
@@ -150,8 +244,17 @@ pub fn run(runnable: impl Runnable, progress: &mut ProgressBar, flags: &mut Flag
     Recoverable::Optional(ok, Some(remedy)) => {
       if flags.interactive {
         eprintln!();
-        ask(&runnable, Some(remedy), progress, AppResult::Ok(ok), flags, context)
+        ask(
+          &runnable,
+          Some(remedy),
+          progress,
+          AppResult::Ok(ok),
+          flags,
+          context,
+          status,
+        )
       } else {
+        *status = Some(Status::Optional);
         let mut rerun = LazyLock::force(&RERUN).lock().unwrap();
         *rerun = false;
         AppResult::Ok(ok)
@@ -161,6 +264,215 @@ pub fn run(runnable: impl Runnable, progress: &mut ProgressBar, flags: &mut Flag
   }
 }
 
+// With `--keep-going`, failures are saved to be reported at the end of the
+// run, and the steps after them still run. Steps which only run other steps
+// have had the failures inside them saved already.
+fn fail(
+  runnable: &impl Runnable,
+  err: Option<MedicError>,
+  remedy: Option<&Remedy>,
+  flags: &Flags,
+  status: &mut Option<Status>,
+) -> AppResult<()> {
+  *status = Some(Status::Failed);
+  if !flags.keep_going {
+    return AppResult::Err(err);
+  }
+  if runnable.name().is_some() {
+    report::failed(&runnable.to_string(), err.as_ref(), remedy);
+  }
+  *LazyLock::force(&RERUN).lock().unwrap() = false;
+  AppResult::Ok(())
+}
+
+/// Runs `runnable` unless it is skipped, or cached because its inputs have
+/// not changed since it last succeeded. With `--dry-run`, steps are printed
+/// instead, while steps which run other steps still run so that the steps
+/// inside them are printed. How the step ended is recorded for the summary,
+/// as remedies are left to the list of steps it is in.
+pub fn run_unless_skipped(
+  runnable: &impl Runnable,
+  progress: &mut ProgressBar,
+  flags: &mut Flags,
+  context: &Context,
+) -> Recoverable<()> {
+  run_unless_skipped_with_status(runnable, progress, flags, context).0
+}
+
+/// Runs `runnable` as `run_unless_skipped` does, also returning how it
+/// ended, or `None` when it was filtered out or only printed by `--dry-run`.
+pub fn run_unless_skipped_with_status(
+  runnable: &impl Runnable,
+  progress: &mut ProgressBar,
+  flags: &mut Flags,
+  context: &Context,
+) -> (Recoverable<()>, Option<Status>) {
+  let started = Instant::now();
+  let (result, status) = match attempt(runnable, progress, flags, context) {
+    Attempt::Cached => (Recoverable::Ok(()), Some(Status::Cached)),
+    Attempt::Filtered | Attempt::Printed => (Recoverable::Ok(()), None),
+    Attempt::Skipped(reason) => (Recoverable::Ok(()), Some(Status::Skipped(reason))),
+    Attempt::Ran(result) => {
+      let status = match &result {
+        _ if interrupt::interrupted() => Status::Interrupted,
+        Recoverable::Ok(_) => Status::Passed,
+        Recoverable::Manual(..) => Status::Manual,
+        Recoverable::Optional(..) => Status::Optional,
+        Recoverable::Err(..) | Recoverable::Nonrecoverable(_) => Status::Failed,
+        Recoverable::Quit => Status::Interrupted,
+      };
+      (result, Some(status))
+    }
+  };
+  if let Some(status) = &status {
+    finished(runnable, status.clone(), started, progress, flags);
+  }
+  (result, status)
+}
+
+/// Whether the steps which depend on `runnable` may run once it ended with
+/// `status`: when it passed, or was cached, but not when it was skipped,
+/// filtered out or failed. With `--dry-run`, steps which would have run
+/// count as passed.
+pub fn passed(runnable: &impl Runnable, status: Option<&Status>, flags: &Flags) -> bool {
+  match status {
+    Some(status) => matches!(status, Status::Cached | Status::Passed | Status::Remedied),
+    None => flags.dry_run && filter::selects(runnable, &flags.only, &flags.skip),
+  }
+}
+
+// Records how a step ended for the summary and, when it passed, how long it
+// took for its history, warning when it took much longer than it usually
+// does. Steps which only run other steps are left to the steps inside them.
+fn finished(runnable: &impl Runnable, status: Status, started: Instant, progress: &mut ProgressBar, flags: &Flags) {
+  if runnable.name().is_none() || flags.dry_run {
+    return;
+  }
+  let step = runnable.to_string();
+  let duration = started.elapsed();
+  let passed = status == Status::Passed;
+  report::finished(&step, status, started);
+
+  if let Some(usual) = passed.then(|| history::passed(&step, duration)).flatten() {
+    progress.print_inline(&format!(
+      "{} {step} {}",
+      OptionalStyled::new("!", current_theme().warning_style.clone()),
+      OptionalStyled::new(
+        format!(
+          "(took {}; it usually takes {})",
+          report::elapsed(duration),
+          report::elapsed(usual)
+        ),
+        current_theme().warning_style.clone()
+      )
+    ));
+  }
+}
+
+// What came of trying to run a step.
+enum Attempt {
+  Cached,
+  Filtered,
+  Printed,
+  Ran(Recoverable<()>),
+  Skipped(String),
+}
+
+fn attempt(runnable: &impl Runnable, progress: &mut ProgressBar, flags: &mut Flags, context: &Context) -> Attempt {
+  if let Some(skipped) = skip(runnable, progress, flags, context) {
+    return skipped;
+  }
+
+  let fingerprint = Fingerprint::of(runnable, flags);
+  if fingerprint.as_ref().is_some_and(Fingerprint::is_cached) {
+    progress.print_inline(&format!(
+      "{} {runnable} {}",
+      OptionalStyled::new("✓", current_theme().success_style.clone()),
+      OptionalStyled::new("(cached)", current_theme().dim_style.clone())
+    ));
+    return Attempt::Cached;
+  }
+
+  if flags.dry_run && runnable.name().is_some() {
+    dry_run::print(runnable, progress);
+    return Attempt::Printed;
+  }
+
+  let result = {
+    let _step = runnable
+      .name()
+      .is_some()
+      .then(|| interrupt::running(&runnable.to_string()));
+    let _slow = runnable
+      .name()
+      .and_then(|_| warn_when_slow(runnable, progress));
+    runnable.run(progress, flags, context)
+  };
+  if let (Recoverable::Ok(_), Some(fingerprint)) = (&result, fingerprint) {
+    fingerprint.save();
+  }
+  Attempt::Ran(result)
+}
+
+// Warns that `runnable` is taking much longer than it usually does once it
+// has, while it is still running. The warning is called off when the
+// returned sender is dropped.
+fn warn_when_slow(runnable: &impl Runnable, progress: &ProgressBar) -> Option<mpsc::Sender<()>> {
+  let step = runnable.to_string();
+  let usual = history::usual(&step)?;
+  let (running, finished) = mpsc::channel();
+  let mut progress = progress.clone();
+
+  thread::spawn(move || {
+    if finished.recv_timeout(history::slow_after(usual)) == Err(RecvTimeoutError::Timeout) {
+      progress.print_inline(&format!(
+        "{} {step} {}",
+        OptionalStyled::new("!", current_theme().warning_style.clone()),
+        OptionalStyled::new(
+          format!("(still running; it usually takes {})", report::elapsed(usual)),
+          current_theme().warning_style.clone()
+        )
+      ));
+    }
+  });
+  Some(running)
+}
+
+/// Whether `runnable` should be skipped, either because it is filtered out
+/// by `--only` or `--skip`, or because it does not run on this platform or
+/// its conditions do not hold. The reason for skipping is printed unless the
+/// step was filtered out.
+fn skip(runnable: &impl Runnable, progress: &mut ProgressBar, flags: &Flags, context: &Context) -> Option<Attempt> {
+  if !filter::selects(runnable, &flags.only, &flags.skip) {
+    return Some(Attempt::Filtered);
+  }
+
+  // Commands in conditions are not run by `--dry-run`, which shows them
+  // instead.
+  let condition = runnable.condition();
+  let skip_reason = context
+    .platform_skip_reason(runnable.platform())
+    .or_else(|| condition.env_skip_reason())
+    .or_else(|| {
+      (!flags.dry_run)
+        .then(|| condition.command_skip_reason())
+        .flatten()
+    });
+  skip_reason.map(|reason| {
+    print_skipped(runnable, &reason, progress);
+    Attempt::Skipped(reason)
+  })
+}
+
+/// Prints that `runnable` was skipped, and why.
+pub fn print_skipped(runnable: &impl Runnable, reason: &str, progress: &mut ProgressBar) {
+  progress.print_inline(&format!(
+    "{} {runnable} {}",
+    OptionalStyled::new("…", current_theme().warning_style.clone()),
+    OptionalStyled::new(format!("(skipped: {reason})"), current_theme().warning_style.clone())
+  ));
+}
+
 fn ask<R: Runnable>(
   runnable: &R,
   remedy: Option<Remedy>,
@@ -168,6 +480,7 @@ fn ask<R: Runnable>(
   default_exit: AppResult<()>,
   flags: &mut Flags,
   context: &Context,
+  status: &mut Option<Status>,
 ) -> AppResult<()> {
   match prompt(&remedy, &default_exit, progress) {
     PromptResult::Help => {
@@ -184,35 +497,42 @@ fn ask<R: Runnable>(
 
       msg.sort();
       eprintln!("\n{}", msg.join("\n"));
-      ask(runnable, remedy, progress, default_exit, flags, context)
+      ask(runnable, remedy, progress, default_exit, flags, context, status)
     }
     PromptResult::All => {
       if let Some(rem) = remedy {
         flags.auto_apply_remedy = true;
         run_remedy(rem, progress)?;
-        run(runnable.clone(), progress, flags, context)
+        run_and_remedy(runnable.clone(), progress, flags, context, status)
       } else {
-        ask(runnable, remedy, progress, default_exit, flags, context)
+        ask(runnable, remedy, progress, default_exit, flags, context, status)
       }
     }
-    PromptResult::No => default_exit,
+    PromptResult::No => {
+      *status = Some(match default_exit {
+        AppResult::Ok(_) => Status::Optional,
+        _ => Status::Failed,
+      });
+      default_exit
+    }
     PromptResult::Quit => AppResult::Quit,
-    PromptResult::Rerun => run(runnable.clone(), progress, flags, context),
+    PromptResult::Rerun => run_and_remedy(runnable.clone(), progress, flags, context, status),
     PromptResult::Skip => {
+      *status = Some(Status::Skipped("at the prompt".into()));
       let mut rerun = LazyLock::force(&RERUN).lock().unwrap();
       *rerun = false;
       AppResult::Ok(())
     }
-    PromptResult::Unknown => ask(runnable, remedy, progress, default_exit, flags, context),
+    PromptResult::Unknown => ask(runnable, remedy, progress, default_exit, flags, context, status),
     PromptResult::Yes => {
       if let Some(rem) = remedy {
         run_remedy(rem, progress)?;
         let mut rerun = LazyLock::force(&RERUN).lock().unwrap();
         *rerun = true;
         drop(rerun);
-        run(runnable.clone(), progress, flags, context)
+        run_and_remedy(runnable.clone(), progress, flags, context, status)
       } else {
-        ask(runnable, remedy, progress, default_exit, flags, context)
+        ask(runnable, remedy, progress, default_exit, flags, context, status)
       }
     }
     PromptResult::Err(e) => {
@@ -273,7 +593,8 @@ fn run_remedy(remedy: Remedy, progress: &mut ProgressBar) -> AppResult<()> {
     .stderr(Stdio::piped());
 
   let pb = progress.append(&remedy.to_string());
-  let mut child = command.spawn()?;
+  let _remedy = interrupt::running(&remedy.to_string());
+  let mut child = extra::command::spawn(&mut command, None, false)?;
   let stderr = child
     .stderr
     .take()
@@ -301,15 +622,20 @@ fn run_remedy(remedy: Remedy, progress: &mut ProgressBar) -> AppResult<()> {
       .for_each(|line| err_progress.println(pb, &line));
   });
 
-  let res = child.wait_with_output();
+  let res = extra::command::wait(child, None);
   out_thr.join().unwrap();
   err_thr.join().unwrap();
 
   match res {
-    Ok(_) => progress.succeeded(pb),
-    Err(_) => progress.failed(pb),
+    Ok(_) => {
+      progress.succeeded(pb);
+      AppResult::Ok(())
+    }
+    Err(err) => {
+      progress.failed(pb);
+      AppResult::Err(Some(err))
+    }
   }
-  res.into()
 }
 
 enum PromptResult {
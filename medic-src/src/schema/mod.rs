@@ -0,0 +1,149 @@
+// @related [tests](medic-src/src/schema/schema_test.rs)
+
+#[cfg(test)]
+mod schema_test;
+
+use crate::config::Manifest;
+use crate::error::MedicError;
+use crate::shell::ShellConfig;
+use crate::step::StepConfig;
+use crate::util::StringOrList;
+use crate::{AppResult, Check, OutdatedCheck};
+
+use schemars::generate::SchemaSettings;
+use schemars::transform::RecursiveTransform;
+use schemars::{JsonSchema, Schema};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::io::{ErrorKind, Write};
+
+// The file layout of a manifest. `include` and `definitions` are resolved by
+// the loader before deserializing a `Manifest`, so they only appear here.
+#[derive(JsonSchema)]
+#[schemars(
+  title = "medic manifest",
+  description = "Checks and steps run by medic, usually found at `.config/medic.toml`."
+)]
+#[allow(dead_code)]
+struct ManifestFile {
+  /// Other manifest files to merge into this one, relative to this file.
+  /// Glob patterns are expanded in sorted order.
+  include: Option<StringOrList>,
+  /// Named steps which may be referenced from any list of steps with
+  /// `{ use = "name" }`.
+  definitions: Option<BTreeMap<String, Definition>>,
+  #[schemars(flatten)]
+  manifest: Manifest,
+}
+
+#[derive(JsonSchema)]
+#[schemars(untagged)]
+#[allow(dead_code)]
+enum Definition {
+  Check(Check),
+  Shell(ShellConfig),
+  Step(StepConfig),
+  Outdated(OutdatedCheck),
+  Use(UseStep),
+}
+
+/// A step from `[definitions]`. Any other keys override those of the
+/// definition, except `env`, which is merged.
+#[derive(JsonSchema)]
+#[schemars(extend("additionalProperties" = true))]
+#[allow(dead_code)]
+struct UseStep {
+  /// The name of the definition.
+  #[schemars(rename = "use")]
+  name: String,
+}
+
+// Lists of steps whose items may `use` a definition.
+const STEP_LISTS: [&str; 4] = ["AuditStep", "DoctorStep", "ShipitStep", "Step"];
+
+/// Prints the JSON Schema of the medic manifest to STDOUT.
+pub fn run() -> AppResult<()> {
+  let schema = serde_json::to_string_pretty(&generate())
+    .map_err(|err| MedicError::from(format!("Unable to generate JSON Schema: {err}")))?;
+  match writeln!(std::io::stdout(), "{schema}") {
+    Err(err) if err.kind() != ErrorKind::BrokenPipe => AppResult::Err(Some(err.into())),
+    _ => AppResult::Ok(()),
+  }
+}
+
+/// Generates a JSON Schema for the medic manifest from the types used to
+/// deserialize it.
+pub fn generate() -> Schema {
+  let mut schema = SchemaSettings::draft07()
+    .with_transform(RecursiveTransform(remove_null))
+    .with_transform(RecursiveTransform(deny_unknown_keys))
+    .into_generator()
+    .into_root_schema_for::<ManifestFile>();
+  allow_use(&mut schema);
+  schema
+}
+
+// Definitions are expanded before steps are deserialized, so the step types
+// know nothing of `use`.
+fn allow_use(schema: &mut Schema) {
+  let use_step = json!({ "$ref": "#/definitions/UseStep" });
+  let Some(Value::Object(definitions)) = schema.get_mut("definitions") else {
+    return;
+  };
+
+  for name in STEP_LISTS {
+    if let Some(Value::Array(variants)) = definitions
+      .get_mut(name)
+      .and_then(|list| list.get_mut("anyOf"))
+    {
+      variants.push(use_step.clone());
+    }
+  }
+
+  if let Some(items) = definitions
+    .get_mut("OutdatedConfig")
+    .and_then(|config| config.pointer_mut("/properties/checks/items"))
+  {
+    *items = json!({ "anyOf": [items.clone(), use_step] });
+  }
+}
+
+// Medic ignores keys it does not know, which hides typos; the schema flags
+// them instead, as `medic validate` does.
+fn deny_unknown_keys(schema: &mut Schema) {
+  if schema.get("properties").is_some() && schema.get("additionalProperties").is_none() {
+    schema.insert("additionalProperties".into(), false.into());
+  }
+}
+
+// TOML has no null, so optional fields are expressed only by leaving them
+// out of `required`.
+fn remove_null(schema: &mut Schema) {
+  if let Some(Value::Array(types)) = schema.get_mut("type") {
+    types.retain(|t| t != "null");
+    if types.len() == 1 {
+      let only = types.remove(0);
+      schema.insert("type".into(), only);
+    }
+  }
+
+  if let Some(Value::Array(variants)) = schema.get_mut("anyOf") {
+    variants.retain(|variant| variant.get("type").is_none_or(|t| t != "null"));
+    // Draft 7 ignores keywords alongside `$ref`, so a lone reference is kept
+    // in `allOf` with the field's description beside it.
+    if variants.len() == 1 {
+      let only = variants.remove(0);
+      schema.remove("anyOf");
+      match only {
+        Value::Object(only) if !only.contains_key("$ref") => {
+          for (key, value) in only {
+            schema.insert(key, value);
+          }
+        }
+        only => {
+          schema.insert("allOf".into(), Value::Array(vec![only]));
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,127 @@
+// @related [subject](medic-src/src/schema/mod.rs)
+
+use super::*;
+use crate::shell::ShellConfig;
+use crate::step::StepConfig;
+use crate::validate::fields;
+use crate::{Check, OutdatedCheck};
+
+use serde::de::DeserializeOwned;
+use serde_json::json;
+
+fn definition(name: &str) -> Value {
+  generate()
+    .get("definitions")
+    .and_then(|definitions| definitions.get(name))
+    .cloned()
+    .unwrap_or_else(|| panic!("missing definition {name}"))
+}
+
+fn assert_matches_deserializer<T: DeserializeOwned>(name: &str) {
+  let definition = definition(name);
+
+  let mut properties: Vec<&str> = definition["properties"]
+    .as_object()
+    .unwrap()
+    .keys()
+    .map(String::as_str)
+    .collect();
+  properties.sort();
+  let mut names = fields::names::<T>().to_vec();
+  names.sort();
+  assert_eq!(properties, names, "properties of {name}");
+
+  let mut required: Vec<&str> = definition["required"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|field| field.as_str().unwrap())
+    .collect();
+  required.sort();
+  let mut expected = fields::required::<T>();
+  expected.sort();
+  assert_eq!(required, expected, "required fields of {name}");
+}
+
+#[test]
+fn schema_step_types_match_deserializers() {
+  assert_matches_deserializer::<Check>("Check");
+  assert_matches_deserializer::<ShellConfig>("ShellConfig");
+  assert_matches_deserializer::<StepConfig>("StepConfig");
+  assert_matches_deserializer::<OutdatedCheck>("OutdatedCheck");
+}
+
+#[test]
+fn schema_manifest_sections() {
+  let schema = generate().to_value();
+  let mut sections: Vec<&String> = schema["properties"].as_object().unwrap().keys().collect();
+  sections.sort();
+
+  assert_eq!(
+    sections,
+    vec![
+      "audit",
+      "definitions",
+      "doctor",
+      "include",
+      "outdated",
+      "shipit",
+      "test",
+      "update",
+      "vars",
+      "workflows"
+    ]
+  );
+  assert_eq!(schema["additionalProperties"], json!(false));
+}
+
+#[test]
+fn schema_nested_steps() {
+  assert_eq!(
+    definition("Step")["anyOf"],
+    json!([
+      { "$ref": "#/definitions/Check" },
+      { "$ref": "#/definitions/ShellConfig" },
+      { "$ref": "#/definitions/StepConfig" },
+      { "$ref": "#/definitions/NestedDoctor" },
+      { "$ref": "#/definitions/NestedSteps" },
+      { "type": "array", "items": { "$ref": "#/definitions/Step" } },
+      { "$ref": "#/definitions/UseStep" },
+    ])
+  );
+  assert_eq!(
+    definition("ShipitStep")["anyOf"],
+    json!([
+      { "$ref": "#/definitions/Check" },
+      { "$ref": "#/definitions/ShellConfig" },
+      { "$ref": "#/definitions/StepConfig" },
+      { "$ref": "#/definitions/ShipitAudit" },
+      { "$ref": "#/definitions/ShipitTest" },
+      { "$ref": "#/definitions/ShipitUpdate" },
+      { "$ref": "#/definitions/UseStep" },
+    ])
+  );
+}
+
+#[test]
+fn schema_steps_may_use_definitions() {
+  assert_eq!(definition("UseStep")["required"], json!(["use"]));
+  assert_eq!(definition("UseStep")["additionalProperties"], json!(true));
+  assert_eq!(
+    definition("OutdatedConfig")["properties"]["checks"]["items"],
+    json!({ "anyOf": [
+      { "$ref": "#/definitions/OutdatedCheck" },
+      { "$ref": "#/definitions/UseStep" },
+    ] })
+  );
+}
+
+#[test]
+fn schema_optional_fields_are_not_nullable() {
+  let check = definition("Check");
+
+  assert_eq!(check["properties"]["cd"]["type"], json!("string"));
+  assert_eq!(check["properties"]["output"]["default"], json!("json"));
+  assert_eq!(check["additionalProperties"], json!(false));
+  assert!(!generate().as_value().to_string().contains("null"));
+}
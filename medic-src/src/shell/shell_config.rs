@@ -1,36 +1,81 @@
 // @related [test](medic-src/src/shell/shell_config_test.rs)
 
 use crate::cli::Flags;
+use crate::condition::{Condition, EnvCondition};
 use crate::context::Context;
 use crate::error::MedicError;
 use crate::optional_styled::OptionalStyled;
 use crate::recoverable::{Recoverable, Remedy};
+use crate::retry::Retry;
 use crate::runnable::Runnable;
 use crate::theme::current_theme;
+use crate::util::HumanDuration;
 use crate::{extra, std_to_string};
 
+use schemars::JsonSchema;
 use serde::Deserialize;
 use std::collections::BTreeMap;
 use std::fmt;
 use std::io::{BufRead, BufReader};
 use std::process::{Command, Stdio};
 use std::thread;
+use std::time::Duration;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq)]
 pub struct ShellConfig {
+  /// Continue even if the command fails.
   #[serde(default)]
   pub allow_failure: bool,
+  /// Directory to change into before running.
   pub cd: Option<String>,
+  /// Ids of steps in the same list which must succeed before this one runs.
+  /// When one of them fails or is skipped, this step is skipped too.
+  #[serde(default)]
+  pub depends_on: Vec<String>,
+  /// Environment variables to set when running.
   #[serde(default)]
   pub env: BTreeMap<String, String>,
+  /// Identifies the step in the `depends_on` of other steps in the same list.
+  pub id: Option<String>,
+  /// A shell command which must succeed for this to run, for example `"test -f package.json"`.
+  pub r#if: Option<String>,
+  /// Environment variables which must be set, or a table of values they must have, for this to run.
+  pub if_env: Option<EnvCondition>,
+  /// Files, or glob patterns, whose contents decide whether the step is cached. Relative to the project directory.
+  #[serde(default)]
+  pub inputs: Vec<String>,
+  /// Print output directly to the terminal without progress bars. Takes priority over `verbose`.
   #[serde(default)]
   pub inline: bool,
+  /// Print the remedy and quit instead of applying it.
   #[serde(default)]
   pub manual: bool,
+  /// Description shown when running.
   pub name: String,
+  /// Platforms on which to run, for example `Darwin`. Runs everywhere when empty.
   pub platform: Option<Vec<String>>,
+  /// A command suggested to fix a failure.
   pub remedy: Option<String>,
+  /// Number of times to run the command again when it fails.
+  #[serde(default)]
+  pub retries: u32,
+  /// Double `retry_delay` after each retry, up to a minute.
+  #[serde(default)]
+  pub retry_backoff: bool,
+  /// How long to wait before retrying, for example `"2s"`. Defaults to one second.
+  pub retry_delay: Option<HumanDuration>,
+  /// The shell command to run.
   pub shell: String,
+  /// Tags selected by `--only tag:<tag>` and `--skip tag:<tag>`.
+  #[serde(default)]
+  pub tags: Vec<String>,
+  /// Kill the command and fail when it runs longer than this, for example `"90s"`. Overrides `--timeout`.
+  pub timeout: Option<HumanDuration>,
+  /// A shell command which must fail for this to run.
+  pub unless: Option<String>,
+  /// Environment variables which must not be set, or a table of values they must not have, for this to run.
+  pub unless_env: Option<EnvCondition>,
+  /// Print output to the terminal as it happens.
   #[serde(default)]
   pub verbose: bool,
 }
@@ -39,12 +84,24 @@ impl ShellConfig {
   pub fn new(name: String, shell: String, cd: Option<String>, remedy: Option<String>, verbose: bool) -> Self {
     Self {
       cd,
+      depends_on: vec![],
       env: BTreeMap::default(),
+      id: None,
+      r#if: None,
+      if_env: None,
+      inputs: vec![],
       manual: false,
       name,
       platform: None,
       shell,
       remedy,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
       verbose,
       allow_failure: false,
       inline: false,
@@ -53,74 +110,111 @@ impl ShellConfig {
 }
 
 impl Runnable for ShellConfig {
+  fn condition(&self) -> Condition<'_> {
+    Condition {
+      cd: self.cd.as_deref(),
+      env: Some(&self.env),
+      r#if: self.r#if.as_deref(),
+      if_env: self.if_env.as_ref(),
+      unless: self.unless.as_deref(),
+      unless_env: self.unless_env.as_ref(),
+    }
+  }
+
+  fn depends_on(&self) -> &[String] {
+    &self.depends_on
+  }
+
+  fn id(&self) -> Option<&str> {
+    self.id.as_deref()
+  }
+
   fn allow_failure(&self) -> bool {
     self.allow_failure
   }
 
+  fn inputs(&self) -> &[String] {
+    &self.inputs
+  }
+
+  fn name(&self) -> Option<&str> {
+    Some(&self.name)
+  }
+
   fn platform(&self) -> &Option<Vec<String>> {
     &self.platform
   }
 
-  fn run(&self, progress: &mut retrogress::ProgressBar, _flags: &mut Flags, _ctx: &Context) -> Recoverable<()> {
+  fn run(&self, progress: &mut retrogress::ProgressBar, flags: &mut Flags, _ctx: &Context) -> Recoverable<()> {
     let allow_failure = self.allow_failure();
     let verbose = self.verbose();
+    let timeout = self.timeout.map(Duration::from).or(flags.timeout);
+    let retry = Retry::new(self.retries, self.retry_delay, self.retry_backoff);
     let pb = progress.append(&self.to_string());
 
     match self.to_command() {
       Ok(mut command) => {
-        let output = if verbose {
-          command
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
-
-          let mut child = command.spawn()?;
-          let stderr = child
-            .stderr
-            .take()
-            .ok_or("Error capturing stderr of shell command.")?;
-          let stdout = child
-            .stdout
-            .take()
-            .ok_or("Error capturing stdout of shell command.")?;
-
-          let mut out_progress = progress.clone();
-          let mut err_progress = progress.clone();
-
-          let out_thr = thread::spawn(move || {
-            let reader = BufReader::new(stdout);
-            reader
-              .lines()
-              .map_while(Result::ok)
-              .for_each(|line| out_progress.println(pb, &line));
-          });
-          let err_thr = thread::spawn(move || {
-            let reader = BufReader::new(stderr);
-            reader
-              .lines()
-              .map_while(Result::ok)
-              .for_each(|line| err_progress.println(pb, &line));
-          });
-
-          let res = child.wait_with_output();
-          out_thr.join().unwrap();
-          err_thr.join().unwrap();
-          res
-        } else {
-          if self.inline {
+        let output = retry.run(progress, pb, &self.to_string(), |progress| {
+          if verbose {
             command
-              .stdin(Stdio::inherit())
-              .stdout(Stdio::inherit())
-              .stderr(Stdio::inherit());
-            progress.hide(pb);
+              .stdin(Stdio::piped())
+              .stdout(Stdio::piped())
+              .stderr(Stdio::piped());
+
+            let mut child = extra::command::spawn(&mut command, timeout, false)?;
+            let stderr = child
+              .stderr
+              .take()
+              .ok_or("Error capturing stderr of shell command.")?;
+            let stdout = child
+              .stdout
+              .take()
+              .ok_or("Error capturing stdout of shell command.")?;
+
+            let mut out_progress = progress.clone();
+            let mut err_progress = progress.clone();
+
+            let out_thr = thread::spawn(move || {
+              let reader = BufReader::new(stdout);
+              reader
+                .lines()
+                .map_while(Result::ok)
+                .for_each(|line| out_progress.println(pb, &line));
+            });
+            let err_thr = thread::spawn(move || {
+              let reader = BufReader::new(stderr);
+              reader
+                .lines()
+                .map_while(Result::ok)
+                .for_each(|line| err_progress.println(pb, &line));
+            });
+
+            let res = extra::command::wait(child, timeout);
+            out_thr.join().unwrap();
+            err_thr.join().unwrap();
+            res
+          } else {
+            if self.inline {
+              command
+                .stdin(Stdio::inherit())
+                .stdout(Stdio::inherit())
+                .stderr(Stdio::inherit());
+              progress.hide(pb);
+            } else {
+              command
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+            }
+            extra::command::output(&mut command, timeout, self.inline)
           }
-          command.output()
-        };
+        });
 
         if self.inline {
           progress.show(pb);
         }
 
+        let (output, timed_out) = extra::command::partial_output(output);
         match output {
           Ok(result) => {
             if result.status.success() {
@@ -128,7 +222,10 @@ impl Runnable for ShellConfig {
               Recoverable::Ok(())
             } else {
               progress.failed(pb);
-              let err = std_to_string(result.stderr);
+              let err = match timed_out {
+                Some(_) => std_to_string([result.stdout, result.stderr].concat()),
+                None => std_to_string(result.stderr),
+              };
               if !verbose && err.trim() != "" {
                 eprintln!(
                   "{}",
@@ -145,20 +242,32 @@ impl Runnable for ShellConfig {
 
               match (self.manual, allow_failure) {
                 (true, _) => Recoverable::Manual(Some(err.into()), remedy),
-                (false, true) => Recoverable::Optional((), remedy),
-                (false, false) => Recoverable::Err(None, remedy),
+                (false, true) => {
+                  if let Some(err) = &timed_out {
+                    eprintln!(
+                      "{}",
+                      OptionalStyled::new(err.to_string(), current_theme().error_style.clone())
+                    );
+                  }
+                  Recoverable::Optional((), remedy)
+                }
+                (false, false) => Recoverable::Err(timed_out, remedy),
               }
             }
           }
           Err(err) => {
             progress.failed(pb);
-            Recoverable::Err(Some(err.into()), None)
+            Recoverable::Err(Some(err), None)
           }
         }
       }
       Err(err) => Recoverable::Err(Some(format!("Failed to parse command: {err}").into()), None),
     }
   }
+  fn tags(&self) -> &[String] {
+    &self.tags
+  }
+
   fn to_command(&self) -> Result<Command, MedicError> {
     if self.shell.is_empty() {
       Err(MedicError::Message("No shell command specified".to_string()))
@@ -1,7 +1,11 @@
 // @related [subject](medic-src/src/shell/shell_config.rs)
 
 use super::*;
+use crate::cli::Flags;
+use crate::context::Context;
+use crate::error::MedicError;
 use crate::extra;
+use crate::recoverable::Recoverable;
 use crate::runnable::Runnable;
 use std::collections::BTreeMap;
 use std::ffi::OsStr;
@@ -30,13 +34,25 @@ fn test_deserialize() {
     ShellConfig {
       allow_failure: false,
       cd: None,
+      depends_on: vec![],
       env: BTreeMap::default(),
+      id: None,
+      r#if: None,
+      if_env: None,
       inline: false,
+      inputs: vec![],
       manual: false,
       platform: None,
       name: "Run some command".to_string(),
       remedy: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
       shell: "some command".to_string(),
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
       verbose: false,
     }
   );
@@ -56,13 +72,25 @@ fn test_deserialize_cd() {
     ShellConfig {
       allow_failure: false,
       cd: Some("./subdirectory".to_string()),
+      depends_on: vec![],
       env: BTreeMap::default(),
+      id: None,
+      r#if: None,
+      if_env: None,
       inline: false,
+      inputs: vec![],
       manual: false,
       platform: None,
       name: "Run some command".to_string(),
       remedy: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
       shell: "some command".to_string(),
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
       verbose: false,
     }
   );
@@ -82,16 +110,28 @@ fn test_deserialize_env() {
     ShellConfig {
       allow_failure: false,
       cd: None,
+      depends_on: vec![],
       env: BTreeMap::from([
         ("MY_VAR".to_string(), "first".to_string()),
         ("SECOND_VAR".to_string(), "second".to_string())
       ]),
+      id: None,
+      r#if: None,
+      if_env: None,
       inline: false,
+      inputs: vec![],
       manual: false,
       platform: None,
       name: "Run some command".to_string(),
       remedy: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
       shell: "some command".to_string(),
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
       verbose: false,
     }
   );
@@ -111,13 +151,25 @@ fn test_deserialize_verbose() {
     ShellConfig {
       allow_failure: false,
       cd: None,
+      depends_on: vec![],
       env: BTreeMap::default(),
+      id: None,
+      r#if: None,
+      if_env: None,
       inline: false,
+      inputs: vec![],
       manual: false,
       platform: None,
       name: "Run some command".to_string(),
       remedy: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
       shell: "some command".to_string(),
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
       verbose: true,
     }
   );
@@ -137,13 +189,25 @@ fn test_deserialize_allow_failure() {
     ShellConfig {
       allow_failure: true,
       cd: None,
+      depends_on: vec![],
       env: BTreeMap::default(),
+      id: None,
+      r#if: None,
+      if_env: None,
       inline: false,
+      inputs: vec![],
       manual: false,
       platform: None,
       name: "Run some command".to_string(),
       remedy: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
       shell: "some command".to_string(),
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
       verbose: false,
     }
   );
@@ -154,13 +218,25 @@ fn test_to_command() {
   let shell = ShellConfig {
     allow_failure: false,
     cd: None,
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
     inline: false,
+    inputs: vec![],
     manual: false,
     platform: None,
     name: "Run some command".to_string(),
     remedy: Some("do something".to_string()),
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
     shell: "some command".to_string(),
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -176,13 +252,25 @@ fn test_to_command_cd() {
   let shell = ShellConfig {
     allow_failure: false,
     cd: Some("../fixtures/bin".to_string()),
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
     inline: false,
+    inputs: vec![],
     manual: false,
     platform: None,
     name: "Run some command".to_string(),
     remedy: Some("do something".to_string()),
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
     shell: "some command".to_string(),
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -199,16 +287,28 @@ fn test_to_command_env() {
   let shell = ShellConfig {
     allow_failure: false,
     cd: None,
+    depends_on: vec![],
     env: BTreeMap::from([
       ("VAR".to_string(), "value".to_string()),
       ("OTHER".to_string(), "other".to_string()),
     ]),
+    id: None,
+    r#if: None,
+    if_env: None,
     inline: false,
+    inputs: vec![],
     manual: false,
     platform: None,
     name: "Run some command".to_string(),
     remedy: Some("do something".to_string()),
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
     shell: "some command".to_string(),
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
   let cmd = shell.to_command().unwrap();
@@ -236,13 +336,25 @@ fn test_deserialize_manual() {
     ShellConfig {
       allow_failure: false,
       cd: None,
+      depends_on: vec![],
       env: BTreeMap::default(),
+      id: None,
+      r#if: None,
+      if_env: None,
       inline: false,
+      inputs: vec![],
       manual: true,
       platform: None,
       name: "Run some command".to_string(),
       remedy: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
       shell: "some command".to_string(),
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
       verbose: false,
     }
   );
@@ -261,13 +373,25 @@ fn test_deserialize_remedy() {
     ShellConfig {
       allow_failure: false,
       cd: None,
+      depends_on: vec![],
       env: BTreeMap::default(),
+      id: None,
+      r#if: None,
+      if_env: None,
       inline: false,
+      inputs: vec![],
       manual: false,
       platform: None,
       name: "Run some command".to_string(),
       remedy: Some("do something".to_string()),
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
       shell: "some command".to_string(),
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
       verbose: false,
     }
   );
@@ -279,13 +403,25 @@ fn test_to_string() {
   let shell = ShellConfig {
     allow_failure: false,
     cd: None,
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
     inline: false,
+    inputs: vec![],
     manual: false,
     platform: None,
     name: "Run some command".to_string(),
     remedy: Some("do something".to_string()),
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
     shell: "some command".to_string(),
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -301,13 +437,25 @@ fn test_to_string_cd() {
   let shell = ShellConfig {
     allow_failure: false,
     cd: Some("../fixtures/bin".to_string()),
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
     inline: false,
+    inputs: vec![],
     manual: false,
     platform: None,
     name: "Run some command".to_string(),
     remedy: Some("do something".to_string()),
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
     shell: "some command".to_string(),
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -316,3 +464,68 @@ fn test_to_string_cd() {
     "\u{1b}[36mRun some command\u{1b}[0m \u{1b}[33m(some command)\u{1b}[0m \u{1b}[32m(../fixtures/bin)\u{1b}[0m"
   );
 }
+
+#[test]
+fn test_deserialize_timeout() {
+  let toml = r#"
+        shell = "some command"
+        name = "Run some command"
+        timeout = "1m 30s"
+        "#;
+
+  let result: ShellConfig = toml::from_str(toml).unwrap();
+  assert_eq!(
+    result.timeout,
+    Some(crate::util::HumanDuration(std::time::Duration::from_secs(90)))
+  );
+}
+
+fn run_with_timeout(shell: &ShellConfig, timeout: Option<std::time::Duration>) -> Recoverable<()> {
+  initialize();
+  let mut flags = Flags {
+    timeout,
+    ..Flags::default()
+  };
+  let mut progress = crate::progress::new(&flags);
+  shell.run(&mut progress, &mut flags, &Context::new())
+}
+
+#[test]
+fn test_run_timeout() {
+  let mut shell = ShellConfig::new(
+    "Hang".to_string(),
+    "echo partial && sleep 5".to_string(),
+    None,
+    None,
+    false,
+  );
+  shell.timeout = Some("100ms".parse().unwrap());
+
+  let started = std::time::Instant::now();
+  let result = run_with_timeout(&shell, None);
+
+  assert!(started.elapsed() < std::time::Duration::from_secs(2));
+  match result {
+    Recoverable::Err(Some(MedicError::TimedOut(timeout, output)), None) => {
+      assert_eq!(timeout, std::time::Duration::from_millis(100));
+      assert_eq!(crate::std_to_string(output.stdout), "partial\n");
+    }
+    _ => panic!("Expected the step to time out"),
+  }
+}
+
+#[test]
+fn test_run_default_timeout() {
+  let shell = ShellConfig::new("Hang".to_string(), "sleep 5".to_string(), None, None, false);
+
+  let result = run_with_timeout(&shell, Some(std::time::Duration::from_millis(100)));
+  assert!(matches!(
+    result,
+    Recoverable::Err(Some(MedicError::TimedOut(_, _)), None)
+  ));
+
+  let mut allowed = shell.clone();
+  allowed.allow_failure = true;
+  let result = run_with_timeout(&allowed, Some(std::time::Duration::from_millis(100)));
+  assert!(matches!(result, Recoverable::Optional((), None)));
+}
@@ -1,4 +1,5 @@
 use crate::cli::Flags;
+use crate::condition::Condition;
 use crate::config;
 use crate::context::Context;
 use crate::error::MedicError;
@@ -10,12 +11,13 @@ use crate::shell::ShellConfig;
 use crate::step::StepConfig;
 use crate::theme::current_theme;
 use crate::{AppResult, Check};
+use schemars::JsonSchema;
 use serde::Deserialize;
 
 use std::fmt;
 use std::process::Command;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq)]
 #[serde(untagged)]
 pub enum ShipitStep {
   Check(Check),
@@ -26,15 +28,18 @@ pub enum ShipitStep {
   Update(UpdateConfig),
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq)]
+#[schemars(rename = "ShipitAudit")]
 pub struct AuditConfig {
   pub audit: NoopConfig,
 }
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq)]
+#[schemars(rename = "ShipitTest")]
 pub struct TestConfig {
   pub test: NoopConfig,
 }
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq)]
+#[schemars(rename = "ShipitUpdate")]
 pub struct UpdateConfig {
   pub update: NoopConfig,
 }
@@ -51,6 +56,61 @@ impl Runnable for ShipitStep {
     }
   }
 
+  fn condition(&self) -> Condition<'_> {
+    match self {
+      ShipitStep::Check(config) => config.condition(),
+      ShipitStep::Shell(config) => config.condition(),
+      ShipitStep::Step(config) => config.condition(),
+      ShipitStep::Audit(_) => Condition::default(),
+      ShipitStep::Test(_) => Condition::default(),
+      ShipitStep::Update(_) => Condition::default(),
+    }
+  }
+
+  fn depends_on(&self) -> &[String] {
+    match self {
+      ShipitStep::Check(config) => config.depends_on(),
+      ShipitStep::Shell(config) => config.depends_on(),
+      ShipitStep::Step(config) => config.depends_on(),
+      ShipitStep::Audit(_) => &[],
+      ShipitStep::Test(_) => &[],
+      ShipitStep::Update(_) => &[],
+    }
+  }
+
+  fn id(&self) -> Option<&str> {
+    match self {
+      ShipitStep::Check(config) => config.id(),
+      ShipitStep::Shell(config) => config.id(),
+      ShipitStep::Step(config) => config.id(),
+      ShipitStep::Audit(_) => None,
+      ShipitStep::Test(_) => None,
+      ShipitStep::Update(_) => None,
+    }
+  }
+
+  fn inputs(&self) -> &[String] {
+    match self {
+      ShipitStep::Check(config) => config.inputs(),
+      ShipitStep::Shell(config) => config.inputs(),
+      ShipitStep::Step(config) => config.inputs(),
+      ShipitStep::Audit(_) => &[],
+      ShipitStep::Test(_) => &[],
+      ShipitStep::Update(_) => &[],
+    }
+  }
+
+  fn name(&self) -> Option<&str> {
+    match self {
+      ShipitStep::Check(config) => config.name(),
+      ShipitStep::Shell(config) => config.name(),
+      ShipitStep::Step(config) => config.name(),
+      ShipitStep::Audit(_) => None,
+      ShipitStep::Test(_) => None,
+      ShipitStep::Update(_) => None,
+    }
+  }
+
   fn platform(&self) -> &Option<Vec<String>> {
     match self {
       ShipitStep::Check(config) => config.platform(),
@@ -73,6 +133,17 @@ impl Runnable for ShipitStep {
     }
   }
 
+  fn tags(&self) -> &[String] {
+    match self {
+      ShipitStep::Check(config) => config.tags(),
+      ShipitStep::Shell(config) => config.tags(),
+      ShipitStep::Step(config) => config.tags(),
+      ShipitStep::Audit(_) => &[],
+      ShipitStep::Test(_) => &[],
+      ShipitStep::Update(_) => &[],
+    }
+  }
+
   fn to_command(&self) -> Result<Command, MedicError> {
     match self {
       ShipitStep::Check(config) => config.to_command(),
@@ -0,0 +1,156 @@
+// @related [tests](medic-src/src/show/show_test.rs)
+
+#[cfg(test)]
+mod show_test;
+
+use crate::config::{Manifest, loader};
+use crate::context::Context;
+use crate::error::MedicError;
+use crate::extra;
+use crate::runnable::Runnable;
+use crate::{AppResult, AuditStep, DoctorStep, OutdatedCheck, ShipitStep, Step};
+
+use serde::de::DeserializeOwned;
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+use toml::{Table, Value};
+
+#[derive(clap::ValueEnum, Clone, Debug, Default)]
+pub enum Format {
+  #[default]
+  Toml,
+  Json,
+}
+
+/// Prints the manifest at `path` to STDOUT as medic sees it, after includes,
+/// definitions, the local override and vars are applied. When `resolved`,
+/// each step also shows the command it runs or why it would be skipped.
+pub fn run(path: &Path, format: Format, resolved: bool, context: &Context) -> AppResult<()> {
+  let (manifest, mut table) = Manifest::load(path)?;
+  manifest.notify_local_override();
+
+  table.remove("definitions");
+  if resolved {
+    resolve(&mut table, context);
+  }
+
+  let output = match format {
+    Format::Toml => Ok(to_toml(&table)),
+    Format::Json => serde_json::to_string_pretty(&table).map_err(|err| err.to_string()),
+  }
+  .map_err(|err| MedicError::from(format!("Unable to print manifest: {err}")))?;
+
+  match writeln!(std::io::stdout(), "{}", output.trim_end()) {
+    Err(err) if err.kind() != ErrorKind::BrokenPipe => AppResult::Err(Some(err.into())),
+    _ => AppResult::Ok(()),
+  }
+}
+
+/// Adds a `resolved` table to each step of `manifest`, with the command line
+/// the step runs, the reason it would be skipped on this platform, or the
+/// error preventing its command from being built.
+pub fn resolve(manifest: &mut Table, context: &Context) {
+  for (section_name, steps) in loader::step_lists(manifest) {
+    resolve_steps(steps, &section_name, context);
+  }
+}
+
+fn resolve_steps(steps: &mut [Value], section_name: &str, context: &Context) {
+  for step in steps.iter_mut() {
+    if let Some(nested) = loader::nested_steps_mut(step) {
+      resolve_steps(nested, section_name, context);
+    } else if let Value::Table(step) = step {
+      let resolved = resolve_step(Value::Table(step.clone()), section_name, context);
+      if !resolved.is_empty() {
+        step.insert("resolved".to_string(), Value::Table(resolved));
+      }
+    }
+  }
+}
+
+// Steps that run another section of the manifest, such as `{ doctor = {} }`,
+// have no command of their own.
+fn resolve_step(step: Value, section_name: &str, context: &Context) -> Table {
+  match section_name {
+    "audit" => inspect::<AuditStep>(step, context),
+    "doctor" => inspect::<DoctorStep>(step, context),
+    "outdated" => inspect::<OutdatedCheck>(step, context),
+    "shipit" => match step.clone().try_into::<ShipitStep>() {
+      Ok(ShipitStep::Audit(_) | ShipitStep::Test(_) | ShipitStep::Update(_)) => Table::new(),
+      _ => inspect::<ShipitStep>(step, context),
+    },
+    _ => match step.clone().try_into::<Step>() {
+      Ok(Step::Doctor(_)) => Table::new(),
+      _ => inspect::<Step>(step, context),
+    },
+  }
+}
+
+fn inspect<T: Runnable + DeserializeOwned>(step: Value, context: &Context) -> Table {
+  let mut resolved = Table::new();
+  let Ok(step) = step.try_into::<T>() else {
+    return resolved;
+  };
+
+  if let Some(reason) = context.platform_skip_reason(step.platform()) {
+    resolved.insert("skipped".to_string(), Value::String(reason));
+  }
+
+  match step.to_command() {
+    Ok(command) => resolved.insert("command".to_string(), Value::String(extra::command::describe(&command))),
+    Err(err) => resolved.insert("error".to_string(), Value::String(err.to_string())),
+  };
+
+  resolved
+}
+
+/// Renders `manifest` as TOML in the layout of a hand-written manifest, with
+/// one inline table per step.
+pub fn to_toml(manifest: &Table) -> String {
+  let mut output = String::new();
+  render_table(&mut output, &[], manifest);
+  output
+}
+
+fn render_table(output: &mut String, path: &[String], table: &Table) {
+  let (tables, values): (Vec<_>, Vec<_>) = table.iter().partition(|(_, value)| value.is_table());
+
+  if !path.is_empty() && !values.is_empty() {
+    output.push_str(&format!("[{}]\n", path.join(".")));
+  }
+  for (key, value) in &values {
+    match value {
+      Value::Array(items) if !items.is_empty() => {
+        output.push_str(&format!("{} = [\n", key_name(key)));
+        for item in items {
+          output.push_str(&format!("  {item},\n"));
+        }
+        output.push_str("]\n");
+      }
+      value => output.push_str(&format!("{} = {value}\n", key_name(key))),
+    }
+  }
+  if !values.is_empty() {
+    output.push('\n');
+  }
+
+  for (key, value) in tables {
+    if let Value::Table(table) = value {
+      let mut path = path.to_vec();
+      path.push(key_name(key));
+      render_table(output, &path, table);
+    }
+  }
+}
+
+fn key_name(key: &str) -> String {
+  if !key.is_empty()
+    && key
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+  {
+    key.to_string()
+  } else {
+    Value::String(key.to_string()).to_string()
+  }
+}
@@ -0,0 +1,123 @@
+// @related [subject](medic-src/src/show/mod.rs)
+
+use super::*;
+use crate::context::Context;
+use indoc::indoc;
+use toml::Table;
+
+fn context() -> Context {
+  Context {
+    platform: "Linux".to_string(),
+  }
+}
+
+fn resolved(manifest: &str) -> String {
+  let mut table: Table = toml::from_str(manifest).unwrap();
+  resolve(&mut table, &context());
+  to_toml(&table)
+}
+
+#[test]
+fn resolve_shows_commands() {
+  let manifest = indoc! {r#"
+    [doctor]
+    checks = [
+      { name = "Setup", shell = "bin/setup --all", env = { SEED = "small world" } },
+      { check = "missing" },
+    ]
+  "#};
+
+  assert_eq!(
+    resolved(manifest),
+    indoc! {r#"
+      [doctor]
+      checks = [
+        { env = { SEED = "small world" }, name = "Setup", resolved = { command = "SEED='small world' sh -c 'bin/setup --all'" }, shell = "bin/setup --all" },
+        { check = "missing", resolved = { error = "executable medic-check-missing not found in PATH" } },
+      ]
+
+    "#}
+  );
+}
+
+#[test]
+fn resolve_marks_steps_skipped_on_platform() {
+  let manifest = indoc! {r#"
+    [test]
+    checks = [
+      { name = "Mac only", shell = "true", platform = ["Darwin"] },
+      { name = "Linux only", shell = "true", platform = ["Linux"] },
+    ]
+  "#};
+
+  assert_eq!(
+    resolved(manifest),
+    indoc! {r#"
+      [test]
+      checks = [
+        { name = "Mac only", platform = ["Darwin"], resolved = { command = "sh -c true", skipped = "only runs on Darwin, not Linux" }, shell = "true" },
+        { name = "Linux only", platform = ["Linux"], resolved = { command = "sh -c true" }, shell = "true" },
+      ]
+
+    "#}
+  );
+}
+
+#[test]
+fn resolve_skips_steps_running_other_sections() {
+  let manifest = indoc! {r#"
+    [shipit]
+    steps = [
+      { audit = {} },
+      { name = "Push", shell = "git push" },
+    ]
+
+    [workflows.seed]
+    steps = [
+      [{ doctor = {} }, { name = "Seed", shell = "bin/seed" }],
+    ]
+  "#};
+
+  assert_eq!(
+    resolved(manifest),
+    indoc! {r#"
+      [shipit]
+      steps = [
+        { audit = {} },
+        { name = "Push", resolved = { command = "sh -c 'git push'" }, shell = "git push" },
+      ]
+
+      [workflows.seed]
+      steps = [
+        [{ doctor = {} }, { name = "Seed", resolved = { command = "sh -c bin/seed" }, shell = "bin/seed" }],
+      ]
+
+    "#}
+  );
+}
+
+#[test]
+fn to_toml_writes_vars_and_empty_lists() {
+  let table: Table = toml::from_str(indoc! {r#"
+    [vars]
+    "with space" = "a"
+    bin = "bin/dev"
+
+    [outdated]
+    checks = []
+  "#})
+  .unwrap();
+
+  assert_eq!(
+    to_toml(&table),
+    indoc! {r#"
+      [outdated]
+      checks = []
+
+      [vars]
+      bin = "bin/dev"
+      "with space" = "a"
+
+    "#}
+  );
+}
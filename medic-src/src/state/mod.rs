@@ -0,0 +1,183 @@
+// @related [tests](medic-src/src/state/state_test.rs)
+
+#[cfg(test)]
+mod state_test;
+
+use crate::AppResult;
+use crate::cli::Flags;
+use crate::config::{discovery, manifest};
+use crate::context::Context;
+use crate::error::MedicError;
+use crate::history;
+use crate::optional_styled::OptionalStyled;
+use crate::report::{self, Status};
+use crate::runnable::{self, Runnable};
+use crate::step::graph;
+use crate::theme::current_theme;
+
+use retrogress::ProgressBar;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+const STATE_DIR: &str = ".medic/state";
+
+/// Progress through a list of steps, saved after each step succeeds so that
+/// `--resume` can skip those steps when the list is run again. The saved
+/// state is discarded once every step has succeeded, or when the manifest
+/// has changed since it was saved.
+#[derive(Debug)]
+pub struct State {
+  fingerprint: String,
+  path: PathBuf,
+  succeeded: BTreeSet<usize>,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+struct Saved {
+  manifest: String,
+  succeeded: BTreeSet<usize>,
+}
+
+impl State {
+  /// Loads the state of the list of steps called `name` for the manifest at
+  /// `flags.config_path`. Steps which succeeded before are only remembered
+  /// with `--resume`.
+  pub fn load(name: &str, flags: &Flags) -> Result<Self, MedicError> {
+    let manifest_path = discovery::locate(&flags.config_path)?;
+    let (table, _) = manifest::resolve(&manifest_path)?;
+    let fingerprint = format!("{:x}", Sha256::digest(table.to_string()));
+    let path = path(&manifest_path, name);
+
+    let mut state = Self {
+      fingerprint,
+      path,
+      succeeded: BTreeSet::new(),
+    };
+    if flags.resume {
+      state.resume();
+    }
+    Ok(state)
+  }
+
+  fn resume(&mut self) {
+    let Some(saved) = std::fs::read_to_string(&self.path)
+      .ok()
+      .and_then(|json| serde_json::from_str::<Saved>(&json).ok())
+    else {
+      return;
+    };
+
+    if saved.manifest == self.fingerprint {
+      self.succeeded = saved.succeeded;
+    } else {
+      eprintln!(
+        "{}",
+        OptionalStyled::new(
+          "The manifest has changed since the last run; running every step",
+          current_theme().warning_style.clone()
+        )
+      );
+    }
+  }
+
+  /// Whether the step at `index` succeeded in the run being resumed.
+  pub fn succeeded(&self, index: usize) -> bool {
+    self.succeeded.contains(&index)
+  }
+
+  /// Saves that the step at `index` succeeded.
+  pub fn record(&mut self, index: usize) -> Result<(), MedicError> {
+    self.succeeded.insert(index);
+
+    let saved = Saved {
+      manifest: self.fingerprint.clone(),
+      succeeded: self.succeeded.clone(),
+    };
+    let json = serde_json::to_string_pretty(&saved).map_err(|err| err.to_string())?;
+    if let Some(dir) = self.path.parent() {
+      std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&self.path, json)?;
+    Ok(())
+  }
+
+  /// Removes the saved state once every step has succeeded.
+  pub fn finish(self) -> Result<(), MedicError> {
+    match std::fs::remove_file(&self.path) {
+      Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+      _ => Ok(()),
+    }
+  }
+}
+
+/// The file in which the state of `name` is saved, in `.medic/state` of the
+/// project the manifest belongs to. Named workflows are saved in a
+/// `workflows` directory, so as not to clash with other subcommands.
+pub fn path(manifest: &Path, name: &str) -> PathBuf {
+  discovery::project_dir(manifest)
+    .join(STATE_DIR)
+    .join(format!("{name}.json"))
+}
+
+/// Runs `steps` in order, saving which have succeeded under `name`. With
+/// `--resume`, steps which succeeded in the previous run are skipped, while
+/// steps which depend on a step that did not pass, whether it failed or was
+/// skipped, are skipped always.
+/// How long each step inside them took is added to its history under
+/// `name`, warning when a step is much slower than usual. Nothing is saved
+/// with `--dry-run`.
+pub fn run_steps<R: Runnable>(
+  name: &str,
+  steps: Vec<R>,
+  progress: &mut ProgressBar,
+  flags: &mut Flags,
+  context: &Context,
+) -> AppResult<()> {
+  let mut state = State::load(name, flags)?;
+  let _history = history::start(name, &flags.config_path)?;
+  let failed_before = report::failure_count();
+
+  let dependencies = graph::dependencies(&steps, name).map_err(MedicError::from)?;
+  let mut failed = vec![false; steps.len()];
+
+  for (index, step) in steps.iter().enumerate() {
+    if let Some(&dep) = dependencies[index].iter().find(|&&dep| failed[dep]) {
+      failed[index] = true;
+      let reason = graph::skip_reason(&steps[dep]);
+      runnable::print_skipped(step, &reason, progress);
+      if step.name().is_some() && !flags.dry_run {
+        report::finished(&step.to_string(), Status::Skipped(reason), Instant::now());
+      }
+      continue;
+    }
+    if state.succeeded(index) {
+      let reason = "succeeded in the previous run";
+      runnable::print_skipped(step, reason, progress);
+      if step.name().is_some() && !flags.dry_run {
+        report::finished(&step.to_string(), Status::Skipped(reason.into()), Instant::now());
+      }
+      continue;
+    }
+    let (result, status) = runnable::run_with_status(step.clone(), progress, flags, context);
+    result?;
+    failed[index] = !runnable::passed(step, status.as_ref(), flags);
+    if flags.dry_run {
+      continue;
+    }
+    // Steps which were filtered out or skipped have yet to succeed.
+    if matches!(status, Some(Status::Cached | Status::Passed | Status::Remedied)) {
+      state.record(index)?;
+    }
+  }
+
+  // With `--keep-going`, failed steps are left for `--resume` to run again.
+  match report::failure_count() - failed_before {
+    0 if !flags.dry_run => state.finish()?,
+    0 => {}
+    count => return AppResult::Err(Some(report::failed_steps(count))),
+  }
+  AppResult::Ok(())
+}
@@ -0,0 +1,326 @@
+// @related [subject](medic-src/src/state/mod.rs)
+
+use super::{State, path, run_steps};
+use crate::AppResult;
+use crate::cli::Flags;
+use crate::context::Context;
+use crate::fixture::{self, Project, shell};
+use crate::history::History;
+use crate::progress;
+use crate::report::Status;
+use crate::step::Step;
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+
+static INIT: Once = Once::new();
+pub fn initialize() {
+  INIT.call_once(|| {
+    if crate::theme::THEME.get().is_none() {
+      let theme = crate::theme::dark_theme();
+      crate::theme::set_theme(theme);
+    }
+  });
+}
+
+fn flags(dir: &Project, resume: bool) -> Flags {
+  Flags { resume, ..dir.flags() }
+}
+
+#[test]
+fn test_path() {
+  assert_eq!(
+    path(Path::new("/project/.config/medic.toml"), "update"),
+    PathBuf::from("/project/.medic/state/update.json")
+  );
+  assert_eq!(
+    path(Path::new("/project/medic.toml"), "workflows/deploy"),
+    PathBuf::from("/project/.medic/state/workflows/deploy.json")
+  );
+}
+
+#[test]
+fn test_resume_remembers_succeeded_steps() {
+  let dir = Project::new("[update]\nsteps = []\n");
+
+  let mut state = State::load("update", &flags(&dir, false)).unwrap();
+  state.record(0).unwrap();
+  state.record(1).unwrap();
+  assert!(dir.join(".medic/state/update.json").exists());
+
+  let state = State::load("update", &flags(&dir, true)).unwrap();
+  assert!(state.succeeded(0));
+  assert!(state.succeeded(1));
+  assert!(!state.succeeded(2));
+
+  let state = State::load("update", &flags(&dir, false)).unwrap();
+  assert!(!state.succeeded(0));
+
+  let state = State::load("test", &flags(&dir, true)).unwrap();
+  assert!(!state.succeeded(0));
+}
+
+#[test]
+fn test_resume_ignores_state_of_a_changed_manifest() {
+  initialize();
+  let dir = Project::new("[update]\nsteps = []\n");
+
+  let mut state = State::load("update", &flags(&dir, false)).unwrap();
+  state.record(0).unwrap();
+
+  std::fs::write(dir.join("medic.toml"), "[update]\nsteps = []\n[test]\nchecks = []\n").unwrap();
+  let state = State::load("update", &flags(&dir, true)).unwrap();
+  assert!(!state.succeeded(0));
+}
+
+#[test]
+fn test_finish_removes_state() {
+  let dir = Project::new("[update]\nsteps = []\n");
+
+  let state = State::load("update", &flags(&dir, false)).unwrap();
+  state.finish().unwrap();
+
+  let mut state = State::load("update", &flags(&dir, false)).unwrap();
+  state.record(0).unwrap();
+  state.finish().unwrap();
+  assert!(!dir.join(".medic/state/update.json").exists());
+}
+
+#[test]
+fn test_run_steps_resumes_from_failed_step() {
+  initialize();
+  let _run = fixture::keep_going();
+  let dir = Project::new("[update]\nsteps = []\n");
+  let log = dir.join("log");
+  let fixed = dir.join("fixed");
+  let steps = || {
+    vec![
+      shell("One", &format!("echo one >> {}", log.display())),
+      shell(
+        "Two",
+        &format!("test -f {} && echo two >> {}", fixed.display(), log.display()),
+      ),
+      shell("Three", &format!("echo three >> {}", log.display())),
+    ]
+  };
+  let context = Context::new();
+
+  let mut flags = flags(&dir, true);
+  let mut progress = progress::new(&flags);
+  assert!(matches!(
+    run_steps("update", steps(), &mut progress, &mut flags, &context),
+    AppResult::Err(_)
+  ));
+  assert_eq!(std::fs::read_to_string(&log).unwrap(), "one\n");
+
+  std::fs::write(&fixed, "").unwrap();
+  assert!(matches!(
+    run_steps("update", steps(), &mut progress, &mut flags, &context),
+    AppResult::Ok(())
+  ));
+  assert_eq!(std::fs::read_to_string(&log).unwrap(), "one\ntwo\nthree\n");
+  assert!(!dir.join(".medic/state/update.json").exists());
+}
+
+#[test]
+fn test_run_steps_keeps_going_past_failed_steps() {
+  initialize();
+  let _run = fixture::keep_going();
+  let dir = Project::new("[update]\nsteps = []\n");
+  let log = dir.join("log");
+  let steps = vec![
+    shell("One", &format!("echo one >> {}", log.display())),
+    shell("Two", &format!("false {}", log.display())),
+    shell("Three", &format!("echo three >> {}", log.display())),
+  ];
+  let names = steps.iter().map(ToString::to_string).collect::<Vec<_>>();
+  let context = Context::new();
+
+  let mut flags = Flags {
+    keep_going: true,
+    ..flags(&dir, true)
+  };
+  let mut progress = progress::new(&flags);
+  let failures = crate::report::failure_count();
+  assert!(matches!(
+    run_steps("update", steps, &mut progress, &mut flags, &context),
+    AppResult::Err(Some(err)) if err.to_string() == "1 step failed"
+  ));
+  assert_eq!(std::fs::read_to_string(&log).unwrap(), "one\nthree\n");
+  assert_eq!(crate::report::failure_count(), failures + 1);
+  let statuses = names
+    .iter()
+    .map(|name| {
+      crate::report::outcomes()
+        .into_iter()
+        .find(|outcome| &outcome.step == name)
+        .map(|outcome| outcome.status)
+    })
+    .collect::<Vec<_>>();
+  assert_eq!(
+    statuses,
+    vec![Some(Status::Passed), Some(Status::Failed), Some(Status::Passed)]
+  );
+
+  let state = State::load("update", &flags).unwrap();
+  assert!(state.succeeded(0));
+  assert!(!state.succeeded(1));
+  assert!(state.succeeded(2));
+}
+
+#[test]
+fn test_run_steps_resumes_steps_which_were_filtered_out() {
+  initialize();
+  let _run = fixture::keep_going();
+  let dir = Project::new("[update]\nsteps = []\n");
+  let log = dir.join("log");
+  let fixed = dir.join("fixed");
+  let steps = || {
+    let mut steps = vec![
+      shell("One", &format!("echo one >> {}", log.display())),
+      shell("Two", &format!("echo two >> {}", log.display())),
+      shell(
+        "Three",
+        &format!("test -f {} && echo three >> {}", fixed.display(), log.display()),
+      ),
+    ];
+    steps[0].tags = vec!["fast".into()];
+    steps[2].tags = vec!["fast".into()];
+    steps
+  };
+  let context = Context::new();
+
+  let mut filtered = Flags {
+    only: vec!["tag:fast".parse().unwrap()],
+    ..flags(&dir, false)
+  };
+  let mut progress = progress::new(&filtered);
+  assert!(matches!(
+    run_steps("update", steps(), &mut progress, &mut filtered, &context),
+    AppResult::Err(_)
+  ));
+  assert_eq!(std::fs::read_to_string(&log).unwrap(), "one\n");
+
+  std::fs::write(&fixed, "").unwrap();
+  let mut flags = flags(&dir, true);
+  assert!(matches!(
+    run_steps("update", steps(), &mut progress, &mut flags, &context),
+    AppResult::Ok(())
+  ));
+  assert_eq!(std::fs::read_to_string(&log).unwrap(), "one\ntwo\nthree\n");
+}
+
+#[test]
+fn test_run_steps_skips_dependents_of_steps_allowed_to_fail() {
+  initialize();
+  let _run = fixture::keep_going();
+  let dir = Project::new("[update]\nsteps = []\n");
+  let log = dir.join("log");
+  let mut optional = shell("Optional", "false");
+  optional.id = Some("optional".into());
+  optional.allow_failure = true;
+  let mut dependent = shell("Dependent", &format!("echo dependent >> {}", log.display()));
+  dependent.depends_on = vec!["optional".into()];
+  let steps = vec![
+    optional,
+    dependent,
+    shell("Independent", &format!("echo independent >> {}", log.display())),
+  ];
+  let dependent = steps[1].to_string();
+  let context = Context::new();
+
+  let mut flags = flags(&dir, false);
+  let mut progress = progress::new(&flags);
+  assert!(matches!(
+    run_steps("update", steps, &mut progress, &mut flags, &context),
+    AppResult::Ok(())
+  ));
+  assert_eq!(std::fs::read_to_string(&log).unwrap(), "independent\n");
+  assert_eq!(
+    crate::report::outcome_of(&dependent).map(|outcome| outcome.status),
+    Some(Status::Skipped("depends on `optional`, which did not succeed".into()))
+  );
+}
+
+#[test]
+fn test_run_steps_skips_dependents_of_skipped_steps() {
+  initialize();
+  let _run = fixture::keep_going();
+  let dir = Project::new("[update]\nsteps = []\n");
+  let log = dir.join("log");
+  let mut skipped = shell("Skipped", &format!("echo skipped >> {}", log.display()));
+  skipped.id = Some("skipped".into());
+  skipped.r#if = Some("false".into());
+  let mut dependent = shell("Dependent", &format!("echo dependent >> {}", log.display()));
+  dependent.depends_on = vec!["skipped".into()];
+  let steps = vec![
+    skipped,
+    dependent,
+    shell("Independent", &format!("echo independent >> {}", log.display())),
+  ];
+  let dependent = steps[1].to_string();
+  let context = Context::new();
+
+  let mut flags = flags(&dir, false);
+  let mut progress = progress::new(&flags);
+  assert!(matches!(
+    run_steps("update", steps, &mut progress, &mut flags, &context),
+    AppResult::Ok(())
+  ));
+  assert_eq!(std::fs::read_to_string(&log).unwrap(), "independent\n");
+  assert_eq!(
+    crate::report::outcome_of(&dependent).map(|outcome| outcome.status),
+    Some(Status::Skipped("depends on `skipped`, which did not succeed".into()))
+  );
+}
+
+#[test]
+fn test_run_steps_keeps_going_without_running_dependents_of_failed_steps() {
+  initialize();
+  let _run = fixture::keep_going();
+  let dir = Project::new("[update]\nsteps = []\n");
+  let log = dir.join("log");
+  let mut failing = shell("Failing", "false");
+  failing.id = Some("failing".into());
+  let mut dependent = shell("Dependent", &format!("echo dependent >> {}", log.display()));
+  dependent.depends_on = vec!["failing".into()];
+  let steps = vec![
+    failing,
+    dependent,
+    shell("Independent", &format!("echo independent >> {}", log.display())),
+  ];
+  let context = Context::new();
+
+  let mut flags = Flags {
+    keep_going: true,
+    ..flags(&dir, false)
+  };
+  let mut progress = progress::new(&flags);
+  assert!(matches!(
+    run_steps("update", steps, &mut progress, &mut flags, &context),
+    AppResult::Err(Some(err)) if err.to_string() == "1 step failed"
+  ));
+  assert_eq!(std::fs::read_to_string(&log).unwrap(), "independent\n");
+}
+
+#[test]
+fn test_run_steps_records_how_long_each_step_inside_them_took() {
+  initialize();
+  let _run = fixture::keep_going();
+  let dir = Project::new("[update]\nsteps = []\n");
+  let steps = vec![
+    Step::Shell(shell("Outer", "true")),
+    Step::Steps(vec![Step::Shell(shell("Inner", "true"))]),
+  ];
+  let context = Context::new();
+
+  let mut flags = flags(&dir, false);
+  let mut progress = progress::new(&flags);
+  assert!(matches!(
+    run_steps("update", steps, &mut progress, &mut flags, &context),
+    AppResult::Ok(())
+  ));
+
+  let history = History::load(&flags.config_path).unwrap();
+  let steps = history.steps("update").unwrap();
+  assert_eq!(steps.keys().collect::<Vec<_>>(), vec!["Inner (true)", "Outer (true)"]);
+}
@@ -0,0 +1,149 @@
+// @related [tests](medic-src/src/stats/stats_test.rs)
+
+#[cfg(test)]
+mod stats_test;
+
+use crate::AppResult;
+use crate::history::History;
+use crate::list::SECTIONS;
+use crate::optional_styled::OptionalStyled;
+use crate::report::elapsed;
+use crate::theme::current_theme;
+
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+use std::time::Duration;
+
+// Changes in how long a step takes smaller than this are shown as steady.
+const STEADY: f64 = 0.05;
+
+/// How long a step has taken across the runs in which it passed.
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+  pub step: String,
+  pub runs: usize,
+  pub mean: Duration,
+  pub p95: Duration,
+  /// How much longer the newer half of the runs took than the older half,
+  /// as a fraction of the older half. Steps which have run fewer than four
+  /// times have no trend.
+  pub trend: Option<f64>,
+}
+
+impl Stats {
+  /// The stats of `step`, from how long each run took in milliseconds,
+  /// oldest first.
+  pub fn of(step: &str, runs: &[u64]) -> Self {
+    let mut sorted = runs.to_vec();
+    sorted.sort_unstable();
+    // The nearest rank, such that 95% of runs took no longer.
+    let p95 = sorted
+      .get((sorted.len() * 95).div_ceil(100).saturating_sub(1))
+      .copied()
+      .unwrap_or_default();
+
+    let trend = (runs.len() >= 4).then(|| {
+      let (older, newer) = runs.split_at(runs.len() / 2);
+      let older = mean(older);
+      if older == 0.0 { 0.0 } else { mean(newer) / older - 1.0 }
+    });
+
+    Self {
+      step: step.to_string(),
+      runs: runs.len(),
+      mean: Duration::from_secs_f64(mean(runs) / 1000.0),
+      p95: Duration::from_millis(p95),
+      trend,
+    }
+  }
+}
+
+fn mean(runs: &[u64]) -> f64 {
+  if runs.is_empty() {
+    return 0.0;
+  }
+  runs.iter().sum::<u64>() as f64 / runs.len() as f64
+}
+
+/// Prints the stats of each step of `workflow`, or of every subcommand and
+/// workflow which has been run in the project of the manifest at `path`.
+pub fn run(path: &Path, workflow: Option<&str>) -> AppResult<()> {
+  let history = History::load(path)?;
+  let sections: Vec<&str> = match workflow {
+    None => history.sections().collect(),
+    Some(name) => {
+      let key = if SECTIONS.contains(&name) {
+        name.to_string()
+      } else {
+        format!("workflows/{name}")
+      };
+      match history.sections().find(|section| *section == key) {
+        Some(section) => vec![section],
+        None => return AppResult::Err(Some(format!("No step timings recorded for `{name}`.").into())),
+      }
+    }
+  };
+
+  let output = if sections.is_empty() {
+    "No step timings recorded yet.".to_string()
+  } else {
+    sections
+      .iter()
+      .map(|section| {
+        let mut stats: Vec<Stats> = history
+          .steps(section)
+          .into_iter()
+          .flatten()
+          .map(|(step, runs)| Stats::of(step, runs))
+          .collect();
+        stats.sort_by_key(|stats| std::cmp::Reverse(stats.mean));
+        format!(
+          "{}\n{}\n",
+          OptionalStyled::new(*section, current_theme().highlight_style.clone()),
+          table(&stats).join("\n")
+        )
+      })
+      .collect::<Vec<_>>()
+      .join("\n")
+  };
+
+  match writeln!(std::io::stdout(), "{}", output.trim_end()) {
+    Err(err) if err.kind() != ErrorKind::BrokenPipe => AppResult::Err(Some(err.into())),
+    _ => AppResult::Ok(()),
+  }
+}
+
+/// The lines of a table of `stats`, beneath a header.
+pub fn table(stats: &[Stats]) -> Vec<String> {
+  let width = stats
+    .iter()
+    .map(|stats| stats.step.chars().count())
+    .chain([4])
+    .max()
+    .unwrap_or_default();
+
+  let header = format!(
+    "  {:width$}  {:>4}  {:>7}  {:>7}  {:>6}",
+    "Step", "runs", "mean", "p95", "trend"
+  );
+  let mut lines = vec![OptionalStyled::new(header, current_theme().dim_style.clone()).to_string()];
+  for stats in stats {
+    lines.push(format!(
+      "  {:width$}  {:>4}  {:>7}  {:>7}  {:>6}",
+      stats.step,
+      stats.runs,
+      elapsed(stats.mean),
+      elapsed(stats.p95),
+      trend(stats.trend)
+    ));
+  }
+  lines
+}
+
+fn trend(trend: Option<f64>) -> String {
+  match trend {
+    None => "—".into(),
+    Some(trend) if trend.abs() < STEADY => "steady".into(),
+    Some(trend) => format!("{:+.0}%", trend * 100.0),
+  }
+}
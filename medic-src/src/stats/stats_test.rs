@@ -0,0 +1,60 @@
+// @related [subject](medic-src/src/stats/mod.rs)
+
+use super::{Stats, table};
+use std::sync::Once;
+use std::time::Duration;
+
+static INIT: Once = Once::new();
+pub fn initialize() {
+  INIT.call_once(|| {
+    if crate::theme::THEME.get().is_none() {
+      let theme = crate::theme::dark_theme();
+      crate::theme::set_theme(theme);
+    }
+  });
+}
+
+#[test]
+fn test_stats_of_runs() {
+  let stats = Stats::of("Unit", &[1_000, 1_000, 3_000, 3_000]);
+  assert_eq!(stats.runs, 4);
+  assert_eq!(stats.mean, Duration::from_secs(2));
+  assert_eq!(stats.p95, Duration::from_secs(3));
+  assert_eq!(stats.trend, Some(2.0));
+}
+
+#[test]
+fn test_stats_p95_ignores_the_slowest_runs() {
+  let runs: Vec<u64> = (1..=40).chain([1_000]).collect();
+  assert_eq!(Stats::of("Unit", &runs).p95, Duration::from_millis(39));
+}
+
+#[test]
+fn test_stats_without_enough_runs_have_no_trend() {
+  let stats = Stats::of("Unit", &[1_000, 2_000, 3_000]);
+  assert_eq!(stats.trend, None);
+  assert_eq!(stats.p95, Duration::from_secs(3));
+}
+
+#[test]
+fn test_table() {
+  initialize();
+  let lines: Vec<String> = table(&[
+    Stats::of("Integration", &[60_000, 62_000, 70_000, 72_000]),
+    Stats::of("Unit", &[1_000, 1_000, 1_000, 1_020]),
+    Stats::of("Lint", &[500]),
+  ])
+  .iter()
+  .map(|line| console::strip_ansi_codes(line).to_string())
+  .collect();
+
+  assert_eq!(
+    lines,
+    vec![
+      "  Step         runs     mean      p95   trend",
+      "  Integration     4   1m 06s   1m 12s    +16%",
+      "  Unit            4     1.0s     1.0s  steady",
+      "  Lint            1     0.5s     0.5s       —",
+    ]
+  );
+}
@@ -0,0 +1,99 @@
+// @related [tests](medic-src/src/step/graph_test.rs)
+
+use crate::runnable::Runnable;
+use std::collections::BTreeMap;
+
+/// For each of `steps`, the indexes of the steps named in its `depends_on`.
+/// Ids are only visible to other steps of the same list.
+pub fn dependencies<T: Runnable>(steps: &[T], section_name: &str) -> Result<Vec<Vec<usize>>, String> {
+  let mut ids = BTreeMap::new();
+  for (index, step) in steps.iter().enumerate() {
+    if let Some(id) = step.id()
+      && ids.insert(id, index).is_some()
+    {
+      return Err(format!("Duplicate id `{id}` in [{section_name}]"));
+    }
+  }
+
+  steps
+    .iter()
+    .map(|step| {
+      step
+        .depends_on()
+        .iter()
+        .map(|dependency| {
+          ids
+            .get(dependency.as_str())
+            .copied()
+            .ok_or_else(|| match step.id() {
+              Some(id) => format!("Step `{id}` in [{section_name}] depends on unknown id `{dependency}`"),
+              None => format!("Unknown id `{dependency}` in `depends_on` of [{section_name}]"),
+            })
+        })
+        .collect()
+    })
+    .collect()
+}
+
+/// Why a step is skipped when `dependency`, a step it depends on, did not
+/// succeed.
+pub fn skip_reason(dependency: &impl Runnable) -> String {
+  format!(
+    "depends on `{}`, which did not succeed",
+    dependency.id().unwrap_or_default()
+  )
+}
+
+/// Reorders `steps` so that each comes after the steps it depends on, keeping
+/// the declared order otherwise.
+pub fn sort<T: Runnable>(steps: &mut Vec<T>, section_name: &str) -> Result<(), String> {
+  let dependencies = dependencies(steps, section_name)?;
+  if dependencies.iter().all(Vec::is_empty) {
+    return Ok(());
+  }
+
+  let mut placed = vec![false; steps.len()];
+  let mut order = vec![];
+  while order.len() < steps.len() {
+    let next = (0..steps.len()).find(|&index| !placed[index] && dependencies[index].iter().all(|&dep| placed[dep]));
+    let Some(next) = next else {
+      let cycle: Vec<&str> = cycle(&dependencies, &placed)
+        .into_iter()
+        .map(|index| steps[index].id().unwrap_or_default())
+        .collect();
+      return Err(format!(
+        "Dependency cycle detected in [{section_name}]: {}",
+        cycle.join(" -> ")
+      ));
+    };
+    placed[next] = true;
+    order.push(next);
+  }
+
+  let mut slots: Vec<Option<T>> = steps.drain(..).map(Some).collect();
+  steps.extend(order.into_iter().filter_map(|index| slots[index].take()));
+  Ok(())
+}
+
+// Every step left unplaced waits on another unplaced step, so following
+// dependencies from any of them must eventually revisit a step.
+fn cycle(dependencies: &[Vec<usize>], placed: &[bool]) -> Vec<usize> {
+  let mut path: Vec<usize> = vec![];
+  let mut current = placed.iter().position(|placed| !placed).unwrap_or_default();
+
+  while !path.contains(&current) {
+    path.push(current);
+    match dependencies[current].iter().find(|&&dep| !placed[dep]) {
+      Some(&dep) => current = dep,
+      None => return path,
+    }
+  }
+
+  let start = path
+    .iter()
+    .position(|&index| index == current)
+    .unwrap_or_default();
+  let mut cycle = path.split_off(start);
+  cycle.push(current);
+  cycle
+}
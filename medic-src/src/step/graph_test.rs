@@ -0,0 +1,115 @@
+// @related [subject](medic-src/src/step/graph.rs)
+
+use super::Step;
+use super::graph::*;
+use crate::runnable::Runnable;
+
+fn steps(toml: &str) -> Vec<Step> {
+  #[derive(serde::Deserialize)]
+  struct List {
+    steps: Vec<Step>,
+  }
+  toml::from_str::<List>(toml).unwrap().steps
+}
+
+fn ids(steps: &[Step]) -> Vec<&str> {
+  steps.iter().map(|step| step.id().unwrap_or("-")).collect()
+}
+
+#[test]
+fn dependencies_finds_indexes() {
+  let steps = steps(
+    r#"steps = [
+      { id = "build", name = "Build", shell = "make" },
+      { name = "Lint", shell = "make lint" },
+      { id = "test", name = "Test", shell = "make test", depends_on = ["build"] },
+      { name = "Package", shell = "make package", depends_on = ["build", "test"] },
+    ]"#,
+  );
+
+  assert_eq!(
+    dependencies(&steps, "test").unwrap(),
+    vec![vec![], vec![], vec![0], vec![0, 2]]
+  );
+}
+
+#[test]
+fn dependencies_rejects_unknown_ids() {
+  let with_id = steps(r#"steps = [{ id = "test", name = "Test", shell = "make test", depends_on = ["biuld"] }]"#);
+  assert_eq!(
+    dependencies(&with_id, "test").unwrap_err(),
+    "Step `test` in [test] depends on unknown id `biuld`"
+  );
+
+  let without_id = steps(r#"steps = [{ step = "git", command = "pull", depends_on = ["fetch"] }]"#);
+  assert_eq!(
+    dependencies(&without_id, "update").unwrap_err(),
+    "Unknown id `fetch` in `depends_on` of [update]"
+  );
+}
+
+#[test]
+fn dependencies_rejects_duplicate_ids() {
+  let steps = steps(
+    r#"steps = [
+      { id = "build", name = "Build", shell = "make" },
+      { id = "build", check = "rust" },
+    ]"#,
+  );
+
+  assert_eq!(
+    dependencies(&steps, "doctor").unwrap_err(),
+    "Duplicate id `build` in [doctor]"
+  );
+}
+
+#[test]
+fn sort_places_steps_after_dependencies() {
+  let mut steps = steps(
+    r#"steps = [
+      { id = "test", name = "Test", shell = "make test", depends_on = ["build"] },
+      { id = "lint", name = "Lint", shell = "make lint" },
+      { id = "build", name = "Build", shell = "make", depends_on = ["deps"] },
+      { id = "deps", name = "Deps", shell = "make deps" },
+    ]"#,
+  );
+
+  sort(&mut steps, "test").unwrap();
+  assert_eq!(ids(&steps), vec!["lint", "deps", "build", "test"]);
+}
+
+#[test]
+fn sort_keeps_declared_order_without_dependencies() {
+  let mut steps = steps(
+    r#"steps = [
+      { id = "b", name = "B", shell = "true" },
+      { id = "a", name = "A", shell = "true" },
+    ]"#,
+  );
+
+  sort(&mut steps, "test").unwrap();
+  assert_eq!(ids(&steps), vec!["b", "a"]);
+}
+
+#[test]
+fn sort_rejects_cycles() {
+  let mut steps = steps(
+    r#"steps = [
+      { id = "lint", name = "Lint", shell = "make lint" },
+      { id = "a", name = "A", shell = "true", depends_on = ["b"] },
+      { id = "b", name = "B", shell = "true", depends_on = ["c"] },
+      { id = "c", name = "C", shell = "true", depends_on = ["a"] },
+    ]"#,
+  );
+
+  assert_eq!(
+    sort(&mut steps, "workflows.release").unwrap_err(),
+    "Dependency cycle detected in [workflows.release]: a -> b -> c -> a"
+  );
+
+  let mut itself = self::steps(r#"steps = [{ id = "a", name = "A", shell = "true", depends_on = ["a"] }]"#);
+  assert_eq!(
+    sort(&mut itself, "test").unwrap_err(),
+    "Dependency cycle detected in [test]: a -> a"
+  );
+}
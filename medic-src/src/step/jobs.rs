@@ -0,0 +1,111 @@
+// @related [tests](medic-src/src/step/jobs_test.rs)
+
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde::Deserialize;
+use serde::de::{self, Deserializer, Visitor};
+use std::borrow::Cow;
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+
+const DEFAULT_JOBS: usize = 5;
+
+/// The number of nested steps that may run at once with `--parallel`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Jobs {
+  /// One job per available CPU.
+  Auto,
+  Count(NonZeroUsize),
+}
+
+impl Jobs {
+  pub fn count(&self) -> usize {
+    match self {
+      Jobs::Auto => std::thread::available_parallelism().map_or(1, NonZeroUsize::get),
+      Jobs::Count(count) => count.get(),
+    }
+  }
+}
+
+impl Default for Jobs {
+  fn default() -> Self {
+    Jobs::Count(NonZeroUsize::new(DEFAULT_JOBS).unwrap())
+  }
+}
+
+impl FromStr for Jobs {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    if s == "auto" {
+      return Ok(Jobs::Auto);
+    }
+    s.parse::<NonZeroUsize>()
+      .map(Jobs::Count)
+      .map_err(|_| format!("invalid jobs `{s}`; expected a positive number or `auto`"))
+  }
+}
+
+impl fmt::Display for Jobs {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Jobs::Auto => write!(f, "auto"),
+      Jobs::Count(count) => write!(f, "{count}"),
+    }
+  }
+}
+
+impl<'de> Deserialize<'de> for Jobs {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    struct JobsVisitor;
+
+    impl Visitor<'_> for JobsVisitor {
+      type Value = Jobs;
+
+      fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a positive number or \"auto\"")
+      }
+
+      fn visit_i64<E: de::Error>(self, value: i64) -> Result<Jobs, E> {
+        usize::try_from(value)
+          .ok()
+          .and_then(NonZeroUsize::new)
+          .map(Jobs::Count)
+          .ok_or_else(|| E::invalid_value(de::Unexpected::Signed(value), &self))
+      }
+
+      fn visit_u64<E: de::Error>(self, value: u64) -> Result<Jobs, E> {
+        usize::try_from(value)
+          .ok()
+          .and_then(NonZeroUsize::new)
+          .map(Jobs::Count)
+          .ok_or_else(|| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+      }
+
+      fn visit_str<E: de::Error>(self, value: &str) -> Result<Jobs, E> {
+        match value {
+          "auto" => Ok(Jobs::Auto),
+          _ => Err(E::invalid_value(de::Unexpected::Str(value), &self)),
+        }
+      }
+    }
+
+    deserializer.deserialize_any(JobsVisitor)
+  }
+}
+
+impl JsonSchema for Jobs {
+  fn schema_name() -> Cow<'static, str> {
+    "Jobs".into()
+  }
+
+  fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+      "description": "A number of steps, or \"auto\" for one per available CPU.",
+      "anyOf": [
+        { "type": "integer", "minimum": 1 },
+        { "const": "auto" },
+      ],
+    })
+  }
+}
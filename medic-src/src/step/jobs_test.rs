@@ -0,0 +1,59 @@
+// @related [subject](medic-src/src/step/jobs.rs)
+
+use super::Jobs;
+use super::{Step, StepGroup};
+use std::num::NonZeroUsize;
+
+fn count(n: usize) -> Jobs {
+  Jobs::Count(NonZeroUsize::new(n).unwrap())
+}
+
+#[test]
+fn test_from_str() {
+  assert_eq!("3".parse::<Jobs>(), Ok(count(3)));
+  assert_eq!("auto".parse::<Jobs>(), Ok(Jobs::Auto));
+  assert_eq!(
+    "0".parse::<Jobs>(),
+    Err("invalid jobs `0`; expected a positive number or `auto`".to_string())
+  );
+  assert!("many".parse::<Jobs>().is_err());
+}
+
+#[test]
+fn test_count() {
+  assert_eq!(Jobs::default().count(), 5);
+  assert_eq!(count(12).count(), 12);
+  assert!(Jobs::Auto.count() >= 1);
+}
+
+#[test]
+fn test_deserialize_group() {
+  #[derive(serde::Deserialize)]
+  struct List {
+    steps: Vec<Step>,
+  }
+
+  let toml = r#"
+    steps = [
+      { jobs = 2, steps = [{ name = "One", shell = "true" }] },
+      { jobs = "auto", steps = [] },
+      { steps = [] },
+    ]
+  "#;
+
+  let steps = toml::from_str::<List>(toml).unwrap().steps;
+  let jobs: Vec<Option<Jobs>> = steps
+    .iter()
+    .map(|step| match step {
+      Step::Group(StepGroup { jobs, .. }) => *jobs,
+      step => panic!("Expected a group of steps, got {step:?}"),
+    })
+    .collect();
+  assert_eq!(jobs, vec![Some(count(2)), Some(Jobs::Auto), None]);
+}
+
+#[test]
+fn test_deserialize_group_invalid_jobs() {
+  let result = toml::from_str::<StepGroup>("jobs = 0\nsteps = []");
+  assert!(result.is_err());
+}
@@ -1,21 +1,33 @@
 #[cfg(test)]
+mod graph_test;
+#[cfg(test)]
+mod jobs_test;
+#[cfg(test)]
 mod step_config_test;
+#[cfg(test)]
+mod step_test;
 
+pub mod graph;
+pub mod jobs;
 pub mod step_config;
+pub use jobs::Jobs;
 pub use step_config::StepConfig;
 
 use crate::cli::Flags;
+use crate::condition::Condition;
 use crate::config;
 use crate::context::Context;
 use crate::error::MedicError;
 use crate::noop_config::NoopConfig;
 use crate::optional_styled::OptionalStyled;
 use crate::recoverable::Recoverable;
-use crate::runnable::Runnable;
+use crate::report;
+use crate::runnable::{self, Runnable};
 use crate::semaphore::Semaphore;
 use crate::shell::ShellConfig;
 use crate::theme::current_theme;
 use crate::{AppResult, Check};
+use schemars::JsonSchema;
 use serde::Deserialize;
 use std::fmt;
 use std::process::Command;
@@ -23,13 +35,14 @@ use std::sync::Arc;
 use std::sync::mpsc;
 use std::thread;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq)]
 #[serde(untagged)]
 pub enum Step {
   Check(Check),
   Shell(ShellConfig),
   Step(StepConfig),
   Doctor(DoctorConfig),
+  Group(StepGroup),
   Steps(Vec<Step>),
 }
 
@@ -38,16 +51,73 @@ impl Runnable for Step {
     match self {
       Step::Check(config) => config.allow_failure(),
       Step::Doctor(_) => false,
+      Step::Group(_) => false,
       Step::Shell(config) => config.allow_failure(),
       Step::Step(config) => config.allow_failure(),
       Step::Steps(_) => false,
     }
   }
 
+  fn condition(&self) -> Condition<'_> {
+    match self {
+      Step::Check(config) => config.condition(),
+      Step::Doctor(_) => Condition::default(),
+      Step::Group(_) => Condition::default(),
+      Step::Shell(config) => config.condition(),
+      Step::Step(config) => config.condition(),
+      Step::Steps(_) => Condition::default(),
+    }
+  }
+
+  fn depends_on(&self) -> &[String] {
+    match self {
+      Step::Check(config) => config.depends_on(),
+      Step::Shell(config) => config.depends_on(),
+      Step::Step(config) => config.depends_on(),
+      Step::Doctor(_) => &[],
+      Step::Group(_) => &[],
+      Step::Steps(_) => &[],
+    }
+  }
+
+  fn id(&self) -> Option<&str> {
+    match self {
+      Step::Check(config) => config.id(),
+      Step::Shell(config) => config.id(),
+      Step::Step(config) => config.id(),
+      Step::Doctor(_) => None,
+      Step::Group(_) => None,
+      Step::Steps(_) => None,
+    }
+  }
+
+  fn inputs(&self) -> &[String] {
+    match self {
+      Step::Check(config) => config.inputs(),
+      Step::Doctor(_) => &[],
+      Step::Group(_) => &[],
+      Step::Shell(config) => config.inputs(),
+      Step::Step(config) => config.inputs(),
+      Step::Steps(_) => &[],
+    }
+  }
+
+  fn name(&self) -> Option<&str> {
+    match self {
+      Step::Check(config) => config.name(),
+      Step::Doctor(_) => None,
+      Step::Group(_) => None,
+      Step::Shell(config) => config.name(),
+      Step::Step(config) => config.name(),
+      Step::Steps(_) => None,
+    }
+  }
+
   fn platform(&self) -> &Option<Vec<String>> {
     match self {
       Step::Check(config) => config.platform(),
       Step::Doctor(_) => &None,
+      Step::Group(_) => &None,
       Step::Shell(config) => config.platform(),
       Step::Step(config) => config.platform(),
       Step::Steps(_) => &None,
@@ -58,15 +128,21 @@ impl Runnable for Step {
     match self {
       Step::Check(config) => config.run(progress, flags, context),
       Step::Doctor(config) => config.run(progress, flags, context),
+      Step::Group(group) => run_steps(&group.steps, group.jobs, progress, flags, context),
       Step::Shell(config) => config.run(progress, flags, context),
       Step::Step(config) => config.run(progress, flags, context),
-      Step::Steps(steps) => {
-        if flags.parallel {
-          run_parallel_steps(steps, progress, flags, context)
-        } else {
-          run_serial_steps(steps, progress, flags, context)
-        }
-      }
+      Step::Steps(steps) => run_steps(steps, None, progress, flags, context),
+    }
+  }
+
+  fn tags(&self) -> &[String] {
+    match self {
+      Step::Check(config) => config.tags(),
+      Step::Doctor(_) => &[],
+      Step::Group(_) => &[],
+      Step::Shell(config) => config.tags(),
+      Step::Step(config) => config.tags(),
+      Step::Steps(_) => &[],
     }
   }
 
@@ -76,7 +152,7 @@ impl Runnable for Step {
       Step::Doctor(config) => config.to_command(),
       Step::Shell(config) => config.to_command(),
       Step::Step(config) => config.to_command(),
-      Step::Steps(_) => Err(MedicError::Message(
+      Step::Group(_) | Step::Steps(_) => Err(MedicError::Message(
         "Steps cannot be converted to a single command".to_string(),
       )),
     }
@@ -86,6 +162,7 @@ impl Runnable for Step {
     match self {
       Step::Check(config) => config.verbose,
       Step::Doctor(_) => true,
+      Step::Group(_) => false,
       Step::Shell(config) => config.verbose,
       Step::Step(config) => config.verbose,
       Step::Steps(_) => false,
@@ -100,7 +177,7 @@ impl fmt::Display for Step {
       Step::Doctor(config) => config.fmt(f),
       Step::Shell(config) => config.fmt(f),
       Step::Step(config) => config.fmt(f),
-      Step::Steps(_) => write!(
+      Step::Group(_) | Step::Steps(_) => write!(
         f,
         "{}",
         OptionalStyled::new("== Nested Steps ==", current_theme().text_style.clone())
@@ -109,7 +186,8 @@ impl fmt::Display for Step {
   }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq)]
+#[schemars(rename = "NestedDoctor")]
 pub struct DoctorConfig {
   pub doctor: NoopConfig,
 }
@@ -151,32 +229,114 @@ impl std::fmt::Display for DoctorConfig {
   }
 }
 
-const MAX_PARALLEL: usize = 5;
+/// A nested list of steps with options of its own, written as
+/// `{ jobs = 2, steps = [...] }`.
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[schemars(rename = "NestedSteps")]
+pub struct StepGroup {
+  /// Maximum number of these steps to run at once with `--parallel`,
+  /// overriding `--jobs`.
+  pub jobs: Option<Jobs>,
+  /// Steps which run together, in parallel with `--parallel`.
+  pub steps: Vec<Step>,
+}
+
+fn run_steps(
+  steps: &[Step],
+  jobs: Option<Jobs>,
+  progress: &mut retrogress::ProgressBar,
+  flags: &mut Flags,
+  context: &Context,
+) -> Recoverable<()> {
+  if flags.parallel && !flags.dry_run {
+    run_parallel_steps(steps, jobs.unwrap_or(flags.jobs), progress, flags, context)
+  } else {
+    run_serial_steps(steps, progress, flags, context)
+  }
+}
 
+#[derive(Clone, Copy, PartialEq)]
+enum Status {
+  Pending,
+  Running,
+  Succeeded,
+  Failed,
+}
+
+// Steps start as soon as the steps they depend on have passed. Steps that
+// depend on a step that did not pass, whether it failed or was skipped, are
+// skipped, as are their own dependents.
 fn run_parallel_steps(
-  steps: &Vec<Step>,
+  steps: &[Step],
+  jobs: Jobs,
   progress: &mut retrogress::ProgressBar,
   flags: &mut Flags,
   context: &Context,
 ) -> Recoverable<()> {
+  let dependencies = match graph::dependencies(steps, "steps") {
+    Ok(dependencies) => dependencies,
+    Err(err) => return Recoverable::Nonrecoverable(err.into()),
+  };
   let (tx, rx) = mpsc::channel();
-  let semaphore = Arc::new(Semaphore::new(MAX_PARALLEL));
+  let semaphore = Arc::new(Semaphore::new(jobs.count()));
+  let mut status = vec![Status::Pending; steps.len()];
+  let mut results = vec![];
+  let mut kept = 0;
 
   thread::scope(|s| {
-    for step in steps {
-      let mut progress = progress.clone();
-      let tx = tx.clone();
-      let mut flags = flags.clone();
-      let sem = Arc::clone(&semaphore);
+    loop {
+      let quit = results
+        .iter()
+        .any(|result| matches!(result, Recoverable::Quit));
+      let mut changed = true;
+      while changed {
+        changed = false;
+        for (index, step) in steps.iter().enumerate() {
+          if status[index] != Status::Pending {
+            continue;
+          }
+          if let Some(&failed) = dependencies[index]
+            .iter()
+            .find(|&&dep| status[dep] == Status::Failed)
+          {
+            status[index] = Status::Failed;
+            changed = true;
+            runnable::print_skipped(step, &graph::skip_reason(&steps[failed]), progress);
+          } else if !quit
+            && dependencies[index]
+              .iter()
+              .all(|&dep| status[dep] == Status::Succeeded)
+          {
+            status[index] = Status::Running;
 
-      s.spawn(move || {
-        let _permit = sem.acquire();
-        let result = step.run(&mut progress, &mut flags, context);
-        let _ = tx.send(result);
-      });
-    }
+            let mut progress = progress.clone();
+            let tx = tx.clone();
+            let mut flags = flags.clone();
+            let sem = Arc::clone(&semaphore);
+
+            s.spawn(move || {
+              let _permit = sem.acquire();
+              let (result, ended) = runnable::run_unless_skipped_with_status(step, &mut progress, &mut flags, context);
+              let passed = runnable::passed(step, ended.as_ref(), &flags);
+              let _ = tx.send((index, result, passed));
+            });
+          }
+        }
+      }
 
-    drop(tx);
+      if !status.contains(&Status::Running) {
+        break;
+      }
+      if let Ok((index, result, passed)) = rx.recv() {
+        status[index] = if passed { Status::Succeeded } else { Status::Failed };
+        if keep_going(&steps[index], &result, flags) {
+          kept += 1;
+        } else {
+          results.push(result);
+        }
+      }
+    }
   });
 
   let mut quit = None;
@@ -185,7 +345,7 @@ fn run_parallel_steps(
   let mut nonrecoverable = None;
   let mut optional = None;
 
-  while let Ok(result) = rx.recv() {
+  for result in results {
     match result {
       Recoverable::Err(_, _) => failure = Some(result),
       Recoverable::Manual(_, _) => manual = Some(result),
@@ -205,6 +365,9 @@ fn run_parallel_steps(
   if let Some(failure) = failure {
     return failure;
   }
+  if kept > 0 {
+    return Recoverable::Err(Some(report::failed_steps(kept)), None);
+  }
   if let Some(manual) = manual {
     return manual;
   }
@@ -214,14 +377,55 @@ fn run_parallel_steps(
   Recoverable::Ok(())
 }
 
+// Steps are sorted by their dependencies when the manifest is loaded, so a
+// step's dependencies have always run before it. Dependents are left to
+// skip by steps which were skipped themselves, and by failures that allow
+// the list to continue or failures with `--keep-going`.
 fn run_serial_steps(
-  steps: &Vec<Step>,
+  steps: &[Step],
   progress: &mut retrogress::ProgressBar,
   flags: &mut Flags,
   context: &Context,
 ) -> Recoverable<()> {
-  for step in steps {
-    step.run(progress, flags, context)?;
+  let dependencies = match graph::dependencies(steps, "steps") {
+    Ok(dependencies) => dependencies,
+    Err(err) => return Recoverable::Nonrecoverable(err.into()),
+  };
+  let mut failed = vec![false; steps.len()];
+  let mut kept = 0;
+
+  for (index, step) in steps.iter().enumerate() {
+    if let Some(&dep) = dependencies[index].iter().find(|&&dep| failed[dep]) {
+      failed[index] = true;
+      runnable::print_skipped(step, &graph::skip_reason(&steps[dep]), progress);
+      continue;
+    }
+    let (result, status) = runnable::run_unless_skipped_with_status(step, progress, flags, context);
+    failed[index] = !runnable::passed(step, status.as_ref(), flags);
+    if keep_going(step, &result, flags) {
+      kept += 1;
+      continue;
+    }
+    result?;
+  }
+  if kept > 0 {
+    return Recoverable::Err(Some(report::failed_steps(kept)), None);
   }
   Recoverable::Ok(())
 }
+
+// With `--keep-going`, a step in a nested list which fails is saved to be
+// reported at the end of the run, and the rest of the list still runs.
+// Returns whether the failure was kept.
+fn keep_going(step: &Step, result: &Recoverable<()>, flags: &Flags) -> bool {
+  let Recoverable::Err(err, remedy) = result else {
+    return false;
+  };
+  if !flags.keep_going {
+    return false;
+  }
+  if step.name().is_some() {
+    report::failed(&step.to_string(), err.as_ref(), remedy.as_ref());
+  }
+  true
+}
@@ -1,91 +1,169 @@
 // @related [tests](medic-src/src/step/step_config_test.rs)
 
 use crate::cli::Flags;
+use crate::condition::{Condition, EnvCondition};
 use crate::context::Context;
 use crate::error::MedicError;
 use crate::optional_styled::OptionalStyled;
 use crate::recoverable::Recoverable;
+use crate::retry::Retry;
 use crate::runnable::Runnable;
 use crate::theme::current_theme;
-use crate::util::StringOrList;
+use crate::util::{HumanDuration, StringOrList};
 use crate::{extra, std_to_string};
 
 use console::{Style, style};
+use schemars::JsonSchema;
 use serde::Deserialize;
 use std::collections::BTreeMap;
 use std::fmt;
 use std::io::{BufRead, BufReader};
 use std::process::{Command, Stdio};
 use std::thread;
+use std::time::Duration;
 use which::which;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq)]
 pub struct StepConfig {
+  /// Flags passed to the command as `--flag <value>`, once per value when given a list.
   pub args: Option<BTreeMap<String, StringOrList>>,
+  /// Directory to change into before running.
   pub cd: Option<String>,
+  /// A subcommand passed as the first argument.
   pub command: Option<String>,
+  /// Ids of steps in the same list which must succeed before this one runs.
+  /// When one of them fails or is skipped, this step is skipped too.
+  #[serde(default)]
+  pub depends_on: Vec<String>,
+  /// Environment variables to set when running.
   #[serde(default)]
   pub env: BTreeMap<String, String>,
+  /// Identifies the step in the `depends_on` of other steps in the same list.
+  pub id: Option<String>,
+  /// A shell command which must succeed for this to run, for example `"test -f package.json"`.
+  pub r#if: Option<String>,
+  /// Environment variables which must be set, or a table of values they must have, for this to run.
+  pub if_env: Option<EnvCondition>,
+  /// Files, or glob patterns, whose contents decide whether the step is cached. Relative to the project directory.
+  #[serde(default)]
+  pub inputs: Vec<String>,
+  /// Description shown when running, in place of the command.
   pub name: Option<String>,
+  /// Platforms on which to run, for example `Darwin`. Runs everywhere when empty.
   pub platform: Option<Vec<String>>,
+  /// Number of times to run the command again when it fails.
+  #[serde(default)]
+  pub retries: u32,
+  /// Double `retry_delay` after each retry, up to a minute.
+  #[serde(default)]
+  pub retry_backoff: bool,
+  /// How long to wait before retrying, for example `"2s"`. Defaults to one second.
+  pub retry_delay: Option<HumanDuration>,
+  /// Tags selected by `--only tag:<tag>` and `--skip tag:<tag>`.
+  #[serde(default)]
+  pub tags: Vec<String>,
+  /// Name of the step. Runs `medic-step-{step}` from the PATH.
   pub step: String,
+  /// Kill the command and fail when it runs longer than this, for example `"90s"`. Overrides `--timeout`.
+  pub timeout: Option<HumanDuration>,
+  /// A shell command which must fail for this to run.
+  pub unless: Option<String>,
+  /// Environment variables which must not be set, or a table of values they must not have, for this to run.
+  pub unless_env: Option<EnvCondition>,
+  /// Print output to the terminal as it happens.
   #[serde(default)]
   pub verbose: bool,
 }
 
 impl Runnable for StepConfig {
+  fn condition(&self) -> Condition<'_> {
+    Condition {
+      cd: self.cd.as_deref(),
+      env: Some(&self.env),
+      r#if: self.r#if.as_deref(),
+      if_env: self.if_env.as_ref(),
+      unless: self.unless.as_deref(),
+      unless_env: self.unless_env.as_ref(),
+    }
+  }
+
+  fn depends_on(&self) -> &[String] {
+    &self.depends_on
+  }
+
+  fn id(&self) -> Option<&str> {
+    self.id.as_deref()
+  }
+
+  fn inputs(&self) -> &[String] {
+    &self.inputs
+  }
+
+  fn name(&self) -> Option<&str> {
+    Some(self.name.as_deref().unwrap_or(&self.step))
+  }
+
   fn platform(&self) -> &Option<Vec<String>> {
     &self.platform
   }
 
-  fn run(&self, progress: &mut retrogress::ProgressBar, _flags: &mut Flags, _ctx: &Context) -> Recoverable<()> {
+  fn run(&self, progress: &mut retrogress::ProgressBar, flags: &mut Flags, _ctx: &Context) -> Recoverable<()> {
     let allow_failure = self.allow_failure();
     let verbose = self.verbose();
+    let timeout = self.timeout.map(Duration::from).or(flags.timeout);
+    let retry = Retry::new(self.retries, self.retry_delay, self.retry_backoff);
     let pb = progress.append(&self.to_string());
 
     match self.to_command() {
       Ok(mut command) => {
-        let output = if verbose {
-          command
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
-
-          let mut child = command.spawn()?;
-          let stderr = child
-            .stderr
-            .take()
-            .ok_or("Error capturing stderr of step.")?;
-          let stdout = child
-            .stdout
-            .take()
-            .ok_or("Error capturing stdout of step.")?;
-
-          let mut out_progress = progress.clone();
-          let mut err_progress = progress.clone();
-
-          let out_thr = thread::spawn(move || {
-            let reader = BufReader::new(stdout);
-            reader
-              .lines()
-              .map_while(Result::ok)
-              .for_each(|line| out_progress.println(pb, &line));
-          });
-          let err_thr = thread::spawn(move || {
-            let reader = BufReader::new(stderr);
-            reader
-              .lines()
-              .map_while(Result::ok)
-              .for_each(|line| err_progress.println(pb, &line));
-          });
-
-          let res = child.wait_with_output();
-          out_thr.join().unwrap();
-          err_thr.join().unwrap();
-          res
-        } else {
-          command.output()
-        };
+        let output = retry.run(progress, pb, &self.to_string(), |progress| {
+          if verbose {
+            command
+              .stdin(Stdio::piped())
+              .stdout(Stdio::piped())
+              .stderr(Stdio::piped());
+
+            let mut child = extra::command::spawn(&mut command, timeout, false)?;
+            let stderr = child
+              .stderr
+              .take()
+              .ok_or("Error capturing stderr of step.")?;
+            let stdout = child
+              .stdout
+              .take()
+              .ok_or("Error capturing stdout of step.")?;
+
+            let mut out_progress = progress.clone();
+            let mut err_progress = progress.clone();
+
+            let out_thr = thread::spawn(move || {
+              let reader = BufReader::new(stdout);
+              reader
+                .lines()
+                .map_while(Result::ok)
+                .for_each(|line| out_progress.println(pb, &line));
+            });
+            let err_thr = thread::spawn(move || {
+              let reader = BufReader::new(stderr);
+              reader
+                .lines()
+                .map_while(Result::ok)
+                .for_each(|line| err_progress.println(pb, &line));
+            });
+
+            let res = extra::command::wait(child, timeout);
+            out_thr.join().unwrap();
+            err_thr.join().unwrap();
+            res
+          } else {
+            command
+              .stdin(Stdio::null())
+              .stdout(Stdio::piped())
+              .stderr(Stdio::piped());
+            extra::command::output(&mut command, timeout, false)
+          }
+        });
+        let (output, timed_out) = extra::command::partial_output(output);
         match output {
           Ok(result) => {
             if result.status.success() {
@@ -98,7 +176,10 @@ impl Runnable for StepConfig {
                 (8u8 as char),
                 OptionalStyled::new("FAILED", current_theme().error_style.clone())
               );
-              let err = std_to_string(result.stderr);
+              let err = match timed_out {
+                Some(_) => std_to_string([result.stdout, result.stderr].concat()),
+                None => std_to_string(result.stderr),
+              };
               if !verbose && err.trim() != "" {
                 eprintln!(
                   "{}",
@@ -108,15 +189,21 @@ impl Runnable for StepConfig {
                 eprint!("{err}");
               }
               if allow_failure {
+                if let Some(err) = &timed_out {
+                  eprintln!(
+                    "{}",
+                    OptionalStyled::new(err.to_string(), current_theme().error_style.clone())
+                  );
+                }
                 Recoverable::Optional((), None)
               } else {
-                Recoverable::Err(None, None)
+                Recoverable::Err(timed_out, None)
               }
             }
           }
           Err(err) => {
             progress.failed(pb);
-            Recoverable::Err(Some(err.into()), None)
+            Recoverable::Err(Some(err), None)
           }
         }
       }
@@ -127,6 +214,10 @@ impl Runnable for StepConfig {
     }
   }
 
+  fn tags(&self) -> &[String] {
+    &self.tags
+  }
+
   fn to_command(&self) -> Result<Command, MedicError> {
     let step_cmd = format!("medic-step-{}", self.step);
     if let Err(_err) = which(&step_cmd) {
@@ -20,10 +20,22 @@ fn deserialize_cd() {
       args: None,
       cd: Some("./subdirectory".to_string()),
       command: None,
+      depends_on: vec![],
       env: BTreeMap::default(),
+      id: None,
+      r#if: None,
+      if_env: None,
+      inputs: vec![],
       name: None,
       platform: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
       step: "step-name".to_string(),
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
       verbose: false
     }
   )
@@ -46,10 +58,22 @@ fn deserialize_arg_string() {
       )])),
       cd: None,
       command: Some("subcommand".to_string()),
+      depends_on: vec![],
       env: BTreeMap::default(),
+      id: None,
+      r#if: None,
+      if_env: None,
+      inputs: vec![],
       name: None,
       platform: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
       step: "step-name".to_string(),
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
       verbose: false
     }
   )
@@ -72,10 +96,22 @@ fn deserialize_arg_list() {
       )])),
       cd: None,
       command: Some("subcommand".to_string()),
+      depends_on: vec![],
       env: BTreeMap::default(),
+      id: None,
+      r#if: None,
+      if_env: None,
+      inputs: vec![],
       name: None,
       platform: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
       step: "step-name".to_string(),
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
       verbose: false
     }
   )
@@ -94,13 +130,25 @@ fn deserialize_env() {
       args: None,
       cd: None,
       command: None,
+      depends_on: vec![],
       env: BTreeMap::from([
         ("MY_VAR".to_string(), "first".to_string()),
         ("SECOND_VAR".to_string(), "second".to_string())
       ]),
+      id: None,
+      r#if: None,
+      if_env: None,
+      inputs: vec![],
       name: None,
       platform: None,
+      retries: 0,
+      retry_backoff: false,
+      retry_delay: None,
       step: "step-name".to_string(),
+      tags: vec![],
+      timeout: None,
+      unless: None,
+      unless_env: None,
       verbose: false
     }
   )
@@ -112,10 +160,22 @@ fn to_command() {
     args: None,
     cd: None,
     command: None,
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     name: None,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
     step: "thing".to_string(),
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -139,10 +199,22 @@ fn to_command_cd() {
     args: None,
     cd: Some("../fixtures/bin".to_string()),
     command: None,
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     name: None,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
     step: "thing".to_string(),
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -160,10 +232,22 @@ fn to_command_subcommand() {
     args: None,
     cd: None,
     command: Some("sub-command".to_string()),
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     name: None,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
     step: "thing".to_string(),
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -185,10 +269,22 @@ fn to_command_args() {
     )])),
     cd: None,
     command: None,
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     name: None,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
     step: "thing".to_string(),
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -210,10 +306,22 @@ fn to_command_args_list() {
     )])),
     cd: None,
     command: None,
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     name: None,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
     step: "thing".to_string(),
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -237,13 +345,25 @@ fn to_command_env() {
     args: None,
     cd: None,
     command: None,
+    depends_on: vec![],
     env: BTreeMap::from([
       ("VAR".to_string(), "value".to_string()),
       ("OTHER".to_string(), "other".to_string()),
     ]),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     name: None,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
     step: "thing".to_string(),
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -264,10 +384,22 @@ fn to_command_missing_command() {
     args: None,
     cd: None,
     command: None,
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     name: None,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
     step: "missing".to_string(),
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -284,10 +416,22 @@ fn to_string_cd() {
     args: None,
     cd: Some("./subdirectory".to_string()),
     command: None,
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     name: None,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
     step: "step-name".to_string(),
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -306,10 +450,22 @@ fn to_string_single_arg() {
     )])),
     cd: None,
     command: None,
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     name: None,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
     step: "step-name".to_string(),
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -328,10 +484,22 @@ fn to_string_subcommand_single_arg() {
     )])),
     cd: None,
     command: Some("subcommand".to_string()),
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     name: None,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
     step: "step-name".to_string(),
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -350,10 +518,22 @@ fn to_string_subcommand_multiple_args() {
     ])),
     cd: None,
     command: Some("subcommand".to_string()),
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     name: None,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
     step: "step-name".to_string(),
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -372,10 +552,22 @@ fn to_string_subcommand_multiple_arg_values() {
     )])),
     cd: None,
     command: Some("subcommand".to_string()),
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     name: None,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
     step: "step-name".to_string(),
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
@@ -397,10 +589,22 @@ fn to_string_subcommand_multiple_arg_values_and_args() {
     ])),
     cd: None,
     command: Some("subcommand".to_string()),
+    depends_on: vec![],
     env: BTreeMap::default(),
+    id: None,
+    r#if: None,
+    if_env: None,
+    inputs: vec![],
     name: None,
     platform: None,
+    retries: 0,
+    retry_backoff: false,
+    retry_delay: None,
     step: "step-name".to_string(),
+    tags: vec![],
+    timeout: None,
+    unless: None,
+    unless_env: None,
     verbose: false,
   };
 
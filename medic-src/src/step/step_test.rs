@@ -0,0 +1,259 @@
+// @related [subject](medic-src/src/step/mod.rs)
+
+use super::Step;
+use crate::cli::Flags;
+use crate::context::Context;
+use crate::fixture;
+use crate::progress;
+use crate::recoverable::Recoverable;
+use crate::report;
+use crate::runnable::Runnable;
+use std::path::Path;
+use std::sync::Once;
+use tempfile::TempPath;
+
+static INIT: Once = Once::new();
+pub fn initialize() {
+  INIT.call_once(|| {
+    if crate::theme::THEME.get().is_none() {
+      let theme = crate::theme::dark_theme();
+      crate::theme::set_theme(theme);
+    }
+  });
+}
+
+fn log_path() -> TempPath {
+  tempfile::NamedTempFile::new().unwrap().into_temp_path()
+}
+
+fn nested(toml: &str, log: &Path) -> Step {
+  #[derive(serde::Deserialize)]
+  struct List {
+    steps: Vec<Step>,
+  }
+  let toml = toml.replace("LOG", &log.to_string_lossy());
+  let mut steps = toml::from_str::<List>(&toml).unwrap().steps;
+  super::graph::sort(&mut steps, "test").unwrap();
+  Step::Steps(steps)
+}
+
+fn run(step: &Step, parallel: bool) -> Recoverable<()> {
+  run_with(
+    step,
+    Flags {
+      parallel,
+      ..Flags::default()
+    },
+  )
+}
+
+fn run_with(step: &Step, mut flags: Flags) -> Recoverable<()> {
+  initialize();
+  let mut progress = progress::new(&flags);
+  step.run(&mut progress, &mut flags, &Context::new())
+}
+
+#[test]
+fn parallel_steps_run_after_dependencies() {
+  let log = log_path();
+  let step = nested(
+    r#"steps = [
+      { id = "test", name = "Test", shell = "echo test >> LOG", depends_on = ["build"] },
+      { id = "build", name = "Build", shell = "sleep 0.2 && echo build >> LOG" },
+    ]"#,
+    &log,
+  );
+
+  assert!(matches!(run(&step, true), Recoverable::Ok(())));
+  assert_eq!(std::fs::read_to_string(&log).unwrap(), "build\ntest\n");
+}
+
+#[test]
+fn parallel_steps_skip_dependents_of_failures() {
+  let log = log_path();
+  let step = nested(
+    r#"steps = [
+      { id = "build", name = "Build", shell = "false" },
+      { id = "test", name = "Test", shell = "echo test >> LOG", depends_on = ["build"] },
+      { id = "package", name = "Package", shell = "echo package >> LOG", depends_on = ["test"] },
+      { id = "lint", name = "Lint", shell = "echo lint >> LOG" },
+    ]"#,
+    &log,
+  );
+
+  assert!(matches!(run(&step, true), Recoverable::Err(_, _)));
+  assert_eq!(std::fs::read_to_string(&log).unwrap(), "lint\n");
+}
+
+#[test]
+fn serial_steps_skip_dependents_of_allowed_failures() {
+  let log = log_path();
+  let step = nested(
+    r#"steps = [
+      { id = "build", name = "Build", shell = "false", allow_failure = true },
+      { id = "test", name = "Test", shell = "echo test >> LOG", depends_on = ["build"] },
+      { id = "lint", name = "Lint", shell = "echo lint >> LOG" },
+    ]"#,
+    &log,
+  );
+
+  assert!(matches!(run(&step, false), Recoverable::Ok(())));
+  assert_eq!(std::fs::read_to_string(&log).unwrap(), "lint\n");
+}
+
+#[test]
+fn steps_skip_dependents_of_skipped_steps() {
+  for parallel in [false, true] {
+    let log = log_path();
+    let step = nested(
+      r#"steps = [
+        { id = "server", name = "Server", shell = "echo server >> LOG", if = "false" },
+        { id = "db", name = "Database", shell = "echo db >> LOG", tags = ["slow"] },
+        { name = "Integration", shell = "echo integration >> LOG", depends_on = ["server"] },
+        { name = "Migrate", shell = "echo migrate >> LOG", depends_on = ["db"] },
+        { name = "Lint", shell = "echo lint >> LOG" },
+      ]"#,
+      &log,
+    );
+    let flags = Flags {
+      parallel,
+      skip: vec!["tag:slow".parse().unwrap()],
+      ..Flags::default()
+    };
+
+    assert!(matches!(run_with(&step, flags), Recoverable::Ok(())));
+    assert_eq!(std::fs::read_to_string(&log).unwrap(), "lint\n");
+  }
+}
+
+#[test]
+fn parallel_groups_limit_jobs() {
+  let log = log_path();
+  let Step::Steps(mut steps) = nested(
+    r#"steps = [
+      { jobs = 1, steps = [
+        { name = "First", shell = "echo start >> LOG && sleep 0.1 && echo end >> LOG" },
+        { name = "Second", shell = "echo start >> LOG && sleep 0.1 && echo end >> LOG" },
+      ] },
+    ]"#,
+    &log,
+  ) else {
+    unreachable!();
+  };
+  let group = steps.remove(0);
+  assert!(matches!(group, Step::Group(_)));
+
+  assert!(matches!(run(&group, true), Recoverable::Ok(())));
+  assert_eq!(std::fs::read_to_string(&log).unwrap(), "start\nend\nstart\nend\n");
+}
+
+#[test]
+fn nested_steps_are_filtered_by_only_and_skip() {
+  for parallel in [false, true] {
+    let log = log_path();
+    let step = nested(
+      r#"steps = [
+        { name = "Lint", shell = "echo lint >> LOG", tags = ["fast"] },
+        [
+          { name = "Unit tests", shell = "echo unit >> LOG", tags = ["fast"] },
+          { name = "Browser tests", shell = "echo browser >> LOG", tags = ["fast", "slow"] },
+        ],
+        { name = "Integration tests", shell = "echo integration >> LOG" },
+      ]"#,
+      &log,
+    );
+    let flags = Flags {
+      only: vec!["tag:fast".parse().unwrap()],
+      parallel,
+      skip: vec!["tag:slow".parse().unwrap()],
+      ..Flags::default()
+    };
+
+    assert!(matches!(run_with(&step, flags), Recoverable::Ok(())));
+    let mut lines: Vec<String> = std::fs::read_to_string(&log)
+      .unwrap()
+      .lines()
+      .map(String::from)
+      .collect();
+    lines.sort();
+    assert_eq!(lines, vec!["lint", "unit"]);
+  }
+}
+
+#[test]
+fn nested_steps_check_conditions() {
+  let log = log_path();
+  let step = nested(
+    r#"steps = [
+      { name = "Skipped", shell = "echo skipped >> LOG", if = "false" },
+      { name = "Runs", shell = "echo runs >> LOG", unless = "false" },
+    ]"#,
+    &log,
+  );
+
+  assert!(matches!(run(&step, false), Recoverable::Ok(())));
+  assert_eq!(std::fs::read_to_string(&log).unwrap(), "runs\n");
+}
+
+#[test]
+fn nested_steps_keep_going_past_failures() {
+  let _run = fixture::keep_going();
+  for parallel in [false, true] {
+    let log = log_path();
+    let step = nested(
+      r#"steps = [
+        { id = "build", name = "Build", shell = "false" },
+        { name = "Test", shell = "echo test >> LOG", depends_on = ["build"] },
+        { name = "Format", shell = "false" },
+        { name = "Lint", shell = "echo lint >> LOG" },
+      ]"#,
+      &log,
+    );
+    let flags = Flags {
+      keep_going: true,
+      parallel,
+      ..Flags::default()
+    };
+
+    let failures = report::failure_count();
+    assert!(matches!(
+      run_with(&step, flags),
+      Recoverable::Err(Some(err), None) if err.to_string() == "2 steps failed"
+    ));
+    assert_eq!(report::failure_count(), failures + 2);
+    assert_eq!(std::fs::read_to_string(&log).unwrap(), "lint\n");
+  }
+}
+
+#[test]
+fn nested_steps_record_how_each_step_ended() {
+  for parallel in [false, true] {
+    let log = log_path();
+    let step = nested(
+      r#"steps = [
+        { name = "Recorded lint", shell = "true" },
+        [
+          { name = "Recorded tests", shell = "true", allow_failure = true },
+          { name = "Recorded skip", shell = "true", if = "false" },
+        ],
+      ]"#,
+      &log,
+    );
+    let Step::Steps(steps) = &step else {
+      unreachable!();
+    };
+    let Step::Steps(inner) = &steps[1] else {
+      unreachable!();
+    };
+    let status = |step: &Step| report::outcome_of(&step.to_string()).map(|outcome| outcome.status);
+
+    assert!(matches!(run(&step, parallel), Recoverable::Ok(())));
+    assert_eq!(status(&steps[0]), Some(report::Status::Passed));
+    assert_eq!(status(&inner[0]), Some(report::Status::Passed));
+    assert_eq!(
+      status(&inner[1]),
+      Some(report::Status::Skipped("`false` failed".into()))
+    );
+    assert_eq!(status(&steps[1]), None);
+  }
+}
@@ -0,0 +1,69 @@
+// @related [tests](medic-src/src/util/duration_test.rs)
+
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde::Deserialize;
+use serde::de::{self, Deserializer, Visitor};
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// A length of time written as a string such as `"90s"`, `"5m"` or
+/// `"1h 30m"`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct HumanDuration(pub Duration);
+
+impl From<HumanDuration> for Duration {
+  fn from(duration: HumanDuration) -> Self {
+    duration.0
+  }
+}
+
+impl FromStr for HumanDuration {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    humantime::parse_duration(s)
+      .map(HumanDuration)
+      .map_err(|err| format!("invalid duration `{s}`: {err}"))
+  }
+}
+
+impl fmt::Display for HumanDuration {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", humantime::format_duration(self.0))
+  }
+}
+
+impl<'de> Deserialize<'de> for HumanDuration {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    struct DurationVisitor;
+
+    impl Visitor<'_> for DurationVisitor {
+      type Value = HumanDuration;
+
+      fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a duration such as \"90s\" or \"5m\"")
+      }
+
+      fn visit_str<E: de::Error>(self, value: &str) -> Result<HumanDuration, E> {
+        value.parse().map_err(E::custom)
+      }
+    }
+
+    deserializer.deserialize_str(DurationVisitor)
+  }
+}
+
+impl JsonSchema for HumanDuration {
+  fn schema_name() -> Cow<'static, str> {
+    "Duration".into()
+  }
+
+  fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+      "type": "string",
+      "description": "A length of time, such as \"90s\", \"5m\" or \"1h 30m\".",
+    })
+  }
+}
@@ -0,0 +1,36 @@
+// @related [subject](medic-src/src/util/duration.rs)
+
+use super::HumanDuration;
+use std::time::Duration;
+
+#[test]
+fn test_from_str() {
+  assert_eq!("90s".parse(), Ok(HumanDuration(Duration::from_secs(90))));
+  assert_eq!("1h 30m".parse(), Ok(HumanDuration(Duration::from_secs(5400))));
+  assert_eq!("250ms".parse(), Ok(HumanDuration(Duration::from_millis(250))));
+  assert!("90".parse::<HumanDuration>().is_err());
+}
+
+#[test]
+fn test_deserialize() {
+  #[derive(serde::Deserialize)]
+  struct Step {
+    timeout: HumanDuration,
+  }
+
+  let step: Step = toml::from_str(r#"timeout = "2m""#).unwrap();
+  assert_eq!(step.timeout, HumanDuration(Duration::from_secs(120)));
+
+  let err = toml::from_str::<Step>(r#"timeout = "soon""#).err().unwrap();
+  assert!(
+    err.message().starts_with("invalid duration `soon`"),
+    "{}",
+    err.message()
+  );
+  assert!(toml::from_str::<Step>("timeout = 90").is_err());
+}
+
+#[test]
+fn test_to_string() {
+  assert_eq!(HumanDuration(Duration::from_secs(90)).to_string(), "1m 30s");
+}
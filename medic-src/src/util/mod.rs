@@ -1,5 +1,14 @@
+#[cfg(test)]
+mod duration_test;
+
+mod duration;
+
+pub use duration::HumanDuration;
+
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
 use serde::de::{self, SeqAccess, Visitor, value};
 use serde::{Deserialize, Deserializer, Serialize};
+use std::borrow::Cow;
 use std::fmt;
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -29,6 +38,21 @@ impl<'a> IntoIterator for &'a StringOrList {
   }
 }
 
+impl JsonSchema for StringOrList {
+  fn schema_name() -> Cow<'static, str> {
+    "StringOrList".into()
+  }
+
+  fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+      "anyOf": [
+        { "type": "string" },
+        { "type": "array", "items": { "type": "string" } }
+      ]
+    })
+  }
+}
+
 fn string_or_vec<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
 where
   D: Deserializer<'de>,
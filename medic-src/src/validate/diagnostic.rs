@@ -0,0 +1,57 @@
+use crate::optional_styled::OptionalStyled;
+use crate::theme::current_theme;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// A problem found in a manifest file, located by line and column.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+  pub path: PathBuf,
+  pub line: usize,
+  pub column: usize,
+  pub message: String,
+}
+
+impl Diagnostic {
+  /// Builds a diagnostic located at byte `offset` of `contents`.
+  pub(crate) fn new(path: &Path, contents: &str, offset: usize, message: impl Into<String>) -> Self {
+    let before = &contents[..offset.min(contents.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+      .rsplit('\n')
+      .next()
+      .map_or(0, |line| line.chars().count())
+      + 1;
+
+    Self {
+      path: path.to_path_buf(),
+      line,
+      column,
+      message: message.into(),
+    }
+  }
+
+  pub fn styled(&self) -> String {
+    format!(
+      "{} {}",
+      OptionalStyled::new(
+        format!("{}:{}:{}:", self.path.display(), self.line, self.column),
+        current_theme().highlight_style.clone()
+      ),
+      OptionalStyled::new(&self.message, current_theme().error_style.clone()),
+    )
+  }
+}
+
+impl fmt::Display for Diagnostic {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{}:{}:{}: {}",
+      self.path.display(),
+      self.line,
+      self.column,
+      self.message
+    )
+  }
+}
@@ -0,0 +1,86 @@
+use serde::de::{self, DeserializeOwned, Visitor};
+use serde::forward_to_deserialize_any;
+use toml::{Table, Value};
+
+/// Field names declared by the `Deserialize` implementation of `T`.
+pub(crate) fn names<T: DeserializeOwned>() -> &'static [&'static str] {
+  let mut fields: &'static [&'static str] = &[];
+  let _ = T::deserialize(FieldNames(&mut fields));
+  fields
+}
+
+/// Fields which must be present when deserializing `T`, found by repeatedly
+/// deserializing a table and filling in whichever field serde reports as
+/// missing.
+pub(crate) fn required<T: DeserializeOwned>() -> Vec<&'static str> {
+  let fields = names::<T>();
+  let mut table = Table::new();
+  let mut required = vec![];
+
+  while let Err(err) = T::deserialize(Value::Table(table.clone())) {
+    let Some(field) = missing_field(err.message()).and_then(|name| fields.iter().find(|f| **f == name)) else {
+      break;
+    };
+    if required.contains(field) {
+      break;
+    }
+    required.push(*field);
+
+    let placeholder = placeholders().into_iter().find(|placeholder| {
+      let mut candidate = table.clone();
+      candidate.insert(field.to_string(), placeholder.clone());
+      match T::deserialize(Value::Table(candidate)) {
+        Ok(_) => true,
+        Err(err) => missing_field(err.message()).is_some_and(|name| name != *field),
+      }
+    });
+    match placeholder {
+      Some(value) => table.insert(field.to_string(), value),
+      None => break,
+    };
+  }
+
+  required
+}
+
+/// Extracts the field name from serde's "missing field `name`" error.
+pub(crate) fn missing_field(message: &str) -> Option<&str> {
+  let rest = message.split("missing field `").nth(1)?;
+  rest.split('`').next()
+}
+
+fn placeholders() -> Vec<Value> {
+  vec![
+    Value::String(String::new()),
+    Value::Table(Table::new()),
+    Value::Array(vec![]),
+    Value::Boolean(false),
+    Value::Integer(0),
+  ]
+}
+
+struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+impl<'de> de::Deserializer<'de> for FieldNames<'_> {
+  type Error = de::value::Error;
+
+  fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+    Err(de::Error::custom("only structs declare field names"))
+  }
+
+  fn deserialize_struct<V: Visitor<'de>>(
+    self,
+    _name: &'static str,
+    fields: &'static [&'static str],
+    _visitor: V,
+  ) -> Result<V::Value, Self::Error> {
+    *self.0 = fields;
+    Err(de::Error::custom("field names collected"))
+  }
+
+  forward_to_deserialize_any! {
+    bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+    bytes byte_buf option unit unit_struct newtype_struct seq tuple
+    tuple_struct map enum identifier ignored_any
+  }
+}
@@ -0,0 +1,695 @@
+// @related [tests](medic-src/src/validate/validate_test.rs)
+
+#[cfg(test)]
+mod validate_test;
+
+mod diagnostic;
+pub(crate) mod fields;
+
+pub use diagnostic::Diagnostic;
+
+use crate::cli::SUBCOMMANDS;
+use crate::config::{Manifest, discovery, loader, local_override, vars};
+use crate::optional_styled::OptionalStyled;
+use crate::shipit;
+use crate::step;
+use crate::theme::current_theme;
+use crate::{AppResult, Check, OutdatedCheck};
+use crate::{shell::ShellConfig, step::StepConfig};
+
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use toml::de::{DeTable, DeValue};
+use toml::{Spanned, Table, Value};
+
+/// Validates the manifest at `path` without running anything, printing a
+/// diagnostic for each problem found.
+pub fn run(path: &Path) -> AppResult<()> {
+  let path_expansion = discovery::locate(path)?;
+  let expanded_path = path_expansion.as_path();
+
+  if !expanded_path.exists() {
+    return AppResult::Err(Some(
+      format!("Medic config file `{}` does not exist.", path.to_string_lossy()).into(),
+    ));
+  }
+
+  let diagnostics = validate(expanded_path);
+  if diagnostics.is_empty() {
+    // Problems spanning the whole manifest, such as dependency cycles between
+    // steps, are only found once it is loaded.
+    if let AppResult::Err(err) = Manifest::new(expanded_path) {
+      return AppResult::Err(err);
+    }
+    eprintln!(
+      "{} {}",
+      OptionalStyled::new("✓", current_theme().success_style.clone()),
+      OptionalStyled::new(
+        format!("{} is valid", expanded_path.display()),
+        current_theme().text_style.clone()
+      ),
+    );
+    return AppResult::Ok(());
+  }
+
+  for diagnostic in &diagnostics {
+    eprintln!("{}", diagnostic.styled());
+  }
+  let problems = if diagnostics.len() == 1 { "problem" } else { "problems" };
+  AppResult::Err(Some(
+    format!("Found {} {problems} in {}", diagnostics.len(), expanded_path.display()).into(),
+  ))
+}
+
+/// Returns a diagnostic for each problem in the manifest at `path`, its
+/// includes and its local override.
+pub fn validate(path: &Path) -> Vec<Diagnostic> {
+  let mut visited = vec![];
+  let mut diagnostics = vec![];
+  let defined = Defined::load(path);
+  let defined = defined.as_ref();
+
+  validate_file(path, false, defined, &mut visited, &mut diagnostics);
+  if let Some(local_path) = local_override::path(path) {
+    validate_file(&local_path, true, defined, &mut visited, &mut diagnostics);
+  }
+
+  let mut files: Vec<PathBuf> = vec![];
+  for diagnostic in &diagnostics {
+    if !files.contains(&diagnostic.path) {
+      files.push(diagnostic.path.clone());
+    }
+  }
+  diagnostics.sort_by_key(|diagnostic| {
+    (
+      files.iter().position(|path| path == &diagnostic.path),
+      diagnostic.line,
+      diagnostic.column,
+    )
+  });
+  diagnostics
+}
+
+/// Names of the variables and definitions available to every file.
+struct Defined {
+  definitions: Vec<String>,
+  vars: Vec<String>,
+}
+
+impl Defined {
+  // Variables and definitions may come from any included file or from the
+  // local override, so references can only be checked once every file has
+  // been loaded. When loading fails, the failure is reported instead.
+  fn load(path: &Path) -> Option<Self> {
+    let mut files = vec![loader::load(path).ok()?];
+    if let Some(local_path) = local_override::path(path) {
+      files.push(loader::load(&local_path).ok()?);
+    }
+
+    let names = |section: &str| {
+      files
+        .iter()
+        .filter_map(|table| table.get(section).and_then(Value::as_table))
+        .flat_map(|table| table.keys().cloned())
+        .collect()
+    };
+
+    Some(Self {
+      definitions: names("definitions"),
+      vars: names("vars"),
+    })
+  }
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+  Audit,
+  Definition,
+  Doctor,
+  Outdated,
+  Shipit,
+  Steps,
+}
+
+struct Section<'a> {
+  name: &'a str,
+  list: &'static str,
+  kind: Kind,
+}
+
+const SECTIONS: [Section<'static>; 6] = [
+  Section {
+    name: "audit",
+    list: "checks",
+    kind: Kind::Audit,
+  },
+  Section {
+    name: "doctor",
+    list: "checks",
+    kind: Kind::Doctor,
+  },
+  Section {
+    name: "outdated",
+    list: "checks",
+    kind: Kind::Outdated,
+  },
+  Section {
+    name: "shipit",
+    list: "steps",
+    kind: Kind::Shipit,
+  },
+  Section {
+    name: "test",
+    list: "checks",
+    kind: Kind::Steps,
+  },
+  Section {
+    name: "update",
+    list: "steps",
+    kind: Kind::Steps,
+  },
+];
+
+const LOCAL_OVERRIDE_KEYS: [&str; 2] = ["disable", "override"];
+
+struct Variant {
+  description: &'static str,
+  fields: &'static [&'static str],
+  required: Vec<&'static str>,
+  check: fn(&Value) -> Option<String>,
+}
+
+impl Variant {
+  fn of<T: DeserializeOwned>(description: &'static str) -> Self {
+    Self {
+      description,
+      fields: fields::names::<T>(),
+      required: fields::required::<T>(),
+      check: |value| {
+        T::deserialize(value.clone())
+          .err()
+          .map(|err| err.message().to_string())
+      },
+    }
+  }
+}
+
+impl Kind {
+  fn allows_nested(&self) -> bool {
+    matches!(self, Kind::Steps)
+  }
+
+  fn variants(&self) -> Vec<Variant> {
+    match self {
+      Kind::Audit | Kind::Doctor => vec![
+        Variant::of::<Check>("a check"),
+        Variant::of::<ShellConfig>("a shell action"),
+        Variant::of::<StepConfig>("a step"),
+      ],
+      Kind::Definition => vec![
+        Variant::of::<Check>("a check"),
+        Variant::of::<ShellConfig>("a shell action"),
+        Variant::of::<StepConfig>("a step"),
+        Variant::of::<OutdatedCheck>("an outdated check"),
+      ],
+      Kind::Outdated => vec![Variant::of::<OutdatedCheck>("an outdated check")],
+      Kind::Shipit => vec![
+        Variant::of::<Check>("a check"),
+        Variant::of::<ShellConfig>("a shell action"),
+        Variant::of::<StepConfig>("a step"),
+        Variant::of::<shipit::AuditConfig>("an audit"),
+        Variant::of::<shipit::TestConfig>("a test"),
+        Variant::of::<shipit::UpdateConfig>("an update"),
+      ],
+      Kind::Steps => vec![
+        Variant::of::<Check>("a check"),
+        Variant::of::<ShellConfig>("a shell action"),
+        Variant::of::<StepConfig>("a step"),
+        Variant::of::<step::DoctorConfig>("a doctor"),
+      ],
+    }
+  }
+}
+
+struct File<'a> {
+  path: &'a Path,
+  contents: &'a str,
+  defined: Option<&'a Defined>,
+}
+
+impl File<'_> {
+  fn diagnostic(&self, offset: usize, message: impl Into<String>) -> Diagnostic {
+    Diagnostic::new(self.path, self.contents, offset, message)
+  }
+}
+
+fn validate_file(
+  path: &Path,
+  local: bool,
+  defined: Option<&Defined>,
+  visited: &mut Vec<PathBuf>,
+  diagnostics: &mut Vec<Diagnostic>,
+) {
+  let contents = match std::fs::canonicalize(path).and_then(|canonical| {
+    if visited.contains(&canonical) {
+      return Ok(None);
+    }
+    visited.push(canonical.clone());
+    std::fs::read_to_string(&canonical).map(Some)
+  }) {
+    Ok(Some(contents)) => contents,
+    Ok(None) => return,
+    Err(err) => {
+      diagnostics.push(Diagnostic::new(path, "", 0, format!("unable to read file: {err}")));
+      return;
+    }
+  };
+  let file = File {
+    path,
+    contents: &contents,
+    defined,
+  };
+
+  let (spanned, document) = match (DeTable::parse(&contents), toml::from_str::<Table>(&contents)) {
+    (Ok(spanned), Ok(document)) => (spanned, document),
+    (Err(err), _) | (_, Err(err)) => {
+      let offset = err.span().map_or(0, |span| span.start);
+      diagnostics.push(file.diagnostic(offset, err.message()));
+      return;
+    }
+  };
+
+  for (key, value) in spanned.get_ref().iter() {
+    let name: &str = key.get_ref();
+
+    if name == "include" {
+      let includes = document
+        .get(name)
+        .cloned()
+        .ok_or_else(|| "unable to read `include`".into())
+        .and_then(|value| loader::include_patterns(value, path));
+      let dir = path.parent().unwrap_or(Path::new("."));
+      let resolved = includes.and_then(|patterns| {
+        patterns
+          .iter()
+          .map(|pattern| loader::resolve(pattern, dir, path))
+          .collect::<Result<Vec<_>, _>>()
+      });
+      match resolved {
+        Ok(paths) => {
+          for included in paths.iter().flatten() {
+            validate_file(included, local, defined, visited, diagnostics);
+          }
+        }
+        Err(err) => diagnostics.push(file.diagnostic(key.span().start, err.to_string())),
+      }
+      continue;
+    }
+
+    if name == "vars" {
+      validate_vars(&file, value, diagnostics);
+      continue;
+    }
+
+    if name == "workflows" {
+      validate_workflows(&file, value, &document[name], local, diagnostics);
+      continue;
+    }
+
+    if name == "definitions" {
+      validate_definitions(&file, value, &document[name], diagnostics);
+      continue;
+    }
+
+    match SECTIONS.iter().find(|section| section.name == name) {
+      Some(section) => validate_section(&file, section, value, &document[name], local, diagnostics),
+      None => diagnostics.push(file.diagnostic(key.span().start, format!("unknown section `{name}`"))),
+    }
+  }
+}
+
+fn validate_section(
+  file: &File,
+  section: &Section,
+  spanned: &Spanned<DeValue>,
+  value: &Value,
+  local: bool,
+  diagnostics: &mut Vec<Diagnostic>,
+) {
+  let (Some(table), Some(values)) = (spanned.get_ref().as_table(), value.as_table()) else {
+    diagnostics.push(file.diagnostic(spanned.span().start, format!("`{}` must be a table", section.name)));
+    return;
+  };
+
+  let mut has_list = false;
+  for (key, entry) in table.iter() {
+    let name: &str = key.get_ref();
+
+    if name == section.list {
+      has_list = true;
+      let label = format!("{}.{}", section.name, section.list);
+      match (entry.get_ref().as_array(), values[name].as_array()) {
+        (Some(items), Some(item_values)) => {
+          validate_steps(file, section.kind, items, item_values, &label, diagnostics);
+        }
+        _ => diagnostics.push(file.diagnostic(entry.span().start, format!("`{label}` must be a list"))),
+      }
+    } else if !(local && LOCAL_OVERRIDE_KEYS.contains(&name)) {
+      diagnostics.push(file.diagnostic(
+        key.span().start,
+        format!(
+          "unknown key `{name}` in [{}]; expected `{}`",
+          section.name, section.list
+        ),
+      ));
+    }
+  }
+
+  if !has_list && !local {
+    diagnostics.push(file.diagnostic(
+      spanned.span().start,
+      format!("[{}] is missing `{}`", section.name, section.list),
+    ));
+  }
+}
+
+fn validate_steps(
+  file: &File,
+  kind: Kind,
+  items: &[Spanned<DeValue>],
+  values: &[Value],
+  label: &str,
+  diagnostics: &mut Vec<Diagnostic>,
+) {
+  for (i, (item, value)) in items.iter().zip(values).enumerate() {
+    validate_step(file, kind, item, value, &format!("{label}[{i}]"), diagnostics);
+  }
+}
+
+fn validate_step(
+  file: &File,
+  kind: Kind,
+  spanned: &Spanned<DeValue>,
+  value: &Value,
+  label: &str,
+  diagnostics: &mut Vec<Diagnostic>,
+) {
+  let table = match (spanned.get_ref(), value) {
+    (DeValue::Array(items), Value::Array(values)) if kind.allows_nested() => {
+      validate_steps(file, kind, items, values, label, diagnostics);
+      return;
+    }
+    (DeValue::Table(spanned_table), Value::Table(table)) => (spanned_table, table),
+    _ => {
+      let expected = if kind.allows_nested() {
+        "a table or a list of steps"
+      } else {
+        "a table"
+      };
+      diagnostics.push(file.diagnostic(spanned.span().start, format!("`{label}` must be {expected}")));
+      return;
+    }
+  };
+  let (spanned_table, table) = table;
+  if kind.allows_nested() && table.contains_key("steps") {
+    validate_group(file, kind, spanned_table, table, label, diagnostics);
+    return;
+  }
+  validate_references(file, spanned_table, label, diagnostics);
+
+  // The other keys of a step which uses a definition only override parts of
+  // it, so they cannot be matched against a step type on their own.
+  if let Some((key, value)) = spanned_table.iter().find(|(key, _)| key.get_ref() == "use") {
+    validate_use(file, key, value, label, diagnostics);
+    return;
+  }
+
+  let mut best: Option<Candidate> = None;
+  for variant in kind.variants() {
+    let candidate = Candidate {
+      unknown: table
+        .keys()
+        .map(String::as_str)
+        .filter(|key| !variant.fields.contains(key))
+        .collect(),
+      missing: variant
+        .required
+        .iter()
+        .copied()
+        .filter(|field| !table.contains_key(*field))
+        .collect(),
+      error: (variant.check)(value),
+      variant,
+    };
+
+    if candidate.error.is_none() && candidate.unknown.is_empty() {
+      return;
+    }
+
+    let better = match &best {
+      None => true,
+      Some(best) => candidate.score(table) > best.score(table),
+    };
+    if better {
+      best = Some(candidate);
+    }
+  }
+
+  let Some(Candidate {
+    variant,
+    unknown,
+    missing,
+    error,
+  }) = best
+  else {
+    return;
+  };
+
+  let mut problems = vec![];
+  if !unknown.is_empty() {
+    problems.push(format!("unknown {} {}", plural("key", unknown.len()), quoted(&unknown)));
+  }
+  if !missing.is_empty() {
+    problems.push(format!("missing {} {}", plural("key", missing.len()), quoted(&missing)));
+  }
+  if problems.is_empty()
+    && let Some(error) = &error
+  {
+    problems.push(error.clone());
+  }
+
+  let summary = if error.is_some() {
+    format!("does not match any step type; the closest is {}", variant.description)
+  } else {
+    format!("is {}", variant.description)
+  };
+
+  let offset = spanned_table
+    .iter()
+    .filter(|(key, _)| unknown.contains(&key.get_ref().as_ref()))
+    .map(|(key, _)| key.span().start)
+    .min()
+    .unwrap_or(spanned.span().start);
+
+  diagnostics.push(file.diagnostic(offset, format!("`{label}` {summary}: {}", problems.join("; "))));
+}
+
+// A nested list written as a table, such as `{ jobs = 2, steps = [...] }`.
+fn validate_group(
+  file: &File,
+  kind: Kind,
+  spanned_table: &DeTable,
+  table: &Table,
+  label: &str,
+  diagnostics: &mut Vec<Diagnostic>,
+) {
+  let fields = fields::names::<step::StepGroup>();
+  for (key, value) in spanned_table.iter() {
+    let name: &str = key.get_ref();
+    if !fields.contains(&name) {
+      diagnostics.push(file.diagnostic(
+        key.span().start,
+        format!(
+          "unknown key `{name}` in nested steps `{label}`; expected {}",
+          quoted(fields)
+        ),
+      ));
+    } else if name == "jobs"
+      && let Err(err) = step::Jobs::deserialize(table[name].clone())
+    {
+      diagnostics.push(file.diagnostic(
+        value.span().start,
+        format!("`{label}.jobs` is invalid: {}", err.message()),
+      ));
+    } else if name == "steps" {
+      let label = format!("{label}.steps");
+      match (value.get_ref().as_array(), table[name].as_array()) {
+        (Some(items), Some(values)) => validate_steps(file, kind, items, values, &label, diagnostics),
+        _ => diagnostics.push(file.diagnostic(value.span().start, format!("`{label}` must be a list"))),
+      }
+    }
+  }
+}
+
+fn validate_vars(file: &File, spanned: &Spanned<DeValue>, diagnostics: &mut Vec<Diagnostic>) {
+  let Some(table) = spanned.get_ref().as_table() else {
+    diagnostics.push(file.diagnostic(spanned.span().start, "`vars` must be a table of strings"));
+    return;
+  };
+
+  for (key, value) in table.iter() {
+    if !matches!(value.get_ref(), DeValue::String(_)) {
+      diagnostics.push(file.diagnostic(value.span().start, format!("`vars.{}` must be a string", key.get_ref())));
+    }
+  }
+}
+
+fn validate_workflows(
+  file: &File,
+  spanned: &Spanned<DeValue>,
+  value: &Value,
+  local: bool,
+  diagnostics: &mut Vec<Diagnostic>,
+) {
+  let (Some(table), Some(values)) = (spanned.get_ref().as_table(), value.as_table()) else {
+    diagnostics.push(file.diagnostic(spanned.span().start, "`workflows` must be a table of workflows"));
+    return;
+  };
+
+  for (key, entry) in table.iter() {
+    let name: &str = key.get_ref();
+    if SUBCOMMANDS.contains(&name) {
+      diagnostics.push(file.diagnostic(
+        key.span().start,
+        format!("workflow `{name}` has the name of a medic subcommand, which would run in its place"),
+      ));
+    }
+    let section = Section {
+      name: &format!("workflows.{name}"),
+      list: "steps",
+      kind: Kind::Steps,
+    };
+    validate_section(file, &section, entry, &values[name], local, diagnostics);
+  }
+}
+
+fn validate_definitions(file: &File, spanned: &Spanned<DeValue>, value: &Value, diagnostics: &mut Vec<Diagnostic>) {
+  let (Some(table), Some(values)) = (spanned.get_ref().as_table(), value.as_table()) else {
+    diagnostics.push(file.diagnostic(spanned.span().start, "`definitions` must be a table of steps"));
+    return;
+  };
+
+  for (key, entry) in table.iter() {
+    let name: &str = key.get_ref();
+    validate_step(
+      file,
+      Kind::Definition,
+      entry,
+      &values[name],
+      &format!("definitions.{name}"),
+      diagnostics,
+    );
+  }
+}
+
+fn validate_use(
+  file: &File,
+  key: &Spanned<Cow<str>>,
+  value: &Spanned<DeValue>,
+  label: &str,
+  diagnostics: &mut Vec<Diagnostic>,
+) {
+  let DeValue::String(name) = value.get_ref() else {
+    diagnostics.push(file.diagnostic(
+      value.span().start,
+      format!("`{label}` must `use` the name of a definition"),
+    ));
+    return;
+  };
+
+  if let Some(Defined { definitions, .. }) = file.defined
+    && !definitions.iter().any(|definition| definition == name)
+  {
+    diagnostics.push(file.diagnostic(key.span().start, format!("`{label}` uses unknown definition `{name}`")));
+  }
+}
+
+fn validate_references(file: &File, table: &DeTable, label: &str, diagnostics: &mut Vec<Diagnostic>) {
+  let Some(Defined { vars, .. }) = file.defined else {
+    return;
+  };
+
+  for (key, value) in table.iter() {
+    if vars::INTERPOLATED_KEYS.contains(&key.get_ref().as_ref()) {
+      validate_value_references(file, value, vars, label, diagnostics);
+    }
+  }
+}
+
+fn validate_value_references(
+  file: &File,
+  spanned: &Spanned<DeValue>,
+  vars: &[String],
+  label: &str,
+  diagnostics: &mut Vec<Diagnostic>,
+) {
+  match spanned.get_ref() {
+    DeValue::String(string) => {
+      for (offset, name) in vars::references(string) {
+        if !vars.iter().any(|var| var == name) {
+          // Offsets are relative to the string's contents, which begin after
+          // its opening quote.
+          diagnostics.push(file.diagnostic(
+            spanned.span().start + 1 + offset,
+            format!("`{label}` references undefined variable `{name}`"),
+          ));
+        }
+      }
+    }
+    DeValue::Array(values) => {
+      for value in values {
+        validate_value_references(file, value, vars, label, diagnostics);
+      }
+    }
+    DeValue::Table(table) => {
+      for (_, value) in table.iter() {
+        validate_value_references(file, value, vars, label, diagnostics);
+      }
+    }
+    _ => {}
+  }
+}
+
+/// How closely a step matches one of the variants of its section.
+struct Candidate<'a> {
+  variant: Variant,
+  unknown: Vec<&'a str>,
+  missing: Vec<&'a str>,
+  error: Option<String>,
+}
+
+impl Candidate<'_> {
+  /// Variants which deserialize beat those which do not; after that, the
+  /// variant recognizing the most keys wins.
+  fn score(&self, table: &Table) -> (bool, usize) {
+    (self.error.is_none(), table.len() - self.unknown.len())
+  }
+}
+
+fn plural(word: &str, count: usize) -> String {
+  if count == 1 {
+    word.to_string()
+  } else {
+    format!("{word}s")
+  }
+}
+
+fn quoted(keys: &[&str]) -> String {
+  keys
+    .iter()
+    .map(|key| format!("`{key}`"))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
@@ -0,0 +1,101 @@
+// @related [subject](medic-src/src/validate/mod.rs)
+
+use super::*;
+use crate::shell::ShellConfig;
+use std::path::Path;
+
+fn messages(path: &str) -> Vec<String> {
+  validate(Path::new(path))
+    .iter()
+    .map(|diagnostic| diagnostic.to_string())
+    .collect()
+}
+
+#[test]
+fn validate_valid_manifest() {
+  assert_eq!(messages("../fixtures/medic.toml"), Vec::<String>::new());
+}
+
+#[test]
+fn validate_includes_and_local_overrides() {
+  assert_eq!(messages("../fixtures/include/medic.toml"), Vec::<String>::new());
+  assert_eq!(messages("../fixtures/workflows/medic.toml"), Vec::<String>::new());
+  assert_eq!(messages("../fixtures/local-override/medic.toml"), Vec::<String>::new());
+}
+
+#[test]
+fn validate_reports_location_and_closest_variant() {
+  assert_eq!(
+    messages("../fixtures/invalid/medic.toml"),
+    vec![
+      "../fixtures/invalid/medic.toml:4:5: `doctor.checks[1]` does not match any step type; the closest is a check: unknown key `chek`; missing key `check`",
+      "../fixtures/invalid/medic.toml:5:21: `doctor.checks[2]` is a check: unknown key `verbos`",
+      "../fixtures/invalid/medic.toml:6:3: `doctor.checks[3]` does not match any step type; the closest is a shell action: invalid type: string \"yes\", expected a boolean",
+      "../fixtures/invalid/medic.toml:12:24: `test.checks[0][0]` does not match any step type; the closest is a shell action: unknown key `shel`; missing key `shell`",
+      "../fixtures/invalid/medic.toml:20:1: unknown key `stepz` in [shipit]; expected `steps`",
+      "../fixtures/invalid/medic.toml:22:2: unknown section `doktor`",
+      "../fixtures/invalid/medic.toml:25:1: [workflows.seed] is missing `steps`",
+      "../fixtures/invalid/medic.toml:26:1: unknown key `step` in [workflows.seed]; expected `steps`",
+      "../fixtures/invalid/medic.toml:30:12: workflow `update` has the name of a medic subcommand, which would run in its place",
+    ]
+  );
+}
+
+#[test]
+fn validate_reports_syntax_errors() {
+  let diagnostics = validate(Path::new("../fixtures/invalid/syntax.toml"));
+
+  assert_eq!(diagnostics.len(), 1);
+  assert_eq!(diagnostics[0].line, 4);
+  assert_eq!(diagnostics[0].column, 1);
+}
+
+#[test]
+fn diagnostic_line_and_column() {
+  let contents = "first\nsecond line\nthird";
+  let diagnostic = Diagnostic::new(Path::new("medic.toml"), contents, 13, "oops");
+
+  assert_eq!(diagnostic.line, 2);
+  assert_eq!(diagnostic.column, 8);
+  assert_eq!(diagnostic.to_string(), "medic.toml:2:8: oops");
+}
+
+#[test]
+fn fields_names_and_required() {
+  assert!(fields::names::<ShellConfig>().contains(&"allow_failure"));
+  assert_eq!(fields::required::<ShellConfig>(), vec!["name", "shell"]);
+  assert_eq!(fields::required::<Check>(), vec!["check"]);
+  assert_eq!(fields::required::<step::DoctorConfig>(), vec!["doctor"]);
+}
+
+#[test]
+fn validate_reports_undefined_variables() {
+  assert_eq!(messages("../fixtures/vars/medic.toml"), Vec::<String>::new());
+  assert_eq!(
+    messages("../fixtures/vars-undefined/medic.toml"),
+    vec!["../fixtures/vars-undefined/medic.toml:6:57: `doctor.checks[0]` references undefined variable `plugin`"]
+  );
+}
+
+#[test]
+fn validate_reports_unknown_definitions() {
+  assert_eq!(messages("../fixtures/definitions/medic.toml"), Vec::<String>::new());
+  assert_eq!(
+    messages("../fixtures/definitions-unknown/medic.toml"),
+    vec!["../fixtures/definitions-unknown/medic.toml:7:5: `doctor.checks[1]` uses unknown definition `teardown`"]
+  );
+}
+
+#[test]
+fn validate_nested_step_groups() {
+  assert_eq!(messages("../fixtures/jobs/medic.toml"), Vec::<String>::new());
+  assert_eq!(
+    messages("../fixtures/invalid/jobs.toml"),
+    vec![
+      "../fixtures/invalid/jobs.toml:3:12: `test.checks[0].jobs` is invalid: invalid value: integer `0`, expected a positive number or \"auto\"",
+      "../fixtures/invalid/jobs.toml:4:5: unknown key `job` in nested steps `test.checks[1]`; expected `jobs`, `steps`",
+      "../fixtures/invalid/jobs.toml:4:40: `test.checks[1].steps[0]` does not match any step type; the closest is a shell action: unknown key `shel`; missing key `shell`",
+      "../fixtures/invalid/jobs.toml:5:23: `test.checks[2].steps` must be a list",
+    ]
+  );
+}
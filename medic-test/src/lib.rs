@@ -6,7 +6,7 @@ use medic_src::AppResult;
 use medic_src::cli::Flags;
 use medic_src::config::Manifest;
 use medic_src::context::Context;
-use medic_src::runnable::run;
+use medic_src::state;
 
 pub fn run_steps(
   manifest: Manifest,
@@ -19,10 +19,7 @@ pub fn run_steps(
       let mut flags = flags.clone();
       flags.recoverable = false;
 
-      for check in test.checks {
-        run(check, progress, &mut flags, context)?;
-      }
-      AppResult::Ok(())
+      state::run_steps("test", test.checks, progress, &mut flags, context)
     }
     None => AppResult::Err(Some("No test checks found in medic config.".into())),
   }
@@ -1,4 +1,4 @@
-use medic_src::config::Manifest;
+use medic_src::config::{Manifest, discovery};
 use medic_src::context::Context;
 use medic_src::{AppResult, progress, theme};
 use medic_test::cli::CliArgs;
@@ -12,7 +12,7 @@ use std::panic;
 fn main() -> AppResult<()> {
   let context = Context::new();
   let cli_args = CliArgs::parse();
-  theme::set_theme((&cli_args.theme).into());
+  theme::set_theme((&cli_args.manifest.theme).into());
 
   if let Some(completion) = cli_args.completion {
     let mut cmd = CliArgs::command();
@@ -22,25 +22,25 @@ fn main() -> AppResult<()> {
     std::process::exit(0);
   }
 
-  let manifest = Manifest::new(&cli_args.config)?;
+  discovery::enter(&cli_args.manifest.config)?;
+  let manifest = Manifest::new(&cli_args.manifest.config)?;
+  manifest.notify_local_override();
 
-  ctrlc::set_handler(interrupt).expect("Unable to set Ctrl-C handler");
+  medic_src::interrupt::install();
 
   console::set_colors_enabled(true);
   console::set_colors_enabled_stderr(true);
 
   let result = panic::catch_unwind(|| {
-    let flags = cli_args.into();
+    let flags = cli_args.manifest.into();
     let mut progress = progress::new(&flags);
     run_steps(manifest, &mut progress, flags, &context)
   });
 
+  medic_src::report::print();
+
   match result {
     Ok(inner) => inner,
     Err(_) => std::process::exit(1),
   }
 }
-
-fn interrupt() {
-  std::process::exit(1);
-}
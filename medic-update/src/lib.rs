@@ -6,7 +6,7 @@ use medic_src::AppResult;
 use medic_src::cli::Flags;
 use medic_src::config::Manifest;
 use medic_src::context::Context;
-use medic_src::runnable::run;
+use medic_src::state;
 
 pub fn run_steps(
   manifest: Manifest,
@@ -15,12 +15,7 @@ pub fn run_steps(
   context: &Context,
 ) -> AppResult<()> {
   match manifest.update {
-    Some(test) => {
-      for step in test.steps {
-        run(step, progress, &mut flags, context)?;
-      }
-      AppResult::Ok(())
-    }
+    Some(update) => state::run_steps("update", update.steps, progress, &mut flags, context),
     None => AppResult::Err(Some("No update steps found in medic config.".into())),
   }
 }
@@ -0,0 +1,19 @@
+use clap::Parser;
+use clap_complete::Shell;
+use medic_src::cli::ManifestArgs;
+
+#[derive(Debug, Parser)]
+#[clap(author, version, about)]
+#[clap(bin_name = "medic workflow")]
+pub struct CliArgs {
+  /// Name of the workflow to run, from `[workflows.<name>]`
+  #[clap(required_unless_present = "completion")]
+  pub name: Option<String>,
+
+  /// Shell to generate completions for
+  #[clap(long, value_enum, value_parser)]
+  pub completion: Option<Shell>,
+
+  #[command(flatten)]
+  pub manifest: ManifestArgs,
+}
@@ -0,0 +1,40 @@
+#![cfg_attr(feature = "strict", deny(warnings))]
+
+pub mod cli;
+
+use medic_src::AppResult;
+use medic_src::cli::Flags;
+use medic_src::config::Manifest;
+use medic_src::context::Context;
+use medic_src::state;
+
+pub fn run_steps(
+  mut manifest: Manifest,
+  name: &str,
+  progress: &mut retrogress::ProgressBar,
+  mut flags: Flags,
+  context: &Context,
+) -> AppResult<()> {
+  match manifest.workflows.remove(name) {
+    Some(workflow) => state::run_steps(
+      &format!("workflows/{name}"),
+      workflow.steps,
+      progress,
+      &mut flags,
+      context,
+    ),
+    None if manifest.workflows.is_empty() => AppResult::Err(Some(
+      format!("No workflow named `{name}` found in medic config.").into(),
+    )),
+    None => {
+      let names: Vec<&str> = manifest.workflows.keys().map(String::as_str).collect();
+      AppResult::Err(Some(
+        format!(
+          "No workflow named `{name}` found in medic config. Available workflows: {}",
+          names.join(", ")
+        )
+        .into(),
+      ))
+    }
+  }
+}
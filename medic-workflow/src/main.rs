@@ -0,0 +1,47 @@
+use medic_src::config::{Manifest, discovery};
+use medic_src::context::Context;
+use medic_src::{AppResult, progress, theme};
+use medic_workflow::cli::CliArgs;
+use medic_workflow::run_steps;
+
+use clap::{CommandFactory, Parser};
+use clap_complete::generate;
+use std::io::stdout;
+use std::panic;
+
+fn main() -> AppResult<()> {
+  let context = Context::new();
+  let mut cli_args = CliArgs::parse();
+  theme::set_theme((&cli_args.manifest.theme).into());
+
+  if let Some(completion) = cli_args.completion {
+    let mut cmd = CliArgs::command();
+    let name = cmd.get_name().to_string();
+    generate(completion, &mut cmd, name, &mut stdout());
+
+    std::process::exit(0);
+  }
+
+  let name = cli_args.name.take().unwrap_or_default();
+  discovery::enter(&cli_args.manifest.config)?;
+  let manifest = Manifest::new(&cli_args.manifest.config)?;
+  manifest.notify_local_override();
+
+  medic_src::interrupt::install();
+
+  console::set_colors_enabled(true);
+  console::set_colors_enabled_stderr(true);
+
+  let result = panic::catch_unwind(|| {
+    let flags = cli_args.manifest.into();
+    let mut progress = progress::new(&flags);
+    run_steps(manifest, &name, &mut progress, flags, &context)
+  });
+
+  medic_src::report::print();
+
+  match result {
+    Ok(inner) => inner,
+    Err(_) => std::process::exit(1),
+  }
+}
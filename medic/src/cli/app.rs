@@ -1,9 +1,15 @@
+// @related [tests](medic/src/cli/app_test.rs)
+
 use clap::Args;
 use clap::Parser;
 use clap::Subcommand;
 use medic_run::cli::CliArgs as RunArgs;
-use medic_src::cli::Flags;
+use medic_src::AppResult;
+use medic_src::cli::{ManifestArgs, SUBCOMMANDS};
+use medic_src::config::Manifest;
+use medic_src::show::Format;
 use medic_src::theme::Theme;
+use std::ffi::OsString;
 use std::path::PathBuf;
 
 #[derive(Debug, Parser)]
@@ -23,8 +29,15 @@ pub enum Command {
   Audit(ManifestArgs),
   /// Runs checks to ensure that a project is fully set up for development.
   Doctor(ManifestArgs),
+  /// Adds a step to a section of an existing medic manifest.
+  Add(AddArgs),
+  /// Inspects the medic manifest.
+  #[command(subcommand)]
+  Config(ConfigCommand),
   /// Creates the shell of a medic manifest file.
   Init(InitArgs),
+  /// Lists the steps of each subcommand and workflow, or of one of them.
+  List(ListArgs),
   /// Runs checks for outdated dependencies
   Outdated(ManifestArgs),
   /// Runs an arbitrary shell command.
@@ -35,26 +48,81 @@ pub enum Command {
   Update(ManifestArgs),
   /// Ship changes. Typically configured to audit, update, test, then release.
   Shipit(ManifestArgs),
+  /// Shows how long the steps of each subcommand and workflow have taken,
+  /// or of one of them.
+  Stats(StatsArgs),
+  /// Checks the medic manifest for problems without running anything.
+  Validate(ValidateArgs),
+  /// Prints a JSON Schema of the medic manifest, for editor completion and
+  /// validation.
+  Schema,
+  /// Runs the steps of a workflow defined in `[workflows.<name>]`.
+  Workflow(WorkflowArgs),
+  /// Runs a workflow by name, when it does not clash with a subcommand.
+  #[command(external_subcommand)]
+  External(Vec<OsString>),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+  /// Prints the manifest after includes, definitions, local overrides and
+  /// vars are applied.
+  Show(ShowArgs),
 }
 
 #[derive(Args, Debug)]
-pub struct ManifestArgs {
+pub struct InitArgs {
   /// Path to a file where medic config can be found
   #[clap(value_parser)]
   #[arg(short, long, env = "MEDIC_CONFIG", default_value = "${PWD}/.config/medic.toml", value_hint = clap::ValueHint::FilePath)]
   pub config: PathBuf,
+  #[clap(short, long, action)]
+  pub force: bool,
+  /// Template to start from: a built-in template (generic, homebrew,
+  /// tool-versions, rust, elixir, node, go), the name of a file in
+  /// .config/medic/templates, or a path to a TOML file. Defaults to templates
+  /// for the files found in the project.
+  #[arg(short, long)]
+  pub template: Option<String>,
+  /// Adds a step to a section of the existing manifest instead of creating
+  /// one, keeping its comments and formatting
+  #[arg(long, num_args = 2, value_names = ["SECTION", "STEP"], conflicts_with_all = ["force", "template"])]
+  pub add: Option<Vec<String>>,
+  /// Color theme
+  #[arg(long, env = "MEDIC_THEME", default_value = "auto")]
+  pub theme: Theme,
+}
 
-  /// Automatically apply suggested remedies
-  #[arg(short, long, env = "MEDIC_APPLY_REMEDIES", action)]
-  pub apply_remedies: bool,
+#[derive(Args, Debug)]
+pub struct AddArgs {
+  /// Section to add the step to, e.g. doctor or workflows.<name>
+  pub section: String,
+  /// The step as an inline TOML table, e.g. '{ check = "homebrew" }'
+  pub step: String,
+  /// Path to a file where medic config can be found
+  #[clap(value_parser)]
+  #[arg(short, long, env = "MEDIC_CONFIG", default_value = "${PWD}/.config/medic.toml", value_hint = clap::ValueHint::FilePath)]
+  pub config: PathBuf,
 
-  /// Provide interactive prompts when possible instead of failing
-  #[arg(short, long, env = "MEDIC_INTERACTIVE", action)]
-  pub interactive: bool,
+  /// Color theme
+  #[arg(short, long, env = "MEDIC_THEME", default_value = "auto")]
+  pub theme: Theme,
+}
 
-  /// Run sub-lists of steps in parallel (experimental)
-  #[arg(short, long, env = "MEDIC_PARALLEL", action)]
-  pub parallel: bool,
+#[derive(Args, Debug)]
+pub struct ShowArgs {
+  /// Path to a file where medic config can be found
+  #[clap(value_parser)]
+  #[arg(short, long, env = "MEDIC_CONFIG", default_value = "${PWD}/.config/medic.toml", value_hint = clap::ValueHint::FilePath)]
+  pub config: PathBuf,
+
+  /// Output format
+  #[arg(short, long, default_value = "toml")]
+  pub format: Format,
+
+  /// Show the command each step runs, and mark steps skipped on this platform
+  #[arg(short, long, action)]
+  pub resolved: bool,
 
   /// Color theme
   #[arg(short, long, env = "MEDIC_THEME", default_value = "auto")]
@@ -62,13 +130,72 @@ pub struct ManifestArgs {
 }
 
 #[derive(Args, Debug)]
-pub struct InitArgs {
+pub struct ListArgs {
+  /// Subcommand or workflow whose steps to list, e.g. update or deploy
+  pub workflow: Option<String>,
+
   /// Path to a file where medic config can be found
   #[clap(value_parser)]
   #[arg(short, long, env = "MEDIC_CONFIG", default_value = "${PWD}/.config/medic.toml", value_hint = clap::ValueHint::FilePath)]
   pub config: PathBuf,
-  #[clap(short, long, action)]
-  pub force: bool,
+
+  /// Print the steps as JSON
+  #[arg(long, action)]
+  pub json: bool,
+
+  /// Color theme
+  #[arg(short, long, env = "MEDIC_THEME", default_value = "auto")]
+  pub theme: Theme,
+}
+
+#[derive(Args, Debug)]
+pub struct StatsArgs {
+  /// Subcommand or workflow whose steps to show, e.g. update or deploy
+  pub workflow: Option<String>,
+
+  /// Path to a file where medic config can be found
+  #[clap(value_parser)]
+  #[arg(short, long, env = "MEDIC_CONFIG", default_value = "${PWD}/.config/medic.toml", value_hint = clap::ValueHint::FilePath)]
+  pub config: PathBuf,
+
+  /// Color theme
+  #[arg(short, long, env = "MEDIC_THEME", default_value = "auto")]
+  pub theme: Theme,
+}
+
+#[derive(Args, Debug)]
+pub struct WorkflowArgs {
+  /// Name of the workflow to run, from `[workflows.<name>]`
+  pub name: String,
+
+  #[command(flatten)]
+  pub manifest: ManifestArgs,
+}
+
+// Parses `medic <name> [args]` as though it were `medic workflow <name> [args]`.
+#[derive(Debug, Parser)]
+#[clap(bin_name = "medic")]
+struct ExternalWorkflow {
+  #[command(flatten)]
+  args: WorkflowArgs,
+}
+
+impl WorkflowArgs {
+  pub fn from_external(args: Vec<OsString>) -> Self {
+    ExternalWorkflow::parse_from(std::iter::once(OsString::from("medic")).chain(args)).args
+  }
+}
+
+#[derive(Args, Debug)]
+pub struct ValidateArgs {
+  /// Path to a file where medic config can be found
+  #[clap(value_parser)]
+  #[arg(short, long, env = "MEDIC_CONFIG", default_value = "${PWD}/.config/medic.toml", value_hint = clap::ValueHint::FilePath)]
+  pub config: PathBuf,
+
+  /// Color theme
+  #[arg(short, long, env = "MEDIC_THEME", default_value = "auto")]
+  pub theme: Theme,
 }
 
 impl Default for CliArgs {
@@ -79,18 +206,28 @@ impl Default for CliArgs {
 
 impl CliArgs {
   pub fn new() -> Self {
-    CliArgs::parse()
+    Self::parse_with_workflows(std::env::args_os())
   }
-}
 
-impl From<ManifestArgs> for Flags {
-  fn from(args: ManifestArgs) -> Self {
-    Self {
-      auto_apply_remedy: args.apply_remedies,
-      config_path: args.config,
-      interactive: args.interactive,
-      parallel: args.parallel,
-      ..Self::default()
+  /// Parses `args`, running a workflow of the manifest rather than the
+  /// subcommand its name abbreviates: with `[workflows.up]`, `medic up` runs
+  /// the workflow rather than `medic update`.
+  pub fn parse_with_workflows(args: impl IntoIterator<Item = OsString>) -> Self {
+    let args: Vec<OsString> = args.into_iter().collect();
+    if let Some(name) = args.get(1).and_then(|name| name.to_str())
+      && !name.starts_with('-')
+      && !SUBCOMMANDS.contains(&name)
+      && let Ok(external) = ExternalWorkflow::try_parse_from(args.iter())
+      && is_workflow(&external.args)
+    {
+      return Self {
+        command: Command::Workflow(external.args),
+      };
     }
+    Self::parse_from(args)
   }
 }
+
+fn is_workflow(args: &WorkflowArgs) -> bool {
+  matches!(Manifest::new(&args.manifest.config), AppResult::Ok(manifest) if manifest.workflows.contains_key(&args.name))
+}
@@ -0,0 +1,56 @@
+// @related [subject](medic/src/cli/app.rs)
+
+use super::app::{CliArgs, Command};
+use clap::CommandFactory;
+use medic_src::cli::SUBCOMMANDS;
+use std::ffi::OsString;
+
+fn parse(args: &[&str]) -> Command {
+  let args = std::iter::once("medic")
+    .chain(args.iter().copied())
+    .map(OsString::from);
+  CliArgs::parse_with_workflows(args).command
+}
+
+#[test]
+fn subcommands_lists_every_subcommand() {
+  let mut command = CliArgs::command();
+  command.build();
+  let mut names: Vec<&str> = command
+    .get_subcommands()
+    .map(|command| command.get_name())
+    .collect();
+  names.sort();
+
+  assert_eq!(names, SUBCOMMANDS);
+}
+
+#[test]
+fn workflows_run_in_place_of_the_subcommand_they_abbreviate() {
+  let command = parse(&["up", "--config", "../fixtures/workflows/medic.toml"]);
+
+  let Command::Workflow(args) = command else {
+    panic!("Expected the workflow to run, got {command:?}");
+  };
+  assert_eq!(args.name, "up");
+}
+
+#[test]
+fn subcommands_may_be_abbreviated_when_no_workflow_has_the_name() {
+  let command = parse(&["upd", "--config", "../fixtures/workflows/medic.toml"]);
+
+  assert!(
+    matches!(command, Command::Update(_)),
+    "Expected update, got {command:?}"
+  );
+}
+
+#[test]
+fn subcommands_run_in_place_of_workflows_of_the_same_name() {
+  let command = parse(&["update", "--config", "../fixtures/invalid/medic.toml"]);
+
+  assert!(
+    matches!(command, Command::Update(_)),
+    "Expected update, got {command:?}"
+  );
+}
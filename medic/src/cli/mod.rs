@@ -1 +1,4 @@
+#[cfg(test)]
+mod app_test;
+
 pub mod app;
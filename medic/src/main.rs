@@ -1,20 +1,24 @@
 #![cfg_attr(feature = "strict", deny(warnings))]
 
-use clap::Parser;
-use medic::cli::app::{CliArgs, Command};
+use medic::cli::app::{CliArgs, Command, ConfigCommand, WorkflowArgs};
 use medic_src::AppResult;
 use medic_src::cli::Flags;
-use medic_src::config::Manifest;
+use medic_src::config::{Manifest, discovery};
 use medic_src::context::Context;
+use medic_src::list;
 use medic_src::progress;
+use medic_src::schema;
+use medic_src::show;
+use medic_src::stats;
 use medic_src::theme;
+use medic_src::validate;
 use std::panic;
 
 fn main() -> AppResult<()> {
   let context = Context::new();
-  let cli = CliArgs::parse();
+  let cli = CliArgs::new();
 
-  ctrlc::set_handler(cleanup).expect("Unable to set Ctrl-C handler");
+  medic_src::interrupt::install();
 
   console::set_colors_enabled(true);
   console::set_colors_enabled_stderr(true);
@@ -22,22 +26,46 @@ fn main() -> AppResult<()> {
   let result = panic::catch_unwind(|| match cli.command {
     Command::Audit(args) => {
       theme::set_theme((&args.theme).into());
+      discovery::enter(&args.config)?;
       let manifest = Manifest::new(&args.config)?;
+      manifest.notify_local_override();
       let flags = args.into();
       let mut progress = progress::new(&flags);
       medic_audit::run_steps(manifest, &mut progress, flags, &context)
     }
     Command::Doctor(args) => {
       theme::set_theme((&args.theme).into());
+      discovery::enter(&args.config)?;
       let manifest = Manifest::new(&args.config)?;
+      manifest.notify_local_override();
       let flags = args.into();
       let mut progress = progress::new(&flags);
       medic_doctor::run_checks(manifest, &mut progress, flags, &context)
     }
-    Command::Init(args) => medic_init::create_config_file(args.config, args.force),
+    Command::Add(args) => {
+      theme::set_theme((&args.theme).into());
+      medic_init::add::add_step(args.config, args.section, args.step)
+    }
+    Command::Config(ConfigCommand::Show(args)) => {
+      theme::set_theme((&args.theme).into());
+      show::run(&args.config, args.format, args.resolved, &context)
+    }
+    Command::Init(args) => {
+      theme::set_theme((&args.theme).into());
+      match args.add.as_deref() {
+        Some([section, step]) => medic_init::add::add_step(args.config, section.clone(), step.clone()),
+        _ => medic_init::create_config_file(args.config, args.force, args.template),
+      }
+    }
+    Command::List(args) => {
+      theme::set_theme((&args.theme).into());
+      list::run(&args.config, args.workflow.as_deref(), args.json)
+    }
     Command::Outdated(args) => {
       theme::set_theme((&args.theme).into());
+      discovery::enter(&args.config)?;
       let manifest = Manifest::new(&args.config)?;
+      manifest.notify_local_override();
       let flags = args.into();
       let mut progress = progress::new(&flags);
       medic_outdated::run_checks(manifest, &mut progress, flags, &context)
@@ -50,35 +78,58 @@ fn main() -> AppResult<()> {
     }
     Command::Test(args) => {
       theme::set_theme((&args.theme).into());
+      discovery::enter(&args.config)?;
       let manifest = Manifest::new(&args.config)?;
+      manifest.notify_local_override();
       let flags = args.into();
       let mut progress = progress::new(&flags);
       medic_test::run_steps(manifest, &mut progress, flags, &context)
     }
     Command::Update(args) => {
       theme::set_theme((&args.theme).into());
+      discovery::enter(&args.config)?;
       let manifest = Manifest::new(&args.config)?;
+      manifest.notify_local_override();
       let flags = args.into();
       let mut progress = progress::new(&flags);
       medic_update::run_steps(manifest, &mut progress, flags, &context)
     }
     Command::Shipit(args) => {
       theme::set_theme((&args.theme).into());
+      discovery::enter(&args.config)?;
       let manifest = Manifest::new(&args.config)?;
+      manifest.notify_local_override();
       let flags = args.into();
       let mut progress = progress::new(&flags);
       medic_shipit::run_steps(manifest, &mut progress, flags, &context)
     }
+    Command::Stats(args) => {
+      theme::set_theme((&args.theme).into());
+      stats::run(&args.config, args.workflow.as_deref())
+    }
+    Command::Validate(args) => {
+      theme::set_theme((&args.theme).into());
+      validate::run(&args.config)
+    }
+    Command::Schema => schema::run(),
+    Command::Workflow(args) => run_workflow(args, &context),
+    Command::External(args) => run_workflow(WorkflowArgs::from_external(args), &context),
   });
 
+  medic_src::report::print();
+
   match result {
     Ok(inner) => inner,
     Err(_) => std::process::exit(1),
   }
 }
 
-fn cleanup() {
-  let _ = console::Term::stdout().show_cursor();
-  let _ = console::Term::stderr().show_cursor();
-  std::process::exit(1);
+fn run_workflow(args: WorkflowArgs, context: &Context) -> AppResult<()> {
+  theme::set_theme((&args.manifest.theme).into());
+  discovery::enter(&args.manifest.config)?;
+  let manifest = Manifest::new(&args.manifest.config)?;
+  manifest.notify_local_override();
+  let flags = args.manifest.into();
+  let mut progress = progress::new(&flags);
+  medic_workflow::run_steps(manifest, &args.name, &mut progress, flags, context)
 }